@@ -0,0 +1,28 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Bot-wide status information, displayed by the `/status` command.
+#[derive(Debug)]
+pub struct BotStatus {
+    started_at: DateTime<Utc>,
+    features: Vec<&'static str>,
+}
+
+impl BotStatus {
+    #[must_use]
+    pub fn new(features: Vec<&'static str>) -> Self {
+        Self {
+            started_at: Utc::now(),
+            features,
+        }
+    }
+
+    #[must_use]
+    pub fn uptime(&self, now: DateTime<Utc>) -> TimeDelta {
+        now - self.started_at
+    }
+
+    #[must_use]
+    pub fn features(&self) -> &[&'static str] {
+        &self.features
+    }
+}
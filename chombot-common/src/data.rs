@@ -1,2 +1,6 @@
 pub const DISCORD_MESSAGE_SIZE_LIMIT: usize = 2000;
 pub const DISCORD_EMBED_FIELD_LIMIT: usize = 25;
+pub const DISCORD_EMBED_TITLE_LIMIT: usize = 256;
+pub const DISCORD_EMBED_FIELD_VALUE_LIMIT: usize = 1024;
+pub const DISCORD_MESSAGE_EMBED_LIMIT: usize = 10;
+pub const DISCORD_MESSAGE_EMBEDS_SIZE_LIMIT: usize = 6000;
@@ -0,0 +1,70 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+
+pub type WatcherHealthRef = Arc<RwLock<WatcherHealth>>;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WatcherHealth {
+    pub last_success: Option<DateTime<Utc>>,
+    pub last_change: Option<DateTime<Utc>>,
+    pub consecutive_errors: u32,
+    pub last_error: Option<String>,
+}
+
+impl WatcherHealth {
+    pub const fn record_success(&mut self, now: DateTime<Utc>, changed: bool) {
+        self.last_success = Some(now);
+        if changed {
+            self.last_change = Some(now);
+        }
+        self.consecutive_errors = 0;
+    }
+
+    /// Records a failed fetch. The error text is kept after subsequent
+    /// successful fetches, so that the last problem can still be inspected.
+    pub fn record_error(&mut self, error: &impl Display) {
+        self.consecutive_errors += 1;
+        self.last_error = Some(error.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::data_watcher::health::WatcherHealth;
+
+    #[test]
+    fn test_watcher_health() {
+        let time_1 = Utc.with_ymd_and_hms(2024, 9, 1, 12, 0, 0).unwrap();
+        let time_2 = Utc.with_ymd_and_hms(2024, 9, 1, 12, 10, 0).unwrap();
+        let mut health = WatcherHealth::default();
+
+        health.record_success(time_1, true);
+        health.record_error(&"Could not parse the tournament list");
+        health.record_error(&"Could not fetch the tournament list");
+        assert_eq!(
+            health,
+            WatcherHealth {
+                last_success: Some(time_1),
+                last_change: Some(time_1),
+                consecutive_errors: 2,
+                last_error: Some("Could not fetch the tournament list".to_owned()),
+            }
+        );
+
+        health.record_success(time_2, false);
+        assert_eq!(
+            health,
+            WatcherHealth {
+                last_success: Some(time_2),
+                last_change: Some(time_1),
+                consecutive_errors: 0,
+                last_error: Some("Could not fetch the tournament list".to_owned()),
+            }
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::fmt::{Debug, Display};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::TimeDelta;
+use log::error;
+use poise::futures_util::future::join_all;
+
+use crate::data_watcher::clock::{Clock, SystemClock};
+use crate::data_watcher::health::WatcherHealthRef;
+use crate::data_watcher::registry::{WatcherCommand, WatcherControl};
+use crate::data_watcher::schedule::PollingSchedule;
+use crate::data_watcher::snapshot::DataSnapshotStore;
+
+pub mod clock;
+pub mod health;
+pub mod notifiers;
+pub mod registry;
+pub mod schedule;
+pub mod snapshot;
+
+pub trait WatchableData: Sized {
+    type Diff;
+
+    #[must_use]
+    fn should_notify(&self, new: &Self) -> Option<Self::Diff>;
+
+    fn update(&mut self, new: Self) {
+        *self = new;
+    }
+}
+
+impl<T: WatchableData> WatchableData for Option<T> {
+    type Diff = T::Diff;
+
+    fn should_notify(&self, new: &Self) -> Option<T::Diff> {
+        match (&self, &new) {
+            (Some(o), Some(n)) => o.should_notify(n),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, new: Self) {
+        match (self.as_mut(), new) {
+            (None, new) => *self = new,
+            (Some(_), None) => (),
+            (Some(old), Some(new)) => old.update(new),
+        }
+    }
+}
+
+pub struct DataWatcher<T: WatchableData, H> {
+    previous_data: T,
+    update_notifiers: Vec<Box<dyn DataUpdateNotifier<T>>>,
+    get_next: H,
+    snapshot_store: Option<Box<dyn DataSnapshotStore<T>>>,
+    schedule: PollingSchedule,
+    clock: Arc<dyn Clock>,
+}
+
+impl<T, H, HOut, E> DataWatcher<T, H>
+where
+    T: WatchableData + Send + Sync + Default,
+    T::Diff: Send + Sync,
+    H: (Fn() -> HOut) + Send + Sync,
+    HOut: Future<Output = Result<T, E>> + Send,
+    E: Debug + Display,
+{
+    #[must_use]
+    async fn fetch_data(&self, health: &WatcherHealthRef) -> Option<T> {
+        match (self.get_next)().await {
+            Ok(r) => Some(r),
+            Err(e) => {
+                error!("Error when fetching data: {e:?}");
+                health.write().await.record_error(&e);
+                None
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn new(get_next: H) -> Self {
+        Self {
+            previous_data: Default::default(),
+            update_notifiers: Vec::new(),
+            get_next,
+            snapshot_store: None,
+            schedule: PollingSchedule::default(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Adds a notifier that receives every detected change. The notifiers are
+    /// independent; an error in one of them does not affect the others.
+    #[must_use]
+    pub fn with_notifier(mut self, update_notifier: impl DataUpdateNotifier<T> + 'static) -> Self {
+        self.update_notifiers.push(Box::new(update_notifier));
+        self
+    }
+
+    #[must_use]
+    pub const fn with_schedule(mut self, schedule: PollingSchedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    #[must_use]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Persists the watched data in given store, so that the changes made
+    /// while the bot was not running are not lost.
+    #[must_use]
+    pub fn with_snapshot_store(
+        mut self,
+        snapshot_store: impl DataSnapshotStore<T> + 'static,
+    ) -> Self {
+        self.snapshot_store = Some(Box::new(snapshot_store));
+        self
+    }
+
+    fn load_snapshot(&mut self) {
+        let Some(snapshot_store) = &self.snapshot_store else {
+            return;
+        };
+        match snapshot_store.load() {
+            Ok(Some(data)) => self.previous_data = data,
+            Ok(None) => (),
+            Err(e) => error!("Could not load data snapshot: {e:?}"),
+        }
+    }
+
+    fn save_snapshot(&self) {
+        if let Some(snapshot_store) = &self.snapshot_store {
+            if let Err(e) = snapshot_store.save(&self.previous_data) {
+                error!("Could not save data snapshot: {e:?}");
+            }
+        }
+    }
+
+    async fn notify_all(&self, diff: &T::Diff) {
+        let results = join_all(
+            self.update_notifiers
+                .iter()
+                .map(|update_notifier| update_notifier.notify(diff)),
+        )
+        .await;
+        for result in results {
+            if let Err(e) = result {
+                error!("Could not send data update notification: {e:?}");
+            }
+        }
+    }
+
+    async fn update(&mut self, health: &WatcherHealthRef) {
+        if let Some(new_data) = self.fetch_data(health).await {
+            let diff = self.previous_data.should_notify(&new_data);
+            health
+                .write()
+                .await
+                .record_success(self.clock.now(), diff.is_some());
+            if let Some(diff) = diff {
+                self.notify_all(&diff).await;
+            }
+            self.previous_data.update(new_data);
+            self.save_snapshot();
+        }
+    }
+
+    /// Runs the watcher until it is cancelled through given control handle.
+    pub async fn run(&mut self, mut control: WatcherControl) {
+        self.load_snapshot();
+        let now = self.clock.now();
+        let mut next_fetch = now + delta(self.schedule.initial_delay(now));
+        let mut paused = false;
+        loop {
+            tokio::select! {
+                () = control.cancellation_token.cancelled() => return,
+                () = self.clock.sleep_until(next_fetch), if !paused => (),
+                command = control.commands.recv() => match command {
+                    Some(WatcherCommand::Pause) => {
+                        paused = true;
+                        continue;
+                    }
+                    Some(WatcherCommand::Resume) => {
+                        paused = false;
+                        continue;
+                    }
+                    Some(WatcherCommand::Refresh) => (),
+                    None => return,
+                },
+            }
+
+            // the fetch and the notifications can hang on a slow server, so
+            // the cancellation is checked while they run as well
+            tokio::select! {
+                () = control.cancellation_token.cancelled() => return,
+                () = self.update(&control.health) => (),
+            }
+            let consecutive_errors = control.health.read().await.consecutive_errors;
+            let now = self.clock.now();
+            next_fetch = now + delta(self.schedule.next_delay(consecutive_errors, now));
+        }
+    }
+}
+
+fn delta(duration: Duration) -> TimeDelta {
+    TimeDelta::from_std(duration).unwrap_or(TimeDelta::MAX)
+}
+
+#[async_trait]
+pub trait DataUpdateNotifier<T: WatchableData>: Send + Sync {
+    async fn notify(&self, diff: &T::Diff) -> anyhow::Result<()>;
+}
@@ -0,0 +1,240 @@
+use std::fmt::{self, Debug, Formatter, Write as _};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{error, info};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Url;
+use ring::hmac;
+use serde::Serialize;
+
+use crate::data_watcher::{DataUpdateNotifier, WatchableData};
+use crate::scraping_utils::create_chombot_http_client;
+
+/// Header of the requests sent by `HttpJsonNotifier`, holding the
+/// hex-encoded HMAC-SHA256 of the request body prefixed with `sha256=`.
+pub const SIGNATURE_HEADER: &str = "X-Chombot-Signature";
+
+/// Writes every diff to the application log.
+#[derive(Clone, Debug)]
+pub struct LogNotifier {
+    name: String,
+}
+
+impl LogNotifier {
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+#[async_trait]
+impl<T> DataUpdateNotifier<T> for LogNotifier
+where
+    T: WatchableData + Send + Sync,
+    T::Diff: Debug + Send + Sync,
+{
+    async fn notify(&self, diff: &T::Diff) -> anyhow::Result<()> {
+        info!("{} update: {diff:?}", self.name);
+        Ok(())
+    }
+}
+
+/// Appends every diff to a JSON Lines file, creating it if needed.
+#[derive(Clone, Debug)]
+pub struct JsonFileAppendNotifier {
+    path: PathBuf,
+}
+
+impl JsonFileAppendNotifier {
+    #[must_use]
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonFileEntry<'a, D> {
+    timestamp: DateTime<Utc>,
+    diff: &'a D,
+}
+
+fn append_json_line<D: Serialize>(
+    path: &Path,
+    timestamp: DateTime<Utc>,
+    diff: &D,
+) -> anyhow::Result<()> {
+    let mut line = serde_json::to_string(&JsonFileEntry { timestamp, diff })?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl<T> DataUpdateNotifier<T> for JsonFileAppendNotifier
+where
+    T: WatchableData + Send + Sync,
+    T::Diff: Serialize + Send + Sync,
+{
+    async fn notify(&self, diff: &T::Diff) -> anyhow::Result<()> {
+        append_json_line(&self.path, Utc::now(), diff)
+    }
+}
+
+/// HTTP endpoint the diffs are posted to, along with the secret the requests
+/// are signed with.
+#[derive(Clone, PartialEq, Eq)]
+pub struct HttpEndpoint {
+    pub url: String,
+    pub secret: String,
+}
+
+impl HttpEndpoint {
+    /// Returns the origin of the URL, which is safe to log, unlike the path
+    /// or the query that may contain a token.
+    #[must_use]
+    fn origin(&self) -> String {
+        Url::parse(&self.url).map_or_else(
+            |_| "<invalid URL>".to_owned(),
+            |url| url.origin().ascii_serialization(),
+        )
+    }
+}
+
+/// Omits the secret and everything in the URL but its origin.
+impl Debug for HttpEndpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpEndpoint")
+            .field("origin", &self.origin())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Posts every diff as JSON to the HTTP endpoints.
+///
+/// The body has the same format as the lines written by
+/// `JsonFileAppendNotifier`. The requests are signed (see
+/// `SIGNATURE_HEADER`), so that the endpoints can verify they come from the
+/// bot.
+#[derive(Clone, Debug)]
+pub struct HttpJsonNotifier {
+    endpoints: Vec<HttpEndpoint>,
+    client: reqwest::Client,
+}
+
+impl HttpJsonNotifier {
+    pub fn new(endpoints: Vec<HttpEndpoint>) -> anyhow::Result<Self> {
+        Ok(Self {
+            endpoints,
+            client: create_chombot_http_client()?,
+        })
+    }
+
+    async fn post(&self, endpoint: &HttpEndpoint, body: &[u8]) -> anyhow::Result<()> {
+        self.client
+            .post(&endpoint.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign(&endpoint.secret, body))
+            .body(body.to_vec())
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)?
+            .error_for_status()
+            .map_err(reqwest::Error::without_url)?;
+        Ok(())
+    }
+}
+
+#[must_use]
+fn sign(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let mut signature = "sha256=".to_owned();
+    for byte in hmac::sign(&key, body).as_ref() {
+        let _ = write!(signature, "{byte:02x}");
+    }
+    signature
+}
+
+#[async_trait]
+impl<T> DataUpdateNotifier<T> for HttpJsonNotifier
+where
+    T: WatchableData + Send + Sync,
+    T::Diff: Serialize + Send + Sync,
+{
+    async fn notify(&self, diff: &T::Diff) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(&JsonFileEntry {
+            timestamp: Utc::now(),
+            diff,
+        })?;
+
+        let mut failed_endpoints = 0;
+        for endpoint in &self.endpoints {
+            if let Err(why) = self.post(endpoint, &body).await {
+                error!(
+                    "Could not post the update to {}: {why:?}",
+                    endpoint.origin()
+                );
+                failed_endpoints += 1;
+            }
+        }
+
+        if failed_endpoints > 0 {
+            bail!("Could not post the update to {failed_endpoints} endpoint(s)");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use chrono::{TimeZone, Utc};
+    use tempfile::TempDir;
+
+    use crate::data_watcher::notifiers::{append_json_line, sign, HttpEndpoint};
+
+    #[test]
+    fn test_append_json_line() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("archive.jsonl");
+        let timestamp = Utc.with_ymd_and_hms(2024, 9, 1, 12, 0, 0).unwrap();
+
+        append_json_line(&path, timestamp, &vec!["Krakow Riichi Open"]).unwrap();
+        append_json_line(&path, timestamp, &vec!["ERMC 2025"]).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "{\"timestamp\":\"2024-09-01T12:00:00Z\",\"diff\":[\"Krakow Riichi Open\"]}\n\
+             {\"timestamp\":\"2024-09-01T12:00:00Z\",\"diff\":[\"ERMC 2025\"]}\n"
+        );
+    }
+
+    #[test]
+    fn test_http_endpoint_debug() {
+        let endpoint = HttpEndpoint {
+            url: "https://example.com/hooks/chombot?token=abc".to_owned(),
+            secret: "secret".to_owned(),
+        };
+
+        let debug = format!("{endpoint:?}");
+        assert!(debug.contains("https://example.com"));
+        assert!(!debug.contains("token"));
+        assert!(!debug.contains("secret"));
+    }
+
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            sign("key", b"The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+}
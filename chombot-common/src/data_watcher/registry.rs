@@ -0,0 +1,404 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{error, info};
+use poise::futures_util::future::join_all;
+use poise::BoxFuture;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+
+use crate::data_watcher::health::{WatcherHealth, WatcherHealthRef};
+
+/// Time a cancelled watcher has to finish before it is aborted.
+const DEFAULT_STOP_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Creates the future running a watcher. Called every time the watcher is
+/// (re)started.
+pub type WatcherFactory = Box<dyn Fn(WatcherControl) -> BoxFuture<'static, ()> + Send + Sync>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatcherCommand {
+    Pause,
+    Resume,
+    Refresh,
+}
+
+/// The receiving end of the registry commands, passed to a running watcher.
+pub struct WatcherControl {
+    pub(crate) commands: UnboundedReceiver<WatcherCommand>,
+    pub(crate) cancellation_token: CancellationToken,
+    pub(crate) health: WatcherHealthRef,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatcherState {
+    Running,
+    Paused,
+    Stopped,
+}
+
+impl Display for WatcherState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Running => write!(f, "running"),
+            Self::Paused => write!(f, "paused"),
+            Self::Stopped => write!(f, "stopped"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WatcherInfo {
+    pub name: String,
+    pub state: WatcherState,
+    pub health: WatcherHealth,
+}
+
+#[derive(Debug)]
+pub enum WatcherRegistryError {
+    UnknownWatcher(String),
+    WatcherNotRunning(String),
+}
+
+impl Display for WatcherRegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownWatcher(name) => write!(f, "Unknown watcher: {name}"),
+            Self::WatcherNotRunning(name) => write!(f, "Watcher is not running: {name}"),
+        }
+    }
+}
+
+impl Error for WatcherRegistryError {}
+
+type WatcherRegistryResult<T> = Result<T, WatcherRegistryError>;
+
+struct RunningWatcher {
+    commands: UnboundedSender<WatcherCommand>,
+    cancellation_token: CancellationToken,
+    handle: JoinHandle<()>,
+    paused: bool,
+}
+
+impl RunningWatcher {
+    /// Cancels the watcher and waits for it to finish, aborting it if it
+    /// does not finish within the grace period.
+    async fn stop(self, name: &str, grace_period: Duration) {
+        info!("Stopping watcher: {name}");
+        self.cancellation_token.cancel();
+        let mut handle = self.handle;
+        match timeout(grace_period, &mut handle).await {
+            Ok(Ok(())) => (),
+            Ok(Err(e)) => error!("Watcher {name} finished abnormally: {e:?}"),
+            Err(_) => {
+                error!("Watcher {name} did not stop in time; aborting it");
+                handle.abort();
+            }
+        }
+    }
+}
+
+struct RegisteredWatcher {
+    name: String,
+    factory: WatcherFactory,
+    health: WatcherHealthRef,
+    running: Option<RunningWatcher>,
+}
+
+impl RegisteredWatcher {
+    fn start(&mut self) {
+        let (commands_sender, commands_receiver) = unbounded_channel();
+        let cancellation_token = CancellationToken::new();
+        let control = WatcherControl {
+            commands: commands_receiver,
+            cancellation_token: cancellation_token.clone(),
+            health: self.health.clone(),
+        };
+
+        info!("Starting watcher: {}", self.name);
+        let handle = tokio::spawn((self.factory)(control));
+        self.running = Some(RunningWatcher {
+            commands: commands_sender,
+            cancellation_token,
+            handle,
+            paused: false,
+        });
+    }
+
+    fn state(&self) -> WatcherState {
+        match &self.running {
+            Some(running) if !running.handle.is_finished() => {
+                if running.paused {
+                    WatcherState::Paused
+                } else {
+                    WatcherState::Running
+                }
+            }
+            _ => WatcherState::Stopped,
+        }
+    }
+
+    fn send(&mut self, command: WatcherCommand) -> WatcherRegistryResult<()> {
+        let running = self
+            .running
+            .as_mut()
+            .filter(|running| !running.handle.is_finished())
+            .ok_or_else(|| WatcherRegistryError::WatcherNotRunning(self.name.clone()))?;
+        running
+            .commands
+            .send(command)
+            .map_err(|_| WatcherRegistryError::WatcherNotRunning(self.name.clone()))?;
+
+        match command {
+            WatcherCommand::Pause => running.paused = true,
+            WatcherCommand::Resume => running.paused = false,
+            WatcherCommand::Refresh => (),
+        }
+        Ok(())
+    }
+}
+
+/// Owns all the data watcher tasks and allows to control them at runtime.
+pub struct WatcherRegistry {
+    watchers: Mutex<Vec<RegisteredWatcher>>,
+    stop_grace_period: Duration,
+}
+
+impl Default for WatcherRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WatcherRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            watchers: Mutex::default(),
+            stop_grace_period: DEFAULT_STOP_GRACE_PERIOD,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_stop_grace_period(mut self, stop_grace_period: Duration) -> Self {
+        self.stop_grace_period = stop_grace_period;
+        self
+    }
+
+    /// Registers and immediately starts a new watcher.
+    pub async fn register(
+        &self,
+        name: impl Into<String>,
+        factory: impl Fn(WatcherControl) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    ) {
+        let mut watcher = RegisteredWatcher {
+            name: name.into(),
+            factory: Box::new(factory),
+            health: Arc::new(RwLock::new(WatcherHealth::default())),
+            running: None,
+        };
+        watcher.start();
+        self.watchers.lock().await.push(watcher);
+    }
+
+    pub async fn list(&self) -> Vec<WatcherInfo> {
+        let watchers = self.watchers.lock().await;
+        let mut infos = Vec::with_capacity(watchers.len());
+        for watcher in watchers.iter() {
+            infos.push(WatcherInfo {
+                name: watcher.name.clone(),
+                state: watcher.state(),
+                health: watcher.health.read().await.clone(),
+            });
+        }
+        drop(watchers);
+
+        infos
+    }
+
+    pub async fn names(&self) -> Vec<String> {
+        self.watchers
+            .lock()
+            .await
+            .iter()
+            .map(|watcher| watcher.name.clone())
+            .collect()
+    }
+
+    pub async fn pause(&self, name: &str) -> WatcherRegistryResult<()> {
+        self.send(name, WatcherCommand::Pause).await
+    }
+
+    pub async fn resume(&self, name: &str) -> WatcherRegistryResult<()> {
+        self.send(name, WatcherCommand::Resume).await
+    }
+
+    /// Makes the watcher fetch the data immediately.
+    pub async fn refresh(&self, name: &str) -> WatcherRegistryResult<()> {
+        self.send(name, WatcherCommand::Refresh).await
+    }
+
+    /// Stops the watcher (if it is running) and starts it again.
+    ///
+    /// The registry is not locked while waiting for the watcher to stop, so
+    /// that the other watchers can be controlled in the meantime.
+    pub async fn restart(&self, name: &str) -> WatcherRegistryResult<()> {
+        let running = Self::find(&mut self.watchers.lock().await, name)?
+            .running
+            .take();
+        if let Some(running) = running {
+            running.stop(name, self.stop_grace_period).await;
+        }
+
+        let mut watchers = self.watchers.lock().await;
+        let watcher = Self::find(&mut watchers, name)?;
+        if watcher.running.is_none() {
+            watcher.start();
+        }
+        drop(watchers);
+
+        Ok(())
+    }
+
+    /// Stops all the watchers and waits for them to finish.
+    pub async fn shutdown(&self) {
+        let running: Vec<_> = self
+            .watchers
+            .lock()
+            .await
+            .iter_mut()
+            .filter_map(|watcher| {
+                let running = watcher.running.take()?;
+                Some((watcher.name.clone(), running))
+            })
+            .collect();
+        join_all(running.into_iter().map(|(name, running)| async move {
+            running.stop(&name, self.stop_grace_period).await;
+        }))
+        .await;
+    }
+
+    async fn send(&self, name: &str, command: WatcherCommand) -> WatcherRegistryResult<()> {
+        let mut watchers = self.watchers.lock().await;
+        Self::find(&mut watchers, name)?.send(command)
+    }
+
+    fn find<'a>(
+        watchers: &'a mut [RegisteredWatcher],
+        name: &str,
+    ) -> WatcherRegistryResult<&'a mut RegisteredWatcher> {
+        watchers
+            .iter_mut()
+            .find(|watcher| watcher.name == name)
+            .ok_or_else(|| WatcherRegistryError::UnknownWatcher(name.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use poise::BoxFuture;
+    use tokio::sync::Mutex;
+
+    use crate::data_watcher::registry::{
+        WatcherCommand, WatcherControl, WatcherRegistry, WatcherState,
+    };
+
+    type ReceivedCommands = Arc<Mutex<Vec<(usize, WatcherCommand)>>>;
+
+    fn recording_watcher(
+        received: ReceivedCommands,
+    ) -> impl Fn(WatcherControl) -> BoxFuture<'static, ()> {
+        let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        move |mut control| {
+            let run = runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let received = received.clone();
+            Box::pin(async move {
+                loop {
+                    tokio::select! {
+                        biased;
+                        command = control.commands.recv() => match command {
+                            Some(command) => received.lock().await.push((run, command)),
+                            None => break,
+                        },
+                        () = control.cancellation_token.cancelled() => break,
+                    }
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn test_watcher_registry() -> std::io::Result<()> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()?
+            .block_on(async {
+                let received = ReceivedCommands::default();
+                let registry = WatcherRegistry::new();
+                registry
+                    .register("tournaments", recording_watcher(received.clone()))
+                    .await;
+
+                registry.pause("tournaments").await.unwrap();
+                assert_eq!(registry.list().await[0].state, WatcherState::Paused);
+                registry.resume("tournaments").await.unwrap();
+                assert_eq!(registry.list().await[0].state, WatcherState::Running);
+                registry.restart("tournaments").await.unwrap();
+                registry.refresh("tournaments").await.unwrap();
+                assert!(registry.refresh("ranking").await.is_err());
+
+                registry.shutdown().await;
+                assert_eq!(registry.list().await[0].state, WatcherState::Stopped);
+                assert!(registry.refresh("tournaments").await.is_err());
+
+                assert_eq!(
+                    *received.lock().await,
+                    vec![
+                        (0, WatcherCommand::Pause),
+                        (0, WatcherCommand::Resume),
+                        (1, WatcherCommand::Refresh),
+                    ]
+                );
+            });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stopping_stuck_watcher() -> std::io::Result<()> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()?
+            .block_on(async {
+                let received = ReceivedCommands::default();
+                let registry =
+                    WatcherRegistry::new().with_stop_grace_period(Duration::from_millis(10));
+                // ignores the cancellation, like a watcher stuck on a request
+                registry
+                    .register("tournaments", |_| Box::pin(std::future::pending()))
+                    .await;
+                registry
+                    .register("ranking", recording_watcher(received.clone()))
+                    .await;
+
+                registry.restart("tournaments").await.unwrap();
+                registry.pause("ranking").await.unwrap();
+                registry.shutdown().await;
+                assert!(registry
+                    .list()
+                    .await
+                    .iter()
+                    .all(|watcher| watcher.state == WatcherState::Stopped));
+            });
+
+        Ok(())
+    }
+}
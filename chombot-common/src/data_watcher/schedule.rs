@@ -0,0 +1,219 @@
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveTime, TimeDelta, Utc};
+
+const DEFAULT_INTERVAL: Duration = Duration::from_mins(10);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_hours(2);
+/// Maximum fraction of the backoff delay added as a random jitter
+const JITTER_FRACTION: f64 = 0.1;
+
+/// Time range (in UTC) in which no data should be fetched. The range can wrap
+/// around midnight, e.g. 22:00-06:00.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    #[must_use]
+    pub const fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PollingSchedule {
+    interval: Duration,
+    max_backoff: Duration,
+    fetch_immediately: bool,
+    quiet_hours: Option<QuietHours>,
+}
+
+impl Default for PollingSchedule {
+    fn default() -> Self {
+        Self::new(DEFAULT_INTERVAL)
+    }
+}
+
+impl PollingSchedule {
+    #[must_use]
+    pub const fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            fetch_immediately: true,
+            quiet_hours: None,
+        }
+    }
+
+    /// Sets the upper limit of the delay between retries after consecutive
+    /// fetch failures.
+    #[must_use]
+    pub const fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_fetch_immediately(mut self, fetch_immediately: bool) -> Self {
+        self.fetch_immediately = fetch_immediately;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_quiet_hours(mut self, quiet_hours: Option<QuietHours>) -> Self {
+        self.quiet_hours = quiet_hours;
+        self
+    }
+
+    /// Returns the delay before the first fetch.
+    #[must_use]
+    pub fn initial_delay(&self, now: DateTime<Utc>) -> Duration {
+        if self.fetch_immediately {
+            self.skip_quiet_hours(now, Duration::ZERO)
+        } else {
+            self.next_delay(0, now)
+        }
+    }
+
+    /// Returns the delay before the next fetch, given the number of fetches
+    /// that failed in a row.
+    #[must_use]
+    pub fn next_delay(&self, consecutive_failures: u32, now: DateTime<Utc>) -> Duration {
+        self.next_delay_with_jitter(consecutive_failures, now, random_jitter())
+    }
+
+    #[must_use]
+    fn next_delay_with_jitter(
+        &self,
+        consecutive_failures: u32,
+        now: DateTime<Utc>,
+        jitter: f64,
+    ) -> Duration {
+        let delay = if consecutive_failures == 0 {
+            self.interval
+        } else {
+            let backoff = self
+                .interval
+                .saturating_mul(2u32.saturating_pow(consecutive_failures))
+                .min(self.max_backoff);
+            backoff + backoff.mul_f64(JITTER_FRACTION * jitter)
+        };
+
+        self.skip_quiet_hours(now, delay)
+    }
+
+    #[must_use]
+    fn skip_quiet_hours(&self, now: DateTime<Utc>, delay: Duration) -> Duration {
+        let Some(quiet_hours) = self.quiet_hours else {
+            return delay;
+        };
+        let Ok(delay_delta) = TimeDelta::from_std(delay) else {
+            return delay;
+        };
+
+        let next_fetch = now + delay_delta;
+        if !quiet_hours.contains(next_fetch.time()) {
+            return delay;
+        }
+
+        let mut quiet_hours_end = next_fetch.date_naive().and_time(quiet_hours.end).and_utc();
+        if quiet_hours_end <= next_fetch {
+            quiet_hours_end += TimeDelta::days(1);
+        }
+        (quiet_hours_end - now).to_std().unwrap_or(delay)
+    }
+}
+
+/// Returns a random number in the range `[0, 1)`.
+#[must_use]
+fn random_jitter() -> f64 {
+    rand::random()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{DateTime, NaiveTime, TimeZone, Utc};
+
+    use crate::data_watcher::schedule::{PollingSchedule, QuietHours};
+
+    fn time(hour: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 9, 1, hour, min, 0).unwrap()
+    }
+
+    fn naive_time(hour: u32, min: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, min, 0).unwrap()
+    }
+
+    #[test]
+    fn test_initial_delay() {
+        let schedule = PollingSchedule::new(Duration::from_mins(5));
+        assert_eq!(schedule.initial_delay(time(12, 0)), Duration::ZERO);
+
+        let schedule = schedule.with_fetch_immediately(false);
+        assert_eq!(schedule.initial_delay(time(12, 0)), Duration::from_mins(5));
+    }
+
+    #[test]
+    fn test_backoff() {
+        let schedule =
+            PollingSchedule::new(Duration::from_mins(5)).with_max_backoff(Duration::from_mins(30));
+
+        assert_eq!(
+            schedule.next_delay_with_jitter(0, time(12, 0), 1.0),
+            Duration::from_mins(5)
+        );
+        assert_eq!(
+            schedule.next_delay_with_jitter(1, time(12, 0), 0.0),
+            Duration::from_mins(10)
+        );
+        assert_eq!(
+            schedule.next_delay_with_jitter(2, time(12, 0), 0.0),
+            Duration::from_mins(20)
+        );
+        assert_eq!(
+            schedule.next_delay_with_jitter(2, time(12, 0), 1.0),
+            Duration::from_mins(22)
+        );
+        assert_eq!(
+            schedule.next_delay_with_jitter(3, time(12, 0), 0.0),
+            Duration::from_mins(30)
+        );
+        assert_eq!(
+            schedule.next_delay_with_jitter(100, time(12, 0), 0.0),
+            Duration::from_mins(30)
+        );
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        let schedule = PollingSchedule::new(Duration::from_mins(10))
+            .with_quiet_hours(Some(QuietHours::new(naive_time(22, 0), naive_time(6, 0))));
+
+        assert_eq!(
+            schedule.next_delay_with_jitter(0, time(12, 0), 0.0),
+            Duration::from_mins(10)
+        );
+        assert_eq!(
+            schedule.next_delay_with_jitter(0, time(21, 55), 0.0),
+            Duration::from_mins(8 * 60 + 5)
+        );
+        assert_eq!(
+            schedule.next_delay_with_jitter(0, time(2, 0), 0.0),
+            Duration::from_hours(4)
+        );
+        assert_eq!(schedule.initial_delay(time(5, 30)), Duration::from_mins(30));
+    }
+}
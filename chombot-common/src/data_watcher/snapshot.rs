@@ -0,0 +1,91 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+use log::info;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub trait DataSnapshotStore<T>: Send + Sync {
+    /// Loads the last stored snapshot, or `None` if there isn't any yet.
+    fn load(&self) -> anyhow::Result<Option<T>>;
+
+    fn save(&self, data: &T) -> anyhow::Result<()>;
+}
+
+/// Stores the snapshot as a JSON file at given path.
+#[derive(Clone, Debug)]
+pub struct JsonFileSnapshotStore {
+    path: PathBuf,
+}
+
+impl JsonFileSnapshotStore {
+    #[must_use]
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> DataSnapshotStore<T> for JsonFileSnapshotStore {
+    fn load(&self) -> anyhow::Result<Option<T>> {
+        if !self.path.try_exists()? {
+            info!(
+                "Snapshot file {} not found; starting from scratch",
+                self.path.to_string_lossy()
+            );
+            return Ok(None);
+        }
+
+        info!("Loading snapshot file: {}", self.path.to_string_lossy());
+        let file_contents = fs::read_to_string(&self.path)?;
+        Ok(Some(serde_json::from_str(&file_contents)?))
+    }
+
+    /// Writes the snapshot to a temporary file first, so that a crash
+    /// mid-write does not leave a truncated snapshot behind.
+    fn save(&self, data: &T) -> anyhow::Result<()> {
+        let contents = serde_json::to_string(data)?;
+        let tmp_path = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::data_watcher::snapshot::{DataSnapshotStore, JsonFileSnapshotStore};
+    use crate::tournaments_watcher::ema::{TournamentEntry, Tournaments};
+
+    #[test]
+    fn test_json_file_snapshot_store() {
+        let dir = TempDir::new().unwrap();
+        let store = JsonFileSnapshotStore::new(dir.path().join("snapshot.json"));
+
+        let loaded: Option<Option<Tournaments>> = store.load().unwrap();
+        assert_eq!(loaded, None);
+
+        let data = Some(Tournaments(vec![TournamentEntry {
+            name: "Krakow Riichi Open".to_owned(),
+            source: "ema".to_owned(),
+            url: "https://chombo.club".to_owned(),
+            rules: "Riichi".to_owned(),
+            date: "27-31 November 2023".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }]));
+        store.save(&data).unwrap();
+        assert!(!dir.path().join("snapshot.tmp").exists());
+
+        let loaded: Option<Option<Tournaments>> = store.load().unwrap();
+        assert_eq!(loaded, Some(data));
+    }
+}
@@ -1,29 +1,181 @@
-use poise::serenity_prelude::{ChannelId, Context, CreateMessage, Error as SerenityError};
+use std::time::Duration;
+
+use poise::serenity_prelude::{
+    CacheHttp, ChannelId, ChannelType, ComponentInteractionCollector, CreateActionRow,
+    CreateButton, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CreateMessage, Error as SerenityError, Http, Message,
+};
+use poise::CreateReply;
 
 use crate::data::DISCORD_MESSAGE_SIZE_LIMIT;
+use crate::i18n::{fill, Messages};
+use crate::tournaments_watcher::embeds::truncate;
+
+const PAGINATION_TIMEOUT: Duration = Duration::from_mins(15);
+/// Maximum number and length of the matches listed by `match_list`, so that
+/// the reply fits in a Discord message.
+pub const MAX_LISTED_MATCHES: usize = 20;
+const MAX_MATCH_LEN: usize = 80;
 
+/// Sends the text, split into several messages if it is too long, and
+/// returns the sent messages.
 pub async fn send_with_overflow(
     channel_id: ChannelId,
-    ctx: &Context,
+    cache_http: impl CacheHttp,
     text: &str,
-) -> Result<(), SerenityError> {
+) -> Result<Vec<Message>, SerenityError> {
+    let mut messages = Vec::new();
+    for message in split_message(text) {
+        messages.push(
+            channel_id
+                .send_message(&cache_http, CreateMessage::new().content(message))
+                .await?,
+        );
+    }
+
+    Ok(messages)
+}
+
+/// Splits the text at line boundaries into chunks fitting in a Discord
+/// message.
+#[must_use]
+pub fn split_message(text: &str) -> Vec<String> {
+    let mut messages = Vec::new();
     let mut message = String::new();
     for line in text.lines() {
-        if message.len() + line.len() + "\n".len() > DISCORD_MESSAGE_SIZE_LIMIT {
-            channel_id
-                .send_message(ctx, CreateMessage::new().content(&message))
-                .await?;
-            message.clear();
+        if !message.is_empty()
+            && message.len() + line.len() + "\n".len() > DISCORD_MESSAGE_SIZE_LIMIT
+        {
+            messages.push(std::mem::take(&mut message));
         }
 
         message.push_str(line);
         message.push('\n');
     }
     if !message.is_empty() {
-        channel_id
-            .send_message(ctx, CreateMessage::new().content(&message))
+        messages.push(message);
+    }
+
+    messages
+}
+
+/// Returns a bulleted list of the first `MAX_LISTED_MATCHES` matches of an
+/// ambiguous query, followed by the number of the matches left out.
+#[must_use]
+pub fn match_list(matches: &[String], messages: &Messages) -> String {
+    let mut lines: Vec<_> = matches
+        .iter()
+        .take(MAX_LISTED_MATCHES)
+        .map(|name| format!("* {}", truncate(name, MAX_MATCH_LEN)))
+        .collect();
+    if matches.len() > MAX_LISTED_MATCHES {
+        let count = matches.len() - MAX_LISTED_MATCHES;
+        lines.push(fill(
+            messages.more_matches,
+            &[("count", &count.to_string())],
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Publishes the messages to the servers following the channel if it is an
+/// announcement channel; does nothing otherwise.
+pub async fn crosspost_if_announcement(
+    channel_id: ChannelId,
+    http: &Http,
+    messages: &[Message],
+) -> Result<(), SerenityError> {
+    let is_announcement = channel_id
+        .to_channel(http)
+        .await?
+        .guild()
+        .is_some_and(|channel| channel.kind == ChannelType::News);
+    if is_announcement {
+        for message in messages {
+            channel_id.crosspost(http, message.id).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends the first embed as a reply and lets the user switch between all the
+/// embeds using buttons, until the interaction times out.
+pub async fn send_paginated_embeds<U: Sync, E>(
+    ctx: poise::Context<'_, U, E>,
+    pages: &[CreateEmbed],
+) -> Result<(), SerenityError> {
+    let Some(first_page) = pages.first() else {
+        return Ok(());
+    };
+    let mut reply = CreateReply::default().embed(first_page.clone());
+    if pages.len() == 1 {
+        ctx.send(reply).await?;
+        return Ok(());
+    }
+
+    let ctx_id = ctx.id().to_string();
+    let prev_button_id = format!("{ctx_id}prev");
+    let next_button_id = format!("{ctx_id}next");
+    reply = reply.components(vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(&prev_button_id).emoji('◀'),
+        CreateButton::new(&next_button_id).emoji('▶'),
+    ])]);
+    ctx.send(reply).await?;
+
+    let mut current_page = 0;
+    while let Some(press) = ComponentInteractionCollector::new(ctx)
+        .filter({
+            let ctx_id = ctx_id.clone();
+            move |press| press.data.custom_id.starts_with(&ctx_id)
+        })
+        .timeout(PAGINATION_TIMEOUT)
+        .await
+    {
+        if press.data.custom_id == next_button_id {
+            current_page = (current_page + 1) % pages.len();
+        } else if press.data.custom_id == prev_button_id {
+            current_page = current_page.checked_sub(1).unwrap_or(pages.len() - 1);
+        } else {
+            continue;
+        }
+
+        press
+            .create_response(
+                ctx.serenity_context(),
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new().embed(pages[current_page].clone()),
+                ),
+            )
             .await?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::discord_utils::{match_list, MAX_LISTED_MATCHES};
+    use crate::i18n::Language;
+
+    #[test]
+    fn test_match_list() {
+        let messages = Language::English.messages();
+        let names: Vec<_> = (1..=100)
+            .map(|i| format!("Krakow Riichi Open {i}"))
+            .collect();
+
+        assert_eq!(
+            match_list(&names[..2], messages),
+            "* Krakow Riichi Open 1\n* Krakow Riichi Open 2"
+        );
+
+        let list = match_list(&names, messages);
+        let lines: Vec<_> = list.lines().collect();
+        assert_eq!(lines.len(), MAX_LISTED_MATCHES + 1);
+        assert_eq!(lines.last(), Some(&"…and 80 more"));
+
+        let long_names = vec!["Riichi".repeat(100); 100];
+        assert!(match_list(&long_names, messages).chars().count() < 1800);
+    }
+}
@@ -0,0 +1,322 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use anyhow::bail;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::scraping_utils::{absolute_url, cell_text, create_chombot_http_client, label_matches};
+use crate::select_all;
+use crate::tournaments_watcher::rulesets::Ruleset;
+
+const RANK_LABELS: [&str; 4] = ["rank", "position", "pos", "place"];
+const EMA_ID_LABELS: [&str; 3] = ["ema", "id", "number"];
+const LAST_NAME_LABELS: [&str; 3] = ["last", "lastname", "surname"];
+const FIRST_NAME_LABELS: [&str; 2] = ["first", "firstname"];
+const COUNTRY_LABELS: [&str; 3] = ["country", "nation", "nationality"];
+const RATING_LABELS: [&str; 3] = ["rating", "points", "score"];
+const TOURNAMENT_COUNT_LABELS: [&str; 2] = ["tournament", "tournaments"];
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankingEntry {
+    pub rank: u32,
+    pub ema_id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub country: String,
+    pub rating: String,
+    pub tournament_count: String,
+    /// Player's page on the EMA website (empty if not known)
+    pub url: String,
+}
+
+impl RankingEntry {
+    #[must_use]
+    pub fn full_name(&self) -> String {
+        format!("{} {}", self.first_name, self.last_name)
+            .trim()
+            .to_owned()
+    }
+}
+
+/// Players ordered by their rank. The final standings of the tournaments use
+/// the same format, with the tournament points as the rating.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ranking(pub Vec<RankingEntry>);
+
+impl Ranking {
+    #[must_use]
+    pub fn by_ema_id(&self, ema_id: &str) -> Option<&RankingEntry> {
+        let ema_id = ema_id.trim();
+        if ema_id.is_empty() {
+            return None;
+        }
+        self.0.iter().find(|entry| entry.ema_id == ema_id)
+    }
+
+    /// Returns the player with given EMA ID or full name or, if there is
+    /// none, all the players containing given text in their names.
+    #[must_use]
+    pub fn find_players(&self, query: &str) -> Vec<&RankingEntry> {
+        if let Some(entry) = self.by_ema_id(query) {
+            return vec![entry];
+        }
+
+        let query = query.split_whitespace().collect::<Vec<_>>().join(" ");
+        let query = query.to_lowercase();
+        let names = |entry: &RankingEntry| {
+            [
+                entry.full_name().to_lowercase(),
+                format!("{} {}", entry.last_name, entry.first_name).to_lowercase(),
+            ]
+        };
+        let exact: Vec<_> = self
+            .0
+            .iter()
+            .filter(|entry| names(entry).contains(&query))
+            .collect();
+        if !exact.is_empty() {
+            return exact;
+        }
+
+        self.0
+            .iter()
+            .filter(|entry| names(entry).iter().any(|name| name.contains(&query)))
+            .collect()
+    }
+}
+
+/// Indices of the ranking table columns, found using the header labels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ColumnLayout {
+    rank: usize,
+    ema_id: usize,
+    last_name: usize,
+    first_name: usize,
+    country: Option<usize>,
+    rating: usize,
+    tournament_count: Option<usize>,
+}
+
+impl ColumnLayout {
+    #[must_use]
+    fn from_header(cells: &[ElementRef]) -> Option<Self> {
+        let labels: Vec<_> = cells
+            .iter()
+            .map(|cell| cell_text(cell).to_lowercase())
+            .collect();
+        let find = |names: &[&str]| labels.iter().position(|label| label_matches(label, names));
+
+        Some(Self {
+            rank: find(&RANK_LABELS)?,
+            ema_id: find(&EMA_ID_LABELS)?,
+            last_name: find(&LAST_NAME_LABELS)?,
+            first_name: find(&FIRST_NAME_LABELS)?,
+            country: find(&COUNTRY_LABELS),
+            rating: find(&RATING_LABELS)?,
+            tournament_count: find(&TOURNAMENT_COUNT_LABELS),
+        })
+    }
+
+    /// Returns `None` if the row is not a player entry (e.g. a repeated
+    /// header).
+    #[must_use]
+    fn make_entry(&self, cells: &[ElementRef], page_url: &str) -> Option<RankingEntry> {
+        let text = |index: usize| cells.get(index).map(cell_text).unwrap_or_default();
+        let rank = text(self.rank).trim_end_matches('.').parse().ok()?;
+
+        Some(RankingEntry {
+            rank,
+            ema_id: text(self.ema_id),
+            first_name: text(self.first_name),
+            last_name: text(self.last_name),
+            country: self
+                .country
+                .and_then(|index| cells.get(index))
+                .map(country_text)
+                .unwrap_or_default(),
+            rating: text(self.rating),
+            tournament_count: self.tournament_count.map(text).unwrap_or_default(),
+            url: cells
+                .iter()
+                .find_map(|cell| select_all!("a", cell).next())
+                .and_then(|link| link.value().attr("href"))
+                .map(|href| absolute_url(page_url, href))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// The countries are sometimes shown as flags only, named after the country
+/// codes (e.g. "Img/flag/16/pl.png") when they have no title.
+fn country_text(cell: &ElementRef) -> String {
+    let text = cell_text(cell);
+    if !text.is_empty() {
+        return text;
+    }
+
+    select_all!("img", cell)
+        .find_map(|image| {
+            let value = image.value();
+            let title = value.attr("title").or_else(|| value.attr("alt"));
+            title.map(|title| title.trim().to_owned()).or_else(|| {
+                let file_name = value.attr("src")?.rsplit('/').next()?;
+                let code = file_name
+                    .split_once('.')
+                    .map_or(file_name, |(code, _)| code);
+                Some(code.to_uppercase())
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the cells of a ranking row: either a table row, or a
+/// `TCTT_ligne` div with a paragraph per cell (the layout of the EMA
+/// website).
+fn row_cells<'a>(row: &ElementRef<'a>) -> Vec<ElementRef<'a>> {
+    if row.value().name() == "tr" {
+        select_all!("th, td", row).collect()
+    } else {
+        row.children()
+            .filter_map(ElementRef::wrap)
+            .filter(|cell| cell.value().name() == "p")
+            .collect()
+    }
+}
+
+/// Parses the EMA player ranking or the final standings of a tournament; the
+/// links to the players' pages are resolved against the URL of the page.
+pub fn parse_ranking(body: &str, page_url: &str) -> anyhow::Result<Ranking> {
+    let html = Html::parse_document(body);
+
+    let mut layout = None;
+    let mut entries = Vec::new();
+    for row in select_all!("tr, div.TCTT_ligne", html) {
+        let cells = row_cells(&row);
+        match &layout {
+            None => layout = ColumnLayout::from_header(&cells),
+            Some(layout) => entries.extend(layout.make_entry(&cells, page_url)),
+        }
+    }
+
+    if layout.is_none() {
+        bail!("Could not find the header of the EMA ranking table");
+    }
+    Ok(Ranking(entries))
+}
+
+pub async fn get_ranking(ruleset: Ruleset) -> Result<Ranking, RankingFetchError> {
+    let body = create_chombot_http_client()
+        .map_err(RankingFetchError::FetchError)?
+        .get(ruleset.ranking_url())
+        .send()
+        .await
+        .map_err(|err| RankingFetchError::FetchError(err.into()))?
+        .text()
+        .await
+        .map_err(|err| RankingFetchError::FetchError(err.into()))?;
+    parse_ranking(&body, ruleset.ranking_url()).map_err(RankingFetchError::ParseError)
+}
+
+#[derive(Debug)]
+pub enum RankingFetchError {
+    FetchError(anyhow::Error),
+    ParseError(anyhow::Error),
+}
+
+impl Display for RankingFetchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FetchError(err) => {
+                write!(f, "Could not fetch the EMA ranking: {err}")
+            }
+            Self::ParseError(err) => {
+                write!(f, "Could not parse the EMA ranking: {err}")
+            }
+        }
+    }
+}
+
+impl Error for RankingFetchError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::ema_ranking::ranking::{parse_ranking, RankingEntry};
+    use crate::tournaments_watcher::rulesets::Ruleset;
+
+    fn parse_test_ranking() -> crate::ema_ranking::ranking::Ranking {
+        parse_ranking(
+            include_str!("test_data/ranking.html"),
+            Ruleset::Riichi.ranking_url(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_ranking() {
+        let ranking = parse_test_ranking();
+
+        assert_eq!(ranking.0.len(), 4);
+        assert_eq!(
+            ranking.0[1],
+            RankingEntry {
+                rank: 2,
+                ema_id: "10990042".to_owned(),
+                first_name: "Jan".to_owned(),
+                last_name: "Kowalski".to_owned(),
+                country: "Poland".to_owned(),
+                rating: "812.45".to_owned(),
+                tournament_count: "23".to_owned(),
+                url: "http://mahjong-europe.org/ranking/Players/10990042.html".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_ranking_in_ema_layout() {
+        let ranking = parse_ranking(
+            include_str!("test_data/ranking_ema_layout.html"),
+            Ruleset::Riichi.ranking_url(),
+        )
+        .unwrap();
+
+        assert_eq!(ranking.0.len(), 2);
+        assert_eq!(
+            ranking.0[1],
+            RankingEntry {
+                rank: 2,
+                ema_id: "10990042".to_owned(),
+                first_name: "Jan".to_owned(),
+                last_name: "Kowalski".to_owned(),
+                country: "PL".to_owned(),
+                rating: "812.45".to_owned(),
+                tournament_count: "23".to_owned(),
+                url: "http://mahjong-europe.org/ranking/Players/10990042.html".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_ranking_without_table() {
+        let body = "<html><body><p>Maintenance</p></body></html>";
+        assert!(parse_ranking(body, Ruleset::Riichi.ranking_url()).is_err());
+    }
+
+    #[test]
+    fn test_find_players() {
+        let ranking = parse_test_ranking();
+        let names = |query: &str| {
+            ranking
+                .find_players(query)
+                .into_iter()
+                .map(RankingEntry::full_name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names("10990042"), vec!["Jan Kowalski"]);
+        assert_eq!(names("kowalski  JAN"), vec!["Jan Kowalski"]);
+        assert_eq!(names("kowalsk"), vec!["Jan Kowalski", "Anna Kowalska"]);
+        assert_eq!(names("Anna Kowalska"), vec!["Anna Kowalska"]);
+        assert!(names("Nobody").is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::sync::Arc;
+
+use anyhow::bail;
+use async_trait::async_trait;
+use log::error;
+use poise::serenity_prelude::{ChannelId, UserId};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::data_watcher::{DataUpdateNotifier, WatchableData};
+use crate::ema_ranking::ranking::{get_ranking, RankingEntry, RankingFetchError};
+use crate::i18n::{fill, Language, Messages};
+use crate::message_sink::MessageSink;
+use crate::tournaments_watcher::rulesets::Ruleset;
+
+/// Guild member linked to their EMA player ID.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkedPlayer {
+    pub user_id: UserId,
+    pub ema_id: String,
+}
+
+/// Channel to announce the rank changes of given players in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmaRankingTarget {
+    pub channel_id: ChannelId,
+    pub players: Vec<LinkedPlayer>,
+    /// Language the rank changes are announced in
+    pub language: Language,
+}
+
+#[async_trait]
+pub trait EmaRankingTargetProvider: Send + Sync {
+    async fn ema_ranking_targets(&self) -> Vec<EmaRankingTarget>;
+}
+
+#[async_trait]
+impl<T: EmaRankingTargetProvider> EmaRankingTargetProvider for Arc<RwLock<T>> {
+    async fn ema_ranking_targets(&self) -> Vec<EmaRankingTarget> {
+        self.read().await.ema_ranking_targets().await
+    }
+}
+
+/// Ranking entries of the linked players, keyed by the EMA ID. Only the
+/// linked players are watched, so that the diffs (and the snapshots) do not
+/// contain the whole ranking.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedPlayersRanking(pub BTreeMap<String, RankingEntry>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RankChange {
+    pub old: RankingEntry,
+    pub new: RankingEntry,
+}
+
+impl RankChange {
+    #[must_use]
+    fn as_message(&self, user_id: UserId, messages: &Messages) -> String {
+        let direction = if self.new.rank < self.old.rank {
+            messages.rank_up
+        } else {
+            messages.rank_down
+        };
+        let mut str = fill(
+            messages.rank_change,
+            &[
+                ("user", &format!("<@{user_id}>")),
+                ("name", &self.new.full_name()),
+                ("old_rank", &self.old.rank.to_string()),
+                ("new_rank", &self.new.rank.to_string()),
+                ("direction", direction),
+                (
+                    "difference",
+                    &self.old.rank.abs_diff(self.new.rank).to_string(),
+                ),
+                ("rating", &self.new.rating),
+            ],
+        );
+        if !self.new.url.is_empty() {
+            let _ = write!(str, " {}", self.new.url);
+        }
+
+        str
+    }
+}
+
+impl WatchableData for LinkedPlayersRanking {
+    type Diff = Vec<RankChange>;
+
+    /// The players that were linked (or entered the ranking) since the
+    /// previous check are not announced.
+    fn should_notify(&self, new: &Self) -> Option<Self::Diff> {
+        let changes: Vec<_> = new
+            .0
+            .iter()
+            .filter_map(|(ema_id, new_entry)| {
+                let old_entry = self.0.get(ema_id)?;
+                (old_entry.rank != new_entry.rank).then(|| RankChange {
+                    old: old_entry.clone(),
+                    new: new_entry.clone(),
+                })
+            })
+            .collect();
+
+        if changes.is_empty() {
+            None
+        } else {
+            Some(changes)
+        }
+    }
+}
+
+pub async fn get_linked_players_ranking<T: EmaRankingTargetProvider>(
+    target_provider: T,
+    ruleset: Ruleset,
+) -> Result<Option<LinkedPlayersRanking>, RankingFetchError> {
+    let ema_ids: BTreeSet<_> = target_provider
+        .ema_ranking_targets()
+        .await
+        .into_iter()
+        .flat_map(|target| target.players)
+        .map(|player| player.ema_id)
+        .collect();
+    if ema_ids.is_empty() {
+        return Ok(Some(LinkedPlayersRanking::default()));
+    }
+
+    let ranking = get_ranking(ruleset).await?;
+    Ok(Some(LinkedPlayersRanking(
+        ema_ids
+            .into_iter()
+            .filter_map(|ema_id| {
+                let entry = ranking.by_ema_id(&ema_id)?.clone();
+                Some((ema_id, entry))
+            })
+            .collect(),
+    )))
+}
+
+pub struct EmaRankingNotifier<T> {
+    target_provider: T,
+    message_sink: Arc<dyn MessageSink>,
+}
+
+impl<T: EmaRankingTargetProvider> EmaRankingNotifier<T> {
+    #[must_use]
+    pub fn new(target_provider: T, message_sink: Arc<dyn MessageSink>) -> Self {
+        Self {
+            target_provider,
+            message_sink,
+        }
+    }
+}
+
+/// Returns `None` if none of the players has changed their rank.
+#[must_use]
+fn build_message(
+    changes: &[RankChange],
+    players: &[LinkedPlayer],
+    messages: &Messages,
+) -> Option<String> {
+    let lines: Vec<_> = players
+        .iter()
+        .filter_map(|player| {
+            changes
+                .iter()
+                .find(|change| change.new.ema_id == player.ema_id)
+                .map(|change| change.as_message(player.user_id, messages))
+        })
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    let mut str = format!("**{}**\n\n", messages.ema_ranking_update);
+    for line in lines {
+        let _ = writeln!(str, "* {line}");
+    }
+    Some(str)
+}
+
+#[async_trait]
+impl<T: EmaRankingTargetProvider> DataUpdateNotifier<Option<LinkedPlayersRanking>>
+    for EmaRankingNotifier<T>
+{
+    async fn notify(&self, diff: &Vec<RankChange>) -> anyhow::Result<()> {
+        let mut failed_channels = 0;
+        for target in self.target_provider.ema_ranking_targets().await {
+            let Some(text) = build_message(diff, &target.players, target.language.messages())
+            else {
+                continue;
+            };
+            if let Err(why) = self
+                .message_sink
+                .send_message(target.channel_id, &text)
+                .await
+            {
+                error!(
+                    "Could not send EMA ranking update to {}: {why:?}",
+                    target.channel_id
+                );
+                failed_channels += 1;
+            }
+        }
+
+        if failed_channels > 0 {
+            bail!("Could not send EMA ranking update to {failed_channels} channel(s)");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use poise::serenity_prelude::UserId;
+
+    use crate::data_watcher::WatchableData;
+    use crate::ema_ranking::ranking::RankingEntry;
+    use crate::ema_ranking::watcher::{
+        build_message, LinkedPlayer, LinkedPlayersRanking, RankChange,
+    };
+    use crate::i18n::Language;
+
+    fn entry(ema_id: &str, last_name: &str, rank: u32) -> RankingEntry {
+        RankingEntry {
+            rank,
+            ema_id: ema_id.to_owned(),
+            first_name: "Jan".to_owned(),
+            last_name: last_name.to_owned(),
+            country: "Poland".to_owned(),
+            rating: "800.00".to_owned(),
+            tournament_count: "20".to_owned(),
+            url: String::new(),
+        }
+    }
+
+    fn ranking(entries: &[RankingEntry]) -> LinkedPlayersRanking {
+        LinkedPlayersRanking(
+            entries
+                .iter()
+                .map(|entry| (entry.ema_id.clone(), entry.clone()))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    }
+
+    #[test]
+    fn test_rank_changes() {
+        let old = ranking(&[entry("1", "Kowalski", 10), entry("2", "Nowak", 20)]);
+        let new = ranking(&[
+            entry("1", "Kowalski", 8),
+            entry("2", "Nowak", 20),
+            entry("3", "Wiśniewski", 30),
+        ]);
+
+        assert_eq!(old.should_notify(&old), None);
+        assert_eq!(
+            old.should_notify(&new),
+            Some(vec![RankChange {
+                old: entry("1", "Kowalski", 10),
+                new: entry("1", "Kowalski", 8),
+            }])
+        );
+    }
+
+    #[test]
+    fn test_build_message() {
+        let changes = vec![
+            RankChange {
+                old: entry("1", "Kowalski", 10),
+                new: entry("1", "Kowalski", 8),
+            },
+            RankChange {
+                old: entry("2", "Nowak", 20),
+                new: entry("2", "Nowak", 23),
+            },
+        ];
+        let player = |user_id: u64, ema_id: &str| LinkedPlayer {
+            user_id: UserId::new(user_id),
+            ema_id: ema_id.to_owned(),
+        };
+        let english = Language::English.messages();
+
+        assert_eq!(
+            build_message(&changes, &[player(2137, "1"), player(69, "2")], english),
+            Some(
+                "**EMA RANKING UPDATE**\n\n\
+                 * <@2137> (_Jan Kowalski_): rank 10 → 8 (up 2); rating: 800.00\n\
+                 * <@69> (_Jan Nowak_): rank 20 → 23 (down 3); rating: 800.00\n"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            build_message(&changes, &[player(2137, "1")], Language::Polish.messages()),
+            Some(
+                "**AKTUALIZACJA RANKINGU EMA**\n\n\
+                 * <@2137> (_Jan Kowalski_): pozycja 10 → 8 (w górę o 2); rating: 800.00\n"
+                    .to_owned()
+            )
+        );
+        assert_eq!(build_message(&changes, &[player(420, "3")], english), None);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::i18n::Messages;
+
+pub const MESSAGES: Messages = Messages {
+    tournaments_update: "TOURNAMENTS UPDATE",
+    tournament_new: "NEW",
+    tournament_changed: "CHANGED",
+    tournament_cancelled: "CANCELLED",
+    tournament_renamed: "RENAMED",
+    tournament_removed: "REMOVED",
+    website: "Website",
+    rules: "Rules",
+    date: "Date",
+    place: "Place",
+    mers: "MERS",
+    mers_approval: "MERS approval",
+    registration_start: "Registration start",
+    results: "Results",
+    source: "Source",
+    entry_fee: "Entry fee",
+    player_cap: "Player cap",
+    venue: "Venue",
+    contact: "Contact",
+
+    tournament_results: "RESULTS",
+    results_placement: "{ordinal} of {count}",
+
+    tournament_reminder: "TOURNAMENT REMINDER",
+    reminder_today: "today",
+    reminder_tomorrow: "in 1 day",
+    reminder_in_days: "in {days} days",
+    registration_opens: "registration for _{name}_ opens {when} ({date})",
+    tournament_starts: "_{name}_ starts {when} ({date}; {place})",
+
+    ema_ranking_update: "EMA RANKING UPDATE",
+    rank_change: "{user} (_{name}_): rank {old_rank} → {new_rank} ({direction} {difference}); \
+                  rating: {rating}",
+    rank_up: "up",
+    rank_down: "down",
+
+    mahjong_tournaments: "MAHJONG TOURNAMENTS",
+    no_tournaments_match_filters: "No tournaments match given filters.",
+    tournaments_page: "Page {page}/{page_count} • {count} tournaments",
+    no_tournament_matches: "No tournament matches `{name}`.",
+    multiple_tournaments_match: "Multiple tournaments match `{name}`; please be more specific:\n\
+                                 {names}",
+    tournaments_calendar: "Calendar with {count} tournaments; import it into your calendar app.",
+    history_not_recorded: "The tournament history is not recorded.",
+    no_history_matches: "No tournament history matches `{name}`.",
+    history_title: "History of {name}",
+    history_page: "Page {page}/{page_count} • {count} changes",
+    history_listed: "listed: {date}",
+    history_renamed: "renamed from _{old_name}_",
+    history_removed: "removed from the calendar",
+    history_cancelled: "**cancelled**",
+    history_website: "website changed to {url}",
+    history_rules: "rules changed to {rules}",
+    history_date: "date moved to {date}",
+    history_place: "place changed to {place}",
+
+    no_player_matches: "No player in the {ruleset} ranking matches `{query}`.",
+    multiple_players_match: "Multiple players match `{query}`; please be more specific:\n{names}",
+    more_matches: "…and {count} more",
+    ema_ranking: "EMA {ruleset} ranking",
+    rank: "Rank",
+    rating: "Rating",
+    tournaments: "Tournaments",
+    country: "Country",
+    ema_id: "EMA ID",
+
+    non_dealer_tsumo: "Non-dealer tsumo",
+    non_dealer_ron: "Non-dealer ron",
+    dealer_tsumo: "Dealer tsumo",
+    dealer_ron: "Dealer ron",
+    not_available: "N/A",
+    no_hand_for_payment: "No hand produces this payment.",
+
+    chombo_counter: "CHOMBO COUNTER",
+    chombo_added: "Adding chombo for {user}: *{description}*",
+    chombo_entry: "{user} at {timestamp}",
+    weekdays: [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ],
+
+    language_set: "I will speak English in this server.",
+};
@@ -0,0 +1,270 @@
+use chrono::{DateTime, Datelike, TimeZone};
+use clap::ValueEnum;
+use poise::ChoiceParameter;
+use serde::{Deserialize, Serialize};
+
+use crate::{ChombotPoiseContext, ChombotPoiseUserData};
+
+mod en;
+mod pl;
+
+/// Languages the bot messages are available in.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    ChoiceParameter,
+    ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    English,
+    #[name = "Polski"]
+    Polish,
+}
+
+impl Language {
+    #[must_use]
+    pub const fn messages(self) -> &'static Messages {
+        match self {
+            Self::English => &en::MESSAGES,
+            Self::Polish => &pl::MESSAGES,
+        }
+    }
+
+    /// Returns the language of given Discord locale (e.g. `en-GB` or `pl`),
+    /// if the bot speaks it.
+    #[must_use]
+    pub fn from_discord_locale(locale: &str) -> Option<Self> {
+        match locale.split('-').next()? {
+            "en" => Some(Self::English),
+            "pl" => Some(Self::Polish),
+            _ => None,
+        }
+    }
+}
+
+/// Catalogue of the user-facing messages in a single language. The labels
+/// are capitalised; the templates contain `{placeholder}`s filled with
+/// `fill`.
+#[derive(Debug)]
+pub struct Messages {
+    /// Header of the tournament update messages
+    pub tournaments_update: &'static str,
+    pub tournament_new: &'static str,
+    pub tournament_changed: &'static str,
+    pub tournament_cancelled: &'static str,
+    pub tournament_renamed: &'static str,
+    pub tournament_removed: &'static str,
+    pub website: &'static str,
+    pub rules: &'static str,
+    pub date: &'static str,
+    pub place: &'static str,
+    pub mers: &'static str,
+    pub mers_approval: &'static str,
+    pub registration_start: &'static str,
+    pub results: &'static str,
+    pub source: &'static str,
+    pub entry_fee: &'static str,
+    pub player_cap: &'static str,
+    pub venue: &'static str,
+    pub contact: &'static str,
+
+    /// Header of the tournament results announcements
+    pub tournament_results: &'static str,
+    /// Placement of a player; `{ordinal}` (e.g. "2nd"), `{rank}` (e.g. "2")
+    /// and `{count}` placeholders
+    pub results_placement: &'static str,
+
+    /// Header of the tournament reminders
+    pub tournament_reminder: &'static str,
+    pub reminder_today: &'static str,
+    pub reminder_tomorrow: &'static str,
+    /// `{days}` placeholder
+    pub reminder_in_days: &'static str,
+    /// `{name}`, `{when}` and `{date}` placeholders
+    pub registration_opens: &'static str,
+    /// `{name}`, `{when}`, `{date}` and `{place}` placeholders
+    pub tournament_starts: &'static str,
+
+    /// Header of the EMA rank change announcements
+    pub ema_ranking_update: &'static str,
+    /// `{user}`, `{name}`, `{old_rank}`, `{new_rank}`, `{direction}`,
+    /// `{difference}` and `{rating}` placeholders
+    pub rank_change: &'static str,
+    pub rank_up: &'static str,
+    pub rank_down: &'static str,
+
+    pub mahjong_tournaments: &'static str,
+    pub no_tournaments_match_filters: &'static str,
+    /// `{page}`, `{page_count}` and `{count}` placeholders
+    pub tournaments_page: &'static str,
+    /// `{name}` placeholder
+    pub no_tournament_matches: &'static str,
+    /// `{name}` and `{names}` placeholders
+    pub multiple_tournaments_match: &'static str,
+    /// `{count}` placeholder
+    pub tournaments_calendar: &'static str,
+    pub history_not_recorded: &'static str,
+    /// `{name}` placeholder
+    pub no_history_matches: &'static str,
+    /// `{name}` placeholder
+    pub history_title: &'static str,
+    /// `{page}`, `{page_count}` and `{count}` placeholders
+    pub history_page: &'static str,
+    /// `{date}` placeholder
+    pub history_listed: &'static str,
+    /// `{old_name}` placeholder
+    pub history_renamed: &'static str,
+    pub history_removed: &'static str,
+    pub history_cancelled: &'static str,
+    /// `{url}` placeholder
+    pub history_website: &'static str,
+    /// `{rules}` placeholder
+    pub history_rules: &'static str,
+    /// `{date}` placeholder
+    pub history_date: &'static str,
+    /// `{place}` placeholder
+    pub history_place: &'static str,
+
+    /// `{ruleset}` and `{query}` placeholders
+    pub no_player_matches: &'static str,
+    /// `{query}` and `{names}` placeholders
+    pub multiple_players_match: &'static str,
+    /// `{count}` placeholder
+    pub more_matches: &'static str,
+    /// `{ruleset}` placeholder
+    pub ema_ranking: &'static str,
+    pub rank: &'static str,
+    pub rating: &'static str,
+    pub tournaments: &'static str,
+    pub country: &'static str,
+    pub ema_id: &'static str,
+
+    pub non_dealer_tsumo: &'static str,
+    pub non_dealer_ron: &'static str,
+    pub dealer_tsumo: &'static str,
+    pub dealer_ron: &'static str,
+    /// Displayed instead of the points of the impossible hands
+    pub not_available: &'static str,
+    pub no_hand_for_payment: &'static str,
+
+    pub chombo_counter: &'static str,
+    /// `{user}` and `{description}` placeholders
+    pub chombo_added: &'static str,
+    /// `{user}` and `{timestamp}` placeholders
+    pub chombo_entry: &'static str,
+    /// Names of the days of the week, starting with Monday
+    pub weekdays: [&'static str; 7],
+
+    /// Confirmation of the server language change
+    pub language_set: &'static str,
+}
+
+impl Messages {
+    /// Formats the timestamp as e.g. "Saturday, 2025-03-15 14:30".
+    #[must_use]
+    pub fn format_timestamp<Tz: TimeZone>(&self, timestamp: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        let weekday = self.weekdays[timestamp.weekday().num_days_from_monday() as usize];
+        format!("{weekday}, {}", timestamp.format("%Y-%m-%d %H:%M"))
+    }
+}
+
+/// Fills the `{name}` placeholders of the message template. The template is
+/// scanned once, so the placeholders in the filled values are kept as they
+/// are; unknown placeholders are left untouched.
+#[must_use]
+pub fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = rest.find('}').and_then(|end| {
+            let name = &rest[1..end];
+            args.iter()
+                .find(|(arg, _)| *arg == name)
+                .map(|(_, value)| (*value, end))
+        });
+        if let Some((value, end)) = placeholder {
+            message.push_str(value);
+            rest = &rest[end + 1..];
+        } else {
+            message.push('{');
+            rest = &rest[1..];
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+/// Returns the language of the messages sent in response to a command: the
+/// language chosen for the server or, if there is none, the language of the
+/// user's Discord client.
+pub async fn context_language<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+) -> Language {
+    if let Some(language) = ctx.data().guild_language(ctx.guild_id()).await {
+        return language;
+    }
+    ctx.locale()
+        .and_then(Language::from_discord_locale)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::i18n::{fill, Language};
+
+    #[test]
+    fn test_from_discord_locale() {
+        assert_eq!(Language::from_discord_locale("pl"), Some(Language::Polish));
+        assert_eq!(
+            Language::from_discord_locale("en-GB"),
+            Some(Language::English)
+        );
+        assert_eq!(Language::from_discord_locale("de"), None);
+    }
+
+    #[test]
+    fn test_messages() {
+        let timestamp = Utc.with_ymd_and_hms(2025, 3, 15, 14, 30, 0).unwrap();
+        let messages = Language::Polish.messages();
+
+        assert_eq!(
+            messages.format_timestamp(&timestamp),
+            "sobota, 2025-03-15 14:30"
+        );
+        assert_eq!(
+            fill(
+                messages.chombo_added,
+                &[("user", "<@!69>"), ("description", "broke the wall")]
+            ),
+            "Dodaję chombo dla <@!69>: *broke the wall*"
+        );
+    }
+
+    #[test]
+    fn test_fill_in_one_pass() {
+        assert_eq!(
+            fill(
+                "{name} on {date} {unknown}",
+                &[("name", "Open {date}"), ("date", "{name}")]
+            ),
+            "Open {date} on {name} {unknown}"
+        );
+        assert_eq!(fill("{{date}} {", &[("date", "today")]), "{today} {");
+    }
+}
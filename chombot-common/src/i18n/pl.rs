@@ -0,0 +1,92 @@
+use crate::i18n::Messages;
+
+pub const MESSAGES: Messages = Messages {
+    tournaments_update: "AKTUALIZACJA TURNIEJÓW",
+    tournament_new: "NOWY",
+    tournament_changed: "ZMIANA",
+    tournament_cancelled: "ODWOŁANY",
+    tournament_renamed: "NOWA NAZWA",
+    tournament_removed: "USUNIĘTY",
+    website: "Strona",
+    rules: "Zasady",
+    date: "Data",
+    place: "Miejsce",
+    mers: "MERS",
+    mers_approval: "Zatwierdzenie MERS",
+    registration_start: "Start rejestracji",
+    results: "Wyniki",
+    source: "Źródło",
+    entry_fee: "Wpisowe",
+    player_cap: "Limit graczy",
+    venue: "Miejsce turnieju",
+    contact: "Kontakt",
+
+    tournament_results: "WYNIKI",
+    results_placement: "{rank}. miejsce na {count}",
+
+    tournament_reminder: "PRZYPOMNIENIE O TURNIEJU",
+    reminder_today: "dzisiaj",
+    reminder_tomorrow: "jutro",
+    reminder_in_days: "za {days} dni",
+    registration_opens: "rejestracja na _{name}_ rusza {when} ({date})",
+    tournament_starts: "_{name}_ zaczyna się {when} ({date}; {place})",
+
+    ema_ranking_update: "AKTUALIZACJA RANKINGU EMA",
+    rank_change:
+        "{user} (_{name}_): pozycja {old_rank} → {new_rank} ({direction} o {difference}); \
+                  rating: {rating}",
+    rank_up: "w górę",
+    rank_down: "w dół",
+
+    mahjong_tournaments: "TURNIEJE MAHJONGA",
+    no_tournaments_match_filters: "Żaden turniej nie pasuje do podanych filtrów.",
+    tournaments_page: "Strona {page}/{page_count} • liczba turniejów: {count}",
+    no_tournament_matches: "Żaden turniej nie pasuje do `{name}`.",
+    multiple_tournaments_match: "Do `{name}` pasuje wiele turniejów; doprecyzuj:\n{names}",
+    tournaments_calendar: "Kalendarz z turniejami (liczba: {count}); zaimportuj go do swojej \
+                           aplikacji kalendarza.",
+    history_not_recorded: "Historia turniejów nie jest zapisywana.",
+    no_history_matches: "Żadna historia turnieju nie pasuje do `{name}`.",
+    history_title: "Historia: {name}",
+    history_page: "Strona {page}/{page_count} • liczba zmian: {count}",
+    history_listed: "dodany do kalendarza: {date}",
+    history_renamed: "zmiana nazwy z _{old_name}_",
+    history_removed: "usunięty z kalendarza",
+    history_cancelled: "**odwołany**",
+    history_website: "nowa strona: {url}",
+    history_rules: "zmiana zasad na {rules}",
+    history_date: "przeniesiony na {date}",
+    history_place: "nowe miejsce: {place}",
+
+    no_player_matches: "Żaden gracz w rankingu {ruleset} nie pasuje do `{query}`.",
+    multiple_players_match: "Do `{query}` pasuje wielu graczy; doprecyzuj:\n{names}",
+    more_matches: "…i {count} więcej",
+    ema_ranking: "Ranking EMA {ruleset}",
+    rank: "Pozycja",
+    rating: "Rating",
+    tournaments: "Turnieje",
+    country: "Kraj",
+    ema_id: "ID EMA",
+
+    non_dealer_tsumo: "Tsumo nie-dealera",
+    non_dealer_ron: "Ron nie-dealera",
+    dealer_tsumo: "Tsumo dealera",
+    dealer_ron: "Ron dealera",
+    not_available: "b.d.",
+    no_hand_for_payment: "Żadna ręka nie daje takiej wypłaty.",
+
+    chombo_counter: "LICZNIK CHOMBO",
+    chombo_added: "Dodaję chombo dla {user}: *{description}*",
+    chombo_entry: "{user}, {timestamp}",
+    weekdays: [
+        "poniedziałek",
+        "wtorek",
+        "środa",
+        "czwartek",
+        "piątek",
+        "sobota",
+        "niedziela",
+    ],
+
+    language_set: "Na tym serwerze będę mówić po polsku.",
+};
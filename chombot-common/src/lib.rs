@@ -5,38 +5,273 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::missing_panics_doc)]
 
-use poise::serenity_prelude::Context as SerenityContext;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{error, info};
+use poise::serenity_prelude::{GuildId, ShardManager};
 use poise::Context;
+use tokio::net::TcpListener;
 
+use crate::bot_status::BotStatus;
 use crate::chombot::ChombotBase;
+use crate::data_watcher::notifiers::{HttpJsonNotifier, JsonFileAppendNotifier, LogNotifier};
+use crate::data_watcher::registry::WatcherRegistry;
+use crate::data_watcher::schedule::PollingSchedule;
+use crate::data_watcher::snapshot::JsonFileSnapshotStore;
 use crate::data_watcher::DataWatcher;
-use crate::tournaments_watcher::ema::get_rcr_tournaments;
+use crate::ema_ranking::watcher::{
+    get_linked_players_ranking, EmaRankingNotifier, EmaRankingTargetProvider,
+};
+use crate::i18n::Language;
+use crate::message_sink::MessageSink;
+use crate::tournaments_watcher::details::TournamentDetailsScraper;
+use crate::tournaments_watcher::events::TournamentEventsNotifier;
+use crate::tournaments_watcher::history::TournamentHistory;
+use crate::tournaments_watcher::ics_server::TournamentsIcsServer;
 use crate::tournaments_watcher::notifier::{
     TournamentWatcherChannelListProvider, TournamentsChannelMessageNotifier,
 };
+use crate::tournaments_watcher::reminders::{
+    get_tournament_reminder_check, ReminderOffsets, TournamentReminderTargetProvider,
+    TournamentRemindersNotifier,
+};
+use crate::tournaments_watcher::rulesets::Ruleset;
+use crate::tournaments_watcher::sources::TournamentSources;
+use crate::tournaments_watcher::threads::TournamentThreadSink;
+use crate::tournaments_watcher::watched::get_watched_tournaments;
+use crate::tournaments_watcher::webhooks::TournamentsWebhookNotifier;
 
+pub mod bot_status;
 pub mod chombot;
 pub mod data;
 pub mod data_watcher;
 pub mod discord_utils;
+pub mod ema_ranking;
+pub mod i18n;
+pub mod message_sink;
 pub mod scraping_utils;
 pub mod slash_commands;
 pub mod tournaments_watcher;
 
 pub trait ChombotPoiseUserData: Sync {
     fn chombot(&self) -> &ChombotBase;
+
+    fn bot_status(&self) -> &BotStatus;
+
+    fn watcher_registry(&self) -> &WatcherRegistry;
+
+    /// Returns `None` if scraping the tournament websites is disabled.
+    fn tournament_details_scraper(&self) -> Option<&TournamentDetailsScraper>;
+
+    fn tournament_sources(&self) -> &TournamentSources;
+
+    /// Returns `None` if the tournament list changes are not archived.
+    fn tournament_history(&self) -> Option<&TournamentHistory>;
+
+    /// Returns the language chosen for the guild, or `None` if there is none
+    /// (or the command was not used in a guild).
+    fn guild_language(
+        &self,
+        guild_id: Option<GuildId>,
+    ) -> impl Future<Output = Option<Language>> + Send;
 }
 
 pub type ChombotPoiseContext<'a, T> = Context<'a, T, anyhow::Error>;
 
-pub fn start_tournaments_watcher<T: TournamentWatcherChannelListProvider + 'static>(
+pub const TOURNAMENTS_WATCHER_NAME: &str = "tournaments";
+pub const TOURNAMENT_REMINDERS_WATCHER_NAME: &str = "tournament-reminders";
+const TOURNAMENT_REMINDERS_INTERVAL: Duration = Duration::from_hours(1);
+pub const EMA_RANKING_WATCHER_NAME: &str = "ema-ranking";
+const EMA_RANKING_WATCHER_INTERVAL: Duration = Duration::from_hours(6);
+
+#[derive(Clone, Debug, Default)]
+pub struct TournamentsWatcherOptions {
+    /// File storing the last seen tournament list
+    pub snapshot_path: Option<PathBuf>,
+    /// File that every tournament list change is appended to
+    pub archive_path: Option<PathBuf>,
+    /// File storing the threads of the tournaments
+    pub threads_path: Option<PathBuf>,
+    pub schedule: PollingSchedule,
+    /// Scraper used to include the tournament details in the notifications
+    pub details_scraper: Option<Arc<TournamentDetailsScraper>>,
+    /// Whether to announce the final standings of the tournaments
+    pub results_announcements: bool,
+    /// Calendars the tournaments are fetched from
+    pub sources: TournamentSources,
+    /// Number of consecutive fetches a tournament has to be missing from to
+    /// be announced as removed (0 and 1 both mean no grace period)
+    pub removal_grace_fetches: u32,
+    /// Notifier keeping the Discord scheduled events of the tournaments in
+    /// sync
+    pub events_notifier: Option<TournamentEventsNotifier>,
+    /// Threads API used to post the updates in the tournament threads (in
+    /// the forum channels and the channels with the threads enabled)
+    pub thread_sink: Option<Arc<dyn TournamentThreadSink>>,
+    /// Notifier posting the updates to Discord webhooks
+    pub webhook_notifier: Option<TournamentsWebhookNotifier>,
+    /// Notifier posting every tournament list change as JSON to HTTP
+    /// endpoints
+    pub http_notifier: Option<HttpJsonNotifier>,
+}
+
+/// Shuts the bot down gracefully on Ctrl+C or on SIGTERM (sent by Docker and
+/// systemd).
+pub fn spawn_shutdown_handler(shard_manager: Arc<ShardManager>) {
+    tokio::spawn(async move {
+        if let Err(why) = shutdown_signal().await {
+            error!("Could not listen for the shutdown signal: {why:?}");
+            return;
+        }
+        info!("Shutting down");
+        shard_manager.shutdown_all().await;
+    });
+}
+
+#[cfg(unix)]
+async fn shutdown_signal() -> std::io::Result<()> {
+    let mut terminate = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())?;
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => result,
+        _ = terminate.recv() => Ok(()),
+    }
+}
+
+#[cfg(not(unix))]
+async fn shutdown_signal() -> std::io::Result<()> {
+    tokio::signal::ctrl_c().await
+}
+
+pub async fn start_tournaments_watcher<
+    T: TournamentWatcherChannelListProvider + Clone + 'static,
+>(
+    watcher_registry: &WatcherRegistry,
     channel_list_provider: T,
-    ctx: SerenityContext,
+    options: TournamentsWatcherOptions,
+    message_sink: Arc<dyn MessageSink>,
+) {
+    watcher_registry
+        .register(TOURNAMENTS_WATCHER_NAME, move |control| {
+            let mut channel_message_notifier = TournamentsChannelMessageNotifier::new(
+                channel_list_provider.clone(),
+                message_sink.clone(),
+            );
+            if let Some(details_scraper) = &options.details_scraper {
+                channel_message_notifier =
+                    channel_message_notifier.with_details_scraper(details_scraper.clone());
+            }
+            if options.results_announcements {
+                channel_message_notifier = channel_message_notifier.with_results_announcements();
+            }
+            if let Some(thread_sink) = &options.thread_sink {
+                channel_message_notifier =
+                    channel_message_notifier.with_thread_sink(thread_sink.clone());
+            }
+            if let Some(threads_path) = &options.threads_path {
+                channel_message_notifier = channel_message_notifier
+                    .with_thread_store(JsonFileSnapshotStore::new(threads_path.clone()));
+            }
+            let sources = options.sources.clone();
+            let removal_grace_fetches = options.removal_grace_fetches;
+            let mut watcher = DataWatcher::new(move || {
+                get_watched_tournaments(sources.clone(), removal_grace_fetches)
+            })
+            .with_schedule(options.schedule)
+            .with_notifier(channel_message_notifier)
+            .with_notifier(LogNotifier::new(TOURNAMENTS_WATCHER_NAME));
+            if let Some(events_notifier) = &options.events_notifier {
+                watcher = watcher.with_notifier(events_notifier.clone());
+            }
+            if let Some(webhook_notifier) = &options.webhook_notifier {
+                watcher = watcher.with_notifier(webhook_notifier.clone());
+            }
+            if let Some(http_notifier) = &options.http_notifier {
+                watcher = watcher.with_notifier(http_notifier.clone());
+            }
+            if let Some(archive_path) = &options.archive_path {
+                watcher = watcher.with_notifier(JsonFileAppendNotifier::new(archive_path.clone()));
+            }
+            if let Some(snapshot_path) = &options.snapshot_path {
+                watcher =
+                    watcher.with_snapshot_store(JsonFileSnapshotStore::new(snapshot_path.clone()));
+            }
+            Box::pin(async move {
+                watcher.run(control).await;
+            })
+        })
+        .await;
+}
+
+pub async fn start_tournament_reminders_watcher<
+    T: TournamentReminderTargetProvider + Clone + 'static,
+>(
+    watcher_registry: &WatcherRegistry,
+    target_provider: T,
+    offsets: ReminderOffsets,
+    sources: TournamentSources,
+    message_sink: Arc<dyn MessageSink>,
 ) {
-    let notifier = TournamentsChannelMessageNotifier::new(channel_list_provider);
+    watcher_registry
+        .register(TOURNAMENT_REMINDERS_WATCHER_NAME, move |control| {
+            let offsets = offsets.clone();
+            let sources = sources.clone();
+            let mut watcher = DataWatcher::new(move || {
+                get_tournament_reminder_check(offsets.clone(), sources.clone())
+            })
+            .with_schedule(PollingSchedule::new(TOURNAMENT_REMINDERS_INTERVAL))
+            .with_notifier(TournamentRemindersNotifier::new(
+                target_provider.clone(),
+                message_sink.clone(),
+            ))
+            .with_notifier(LogNotifier::new(TOURNAMENT_REMINDERS_WATCHER_NAME));
+            Box::pin(async move {
+                watcher.run(control).await;
+            })
+        })
+        .await;
+}
+
+pub async fn start_ema_ranking_watcher<T: EmaRankingTargetProvider + Clone + 'static>(
+    watcher_registry: &WatcherRegistry,
+    target_provider: T,
+    ruleset: Ruleset,
+    message_sink: Arc<dyn MessageSink>,
+) {
+    watcher_registry
+        .register(EMA_RANKING_WATCHER_NAME, move |control| {
+            let fetch_target_provider = target_provider.clone();
+            let mut watcher = DataWatcher::new(move || {
+                get_linked_players_ranking(fetch_target_provider.clone(), ruleset)
+            })
+            .with_schedule(PollingSchedule::new(EMA_RANKING_WATCHER_INTERVAL))
+            .with_notifier(EmaRankingNotifier::new(
+                target_provider.clone(),
+                message_sink.clone(),
+            ))
+            .with_notifier(LogNotifier::new(EMA_RANKING_WATCHER_NAME));
+            Box::pin(async move {
+                watcher.run(control).await;
+            })
+        })
+        .await;
+}
+
+/// Starts serving the tournament calendar in the iCalendar format in the
+/// background.
+pub async fn start_tournaments_ics_server(
+    addr: SocketAddr,
+    sources: TournamentSources,
+) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    let server = Arc::new(TournamentsIcsServer::new(sources));
     tokio::spawn(async move {
-        DataWatcher::new(notifier, get_rcr_tournaments)
-            .run(&ctx)
-            .await;
+        if let Err(why) = server.serve(listener).await {
+            error!("Tournament calendar server error: {why:?}");
+        }
     });
+    Ok(())
 }
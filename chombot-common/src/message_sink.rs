@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use poise::serenity_prelude::{ChannelId, Context, CreateEmbed, CreateMessage, Http};
+use tokio::sync::Mutex;
+
+use crate::discord_utils::{crosspost_if_announcement, send_with_overflow};
+
+/// Destination for the text messages sent by the bot outside of command
+/// invocations (e.g. data watcher notifications).
+#[async_trait]
+pub trait MessageSink: Send + Sync {
+    async fn send_message(&self, channel_id: ChannelId, text: &str) -> anyhow::Result<()>;
+
+    /// Sends a single message with given text and embeds. The caller is
+    /// responsible for keeping them within the Discord limits.
+    async fn send_embeds(
+        &self,
+        channel_id: ChannelId,
+        text: &str,
+        embeds: Vec<CreateEmbed>,
+    ) -> anyhow::Result<()>;
+}
+
+/// Sends the messages to Discord channels, splitting them if they are too
+/// long. The messages sent to announcement channels are crossposted.
+#[derive(Clone)]
+pub struct DiscordMessageSink {
+    http: Arc<Http>,
+}
+
+impl DiscordMessageSink {
+    #[must_use]
+    pub const fn new(http: Arc<Http>) -> Self {
+        Self { http }
+    }
+}
+
+impl From<&Context> for DiscordMessageSink {
+    fn from(ctx: &Context) -> Self {
+        Self::new(ctx.http.clone())
+    }
+}
+
+#[async_trait]
+impl MessageSink for DiscordMessageSink {
+    async fn send_message(&self, channel_id: ChannelId, text: &str) -> anyhow::Result<()> {
+        let messages = send_with_overflow(channel_id, &self.http, text).await?;
+        crosspost_if_announcement(channel_id, &self.http, &messages).await?;
+        Ok(())
+    }
+
+    async fn send_embeds(
+        &self,
+        channel_id: ChannelId,
+        text: &str,
+        embeds: Vec<CreateEmbed>,
+    ) -> anyhow::Result<()> {
+        let mut message = CreateMessage::new().embeds(embeds);
+        if !text.is_empty() {
+            message = message.content(text);
+        }
+        let message = channel_id.send_message(&self.http, message).await?;
+        crosspost_if_announcement(channel_id, &self.http, &[message]).await?;
+        Ok(())
+    }
+}
+
+/// Keeps the messages in memory; useful for testing.
+#[derive(Debug, Default)]
+pub struct RecordingMessageSink {
+    messages: Mutex<Vec<(ChannelId, String)>>,
+    embeds: Mutex<Vec<(ChannelId, String, Vec<CreateEmbed>)>>,
+}
+
+impl RecordingMessageSink {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn messages(&self) -> Vec<(ChannelId, String)> {
+        self.messages.lock().await.clone()
+    }
+
+    pub async fn embeds(&self) -> Vec<(ChannelId, String, Vec<CreateEmbed>)> {
+        self.embeds.lock().await.clone()
+    }
+}
+
+#[async_trait]
+impl MessageSink for RecordingMessageSink {
+    async fn send_message(&self, channel_id: ChannelId, text: &str) -> anyhow::Result<()> {
+        self.messages
+            .lock()
+            .await
+            .push((channel_id, text.to_owned()));
+        Ok(())
+    }
+
+    async fn send_embeds(
+        &self,
+        channel_id: ChannelId,
+        text: &str,
+        embeds: Vec<CreateEmbed>,
+    ) -> anyhow::Result<()> {
+        self.embeds
+            .lock()
+            .await
+            .push((channel_id, text.to_owned(), embeds));
+        Ok(())
+    }
+}
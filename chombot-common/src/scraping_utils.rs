@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result};
 use itertools::Itertools;
-use reqwest::ClientBuilder;
+use reqwest::{ClientBuilder, Url};
 use scraper::ElementRef;
 
 const USER_AGENT: &str = concat!("chombot/", env!("CARGO_PKG_VERSION"));
@@ -54,6 +54,23 @@ pub fn cell_text(e: &ElementRef) -> String {
     e.text().map(str::trim).join(" ").trim().to_owned()
 }
 
+/// Checks whether any word of the label is one of given names.
+#[must_use]
+pub fn label_matches(label: &str, names: &[&str]) -> bool {
+    label
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| names.contains(&word))
+}
+
+/// Resolves a link found on the page at given URL; returns the link as-is if
+/// it cannot be resolved.
+#[must_use]
+pub fn absolute_url(page_url: &str, href: &str) -> String {
+    Url::parse(page_url)
+        .and_then(|page_url| page_url.join(href.trim()))
+        .map_or_else(|_| href.to_owned(), String::from)
+}
+
 pub fn create_chombot_http_client() -> Result<reqwest::Client> {
     Ok(create_chombot_http_client_base().build()?)
 }
@@ -64,6 +81,6 @@ pub fn create_chombot_http_client_insecure() -> Result<reqwest::Client> {
         .build()?)
 }
 
-fn create_chombot_http_client_base() -> ClientBuilder {
+pub(crate) fn create_chombot_http_client_base() -> ClientBuilder {
     reqwest::Client::builder().user_agent(USER_AGENT)
 }
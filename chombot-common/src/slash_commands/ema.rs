@@ -0,0 +1,88 @@
+use anyhow::Result;
+use poise::serenity_prelude::{Color, CreateEmbed};
+use poise::CreateReply;
+
+use crate::discord_utils::match_list;
+use crate::ema_ranking::ranking::{get_ranking, RankingEntry};
+use crate::i18n::{context_language, fill, Messages};
+use crate::tournaments_watcher::rulesets::Ruleset;
+use crate::{ChombotPoiseContext, ChombotPoiseUserData};
+
+#[poise::command(slash_command, subcommands("player"))]
+pub async fn ema<T: ChombotPoiseUserData>(_: ChombotPoiseContext<'_, T>) -> Result<()> {
+    #![allow(clippy::unused_async)]
+    Ok(())
+}
+
+/// Show the EMA ranking of a player.
+#[poise::command(
+    slash_command,
+    name_localized("pl", "gracz"),
+    description_localized("pl", "Pokaż pozycję gracza w rankingu EMA.")
+)]
+async fn player<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Player name (or its part) or EMA ID"]
+    #[max_length = 100]
+    query: String,
+    #[description = "Ranking ruleset (default: Riichi)"] ruleset: Option<Ruleset>,
+) -> Result<()> {
+    ctx.defer().await?;
+
+    let messages = context_language(ctx).await.messages();
+    let ruleset = ruleset.unwrap_or(Ruleset::Riichi);
+    let ranking = get_ranking(ruleset).await?;
+    match ranking.find_players(&query).as_slice() {
+        [] => {
+            ctx.say(fill(
+                messages.no_player_matches,
+                &[("ruleset", &ruleset.to_string()), ("query", &query)],
+            ))
+            .await?;
+        }
+        [entry] => {
+            ctx.send(CreateReply::default().embed(create_player_embed(entry, ruleset, messages)))
+                .await?;
+        }
+        matching => {
+            let names: Vec<_> = matching
+                .iter()
+                .map(|entry| format!("{} ({})", entry.full_name(), entry.ema_id))
+                .collect();
+            ctx.say(fill(
+                messages.multiple_players_match,
+                &[("query", &query), ("names", &match_list(&names, messages))],
+            ))
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn create_player_embed(entry: &RankingEntry, ruleset: Ruleset, messages: &Messages) -> CreateEmbed {
+    let fields = [
+        (messages.rank, entry.rank.to_string()),
+        (messages.rating, entry.rating.clone()),
+        (messages.tournaments, entry.tournament_count.clone()),
+        (messages.country, entry.country.clone()),
+        (messages.ema_id, entry.ema_id.clone()),
+    ]
+    .into_iter()
+    .filter(|(_, value)| !value.is_empty())
+    .map(|(name, value)| (name, value, true));
+
+    let mut embed = CreateEmbed::new()
+        .title(entry.full_name())
+        .description(fill(
+            messages.ema_ranking,
+            &[("ruleset", &ruleset.to_string())],
+        ))
+        .color(Color::DARK_GREEN)
+        .fields(fields);
+    if !entry.url.is_empty() {
+        embed = embed.url(&entry.url);
+    }
+
+    embed
+}
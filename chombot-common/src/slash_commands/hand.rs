@@ -30,7 +30,11 @@ impl From<Tileset> for TileStyle {
 }
 
 /// Draw a specified hand.
-#[poise::command(slash_command)]
+#[poise::command(
+    slash_command,
+    name_localized("pl", "ręka"),
+    description_localized("pl", "Narysuj podaną rękę.")
+)]
 pub async fn hand<T: ChombotPoiseUserData>(
     ctx: ChombotPoiseContext<'_, T>,
     #[description = "The hand to render"]
@@ -1,2 +1,6 @@
+pub mod ema;
 pub mod hand;
 pub mod score;
+pub mod status;
+pub mod tournaments;
+pub mod watchers;
@@ -1,19 +1,31 @@
-use anyhow::Result;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use itertools::Itertools;
 use log::info;
 use num_bigint::BigInt;
 use poise::serenity_prelude::{Color, CreateEmbed};
 use poise::{ChoiceParameter, CreateReply};
 use riichi_hand::points::{Fu, Han, Honbas, PointsCalculationMode, PointsCustom};
 
+use crate::i18n::{context_language, Messages};
 use crate::{ChombotPoiseContext, ChombotPoiseUserData};
 
 type Points = PointsCustom<BigInt>;
 
+const REVERSE_LOOKUP_MAX_HAN: i32 = 13;
+const REVERSE_LOOKUP_LIMITED_HAN: i32 = 5;
+const REVERSE_LOOKUP_FU: [i32; 11] = [20, 25, 30, 40, 50, 60, 70, 80, 90, 100, 110];
+
 #[derive(Debug, ChoiceParameter, Default)]
 pub enum Mode {
     #[default]
+    #[name_localized("pl", "Domyślny")]
     Default,
+    #[name_localized("pl", "Luźny")]
     Loose,
+    #[name_localized("pl", "Bez limitu")]
     Unlimited,
 }
 
@@ -28,22 +40,32 @@ impl From<Mode> for PointsCalculationMode {
 }
 
 /// Calculate the score for given number of han and fu points.
-#[poise::command(slash_command)]
+#[poise::command(
+    slash_command,
+    name_localized("pl", "punkty"),
+    description_localized("pl", "Policz punkty za rękę o danej liczbie han i fu.")
+)]
 pub async fn score<T: ChombotPoiseUserData>(
     ctx: ChombotPoiseContext<'_, T>,
     #[description = "Number of han points"]
+    #[description_localized("pl", "Liczba han")]
     #[min = -1600]
     #[max = 1600]
     han: i32,
     #[description = "Number of fu points"]
+    #[description_localized("pl", "Liczba fu")]
     #[min = -100000]
     #[max = 100000]
     fu: i32,
     #[description = "Number of honbas (counter sticks)"]
+    #[description_localized("pl", "Liczba honba (patyczków 100 punktów)")]
     #[min = -10000]
     #[max = 10000]
     honbas: Option<i32>,
-    #[description = "Calculating mode"] mode: Option<Mode>,
+    #[description = "Calculating mode"]
+    #[name_localized("pl", "tryb")]
+    #[description_localized("pl", "Tryb liczenia")]
+    mode: Option<Mode>,
 ) -> Result<()> {
     let points_calculation_mode: PointsCalculationMode = mode.unwrap_or_default().into();
 
@@ -51,7 +73,8 @@ pub async fn score<T: ChombotPoiseUserData>(
     let fu = Fu::new(fu);
     let honbas = honbas.map(Honbas::new).unwrap_or_default();
     let points = Points::from_calculated(points_calculation_mode, han, fu, honbas)?;
-    let fields = create_points_embed_fields(&points);
+    let messages = context_language(ctx).await.messages();
+    let fields = create_points_embed_fields(&points, messages);
 
     ctx.send(CreateReply::default().embed(create_points_embed(han, fu, honbas, fields)))
         .await?;
@@ -59,6 +82,41 @@ pub async fn score<T: ChombotPoiseUserData>(
     Ok(())
 }
 
+// A separate command rather than `/score reverse`: Discord does not allow
+// invoking a command that has subcommands, so the latter would move the
+// han/fu calculation from `/score` to e.g. `/score calculate`.
+/// List all han/fu combinations that produce the given payment.
+#[poise::command(
+    slash_command,
+    name_localized("pl", "punkty_odwrotnie"),
+    description_localized("pl", "Pokaż wszystkie kombinacje han i fu dające daną wypłatę.")
+)]
+pub async fn score_reverse<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Payment received, e.g. 7700 or 2000/3900"]
+    #[name_localized("pl", "wypłata")]
+    #[description_localized("pl", "Otrzymana wypłata, np. 7700 albo 2000/3900")]
+    #[max_length = 50]
+    points: String,
+    #[description = "Number of honbas (counter sticks) included in the payment"]
+    #[description_localized("pl", "Liczba honba (patyczków 100 punktów) wliczonych w wypłatę")]
+    #[min = 0]
+    #[max = 100]
+    honbas: Option<i32>,
+) -> Result<()> {
+    let payment: Payment = points.parse()?;
+    let honbas = honbas.map(Honbas::new).unwrap_or_default();
+    let messages = context_language(ctx).await.messages();
+    let fields = create_reverse_embed_fields(&payment, honbas, messages);
+
+    ctx.send(
+        CreateReply::default().embed(create_reverse_embed(&payment, honbas, fields, messages)),
+    )
+    .await?;
+
+    Ok(())
+}
+
 fn create_points_embed(
     han: Han,
     fu: Fu,
@@ -73,28 +131,196 @@ fn create_points_embed(
 
 fn create_points_embed_fields(
     points: &Points,
+    messages: &Messages,
 ) -> impl Iterator<Item = (&'static str, String, bool)> {
     info!("{points:?}");
     [
         (
-            "Non-dealer tsumo",
-            format_ko_tsumo_points(points.ko_tsumo()),
+            messages.non_dealer_tsumo,
+            format_ko_tsumo_points(points.ko_tsumo(), messages),
+            false,
+        ),
+        (
+            messages.non_dealer_ron,
+            format_points(points.ko_ron(), messages),
+            false,
+        ),
+        (
+            messages.dealer_tsumo,
+            format_points(points.oya_tsumo(), messages),
+            false,
+        ),
+        (
+            messages.dealer_ron,
+            format_points(points.oya_ron(), messages),
             false,
         ),
-        ("Non-dealer ron", format_points(points.ko_ron()), false),
-        ("Dealer tsumo", format_points(points.oya_tsumo()), false),
-        ("Dealer ron", format_points(points.oya_ron()), false),
     ]
     .into_iter()
 }
 
-fn format_points(points: Option<BigInt>) -> String {
-    points.map_or_else(|| "N/A".to_owned(), |value| value.to_string())
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Payment {
+    /// Single payment: a ron, or a dealer tsumo paid by everyone
+    Single(BigInt),
+    /// Non-dealer tsumo: paid by the non-dealers and by the dealer
+    Split(BigInt, BigInt),
+}
+
+impl FromStr for Payment {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parse_value = |value: &str| {
+            value
+                .trim()
+                .parse::<BigInt>()
+                .map_err(|_| anyhow!("Invalid payment value: {value}"))
+        };
+
+        match s.split_once('/') {
+            Some((value_ko, value_oya)) => {
+                Ok(Self::Split(parse_value(value_ko)?, parse_value(value_oya)?))
+            }
+            None => Ok(Self::Single(parse_value(s)?)),
+        }
+    }
+}
+
+impl Display for Payment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Single(value) => write!(f, "{value}"),
+            Self::Split(value_ko, value_oya) => write!(f, "{value_ko}/{value_oya}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct HandValue {
+    han: Han,
+    /// `None` for limit hands, where the fu count does not matter
+    fu: Option<Fu>,
+}
+
+impl Display for HandValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.fu {
+            Some(fu) => write!(f, "{} {}", self.han, fu),
+            None => write!(f, "{}", self.han),
+        }
+    }
+}
+
+fn possible_hand_values() -> impl Iterator<Item = HandValue> {
+    (1..=REVERSE_LOOKUP_MAX_HAN).flat_map(|han| {
+        let fu_values: Vec<Option<Fu>> = if han < REVERSE_LOOKUP_LIMITED_HAN {
+            REVERSE_LOOKUP_FU
+                .iter()
+                .map(|&fu| Some(Fu::new(fu)))
+                .collect()
+        } else {
+            vec![None]
+        };
+        fu_values.into_iter().map(move |fu| HandValue {
+            han: Han::new(han),
+            fu,
+        })
+    })
+}
+
+fn find_hand_values(
+    payment: &Payment,
+    honbas: Honbas,
+    messages: &Messages,
+) -> Vec<(&'static str, Vec<HandValue>)> {
+    let mut non_dealer_tsumo = Vec::new();
+    let mut non_dealer_ron = Vec::new();
+    let mut dealer_tsumo = Vec::new();
+    let mut dealer_ron = Vec::new();
+
+    for hand_value in possible_hand_values() {
+        let fu = hand_value
+            .fu
+            .unwrap_or_else(|| Fu::new(REVERSE_LOOKUP_FU[0]));
+        let Ok(points) =
+            Points::from_calculated(PointsCalculationMode::Default, hand_value.han, fu, honbas)
+        else {
+            continue;
+        };
+
+        match payment {
+            Payment::Single(value) => {
+                if points.ko_ron().as_ref() == Some(value) {
+                    non_dealer_ron.push(hand_value);
+                }
+                if points.oya_tsumo().as_ref() == Some(value) {
+                    dealer_tsumo.push(hand_value);
+                }
+                if points.oya_ron().as_ref() == Some(value) {
+                    dealer_ron.push(hand_value);
+                }
+            }
+            Payment::Split(value_ko, value_oya) => {
+                if points.ko_tsumo() == Some((value_ko.clone(), value_oya.clone())) {
+                    non_dealer_tsumo.push(hand_value);
+                }
+            }
+        }
+    }
+
+    [
+        (messages.non_dealer_tsumo, non_dealer_tsumo),
+        (messages.non_dealer_ron, non_dealer_ron),
+        (messages.dealer_tsumo, dealer_tsumo),
+        (messages.dealer_ron, dealer_ron),
+    ]
+    .into_iter()
+    .filter(|(_, hand_values)| !hand_values.is_empty())
+    .collect()
+}
+
+fn create_reverse_embed(
+    payment: &Payment,
+    honbas: Honbas,
+    fields: Vec<(&'static str, String, bool)>,
+    messages: &Messages,
+) -> CreateEmbed {
+    let embed = CreateEmbed::new()
+        .title(format!("**{payment} ({honbas})**"))
+        .color(Color::DARK_GREEN);
+
+    if fields.is_empty() {
+        embed.description(messages.no_hand_for_payment)
+    } else {
+        embed.fields(fields)
+    }
+}
+
+fn create_reverse_embed_fields(
+    payment: &Payment,
+    honbas: Honbas,
+    messages: &Messages,
+) -> Vec<(&'static str, String, bool)> {
+    find_hand_values(payment, honbas, messages)
+        .into_iter()
+        .map(|(name, hand_values)| {
+            let value = hand_values.iter().map(ToString::to_string).join(", ");
+            (name, value, false)
+        })
+        .collect()
+}
+
+fn format_points(points: Option<BigInt>, messages: &Messages) -> String {
+    points.map_or_else(
+        || messages.not_available.to_owned(),
+        |value| value.to_string(),
+    )
 }
 
-fn format_ko_tsumo_points(points: Option<(BigInt, BigInt)>) -> String {
+fn format_ko_tsumo_points(points: Option<(BigInt, BigInt)>, messages: &Messages) -> String {
     match points {
-        None => "N/A".to_owned(),
+        None => messages.not_available.to_owned(),
         Some((value_ko, value_oya)) => format!("{value_ko}/{value_oya}"),
     }
 }
@@ -104,13 +330,14 @@ mod tests {
     use riichi_hand::points::Honbas;
 
     use super::*;
+    use crate::i18n::Language;
 
     macro_rules! test_create_points_embed_fields_impl {
         {$id:ident, $points:expr, $ko_tsumo:expr, $ko_ron:expr, $oya_tsumo:expr, $oya_ron:expr} => {
             #[test]
             fn $id() {
                 let points: Points = $points;
-                let fields: Vec<_> = create_points_embed_fields(&points).collect();
+                let fields: Vec<_> = create_points_embed_fields(&points, Language::English.messages()).collect();
                 assert_eq!(fields, vec![
                     ("Non-dealer tsumo", String::from($ko_tsumo), false),
                     ("Non-dealer ron", String::from($ko_ron), false),
@@ -145,4 +372,104 @@ mod tests {
         "N/A",
         "2400"
     }
+
+    #[test]
+    fn test_create_points_embed_fields_polish() {
+        let points = Points::from_calculated(
+            PointsCalculationMode::Default,
+            Han::new(3),
+            Fu::new(20),
+            Honbas::default(),
+        )
+        .unwrap();
+        let fields: Vec<_> =
+            create_points_embed_fields(&points, Language::Polish.messages()).collect();
+
+        assert_eq!(
+            fields[0],
+            ("Tsumo nie-dealera", "700/1300".to_owned(), false)
+        );
+        assert_eq!(fields[1], ("Ron nie-dealera", "b.d.".to_owned(), false));
+    }
+
+    #[test]
+    fn test_parse_payment() {
+        assert_eq!(
+            "7700".parse::<Payment>().unwrap(),
+            Payment::Single(BigInt::from(7700))
+        );
+        assert_eq!(
+            " 2000 / 3900 ".parse::<Payment>().unwrap(),
+            Payment::Split(BigInt::from(2000), BigInt::from(3900))
+        );
+        assert!("abc".parse::<Payment>().is_err());
+        assert!("2000/".parse::<Payment>().is_err());
+    }
+
+    #[test]
+    fn test_create_reverse_embed_fields_single() {
+        let fields = create_reverse_embed_fields(
+            &"7700".parse().unwrap(),
+            Honbas::default(),
+            Language::English.messages(),
+        );
+        assert_eq!(
+            fields,
+            vec![
+                (
+                    "Non-dealer ron",
+                    "3 han 60 fu, 4 han 30 fu".to_owned(),
+                    false
+                ),
+                ("Dealer ron", "2 han 80 fu, 3 han 40 fu".to_owned(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_reverse_embed_fields_split() {
+        let fields = create_reverse_embed_fields(
+            &"2000/3900".parse().unwrap(),
+            Honbas::default(),
+            Language::English.messages(),
+        );
+        assert_eq!(
+            fields,
+            vec![(
+                "Non-dealer tsumo",
+                "3 han 60 fu, 4 han 30 fu".to_owned(),
+                false
+            )]
+        );
+    }
+
+    #[test]
+    fn test_create_reverse_embed_fields_honbas() {
+        let fields = create_reverse_embed_fields(
+            &"12900".parse().unwrap(),
+            Honbas::new(3),
+            Language::English.messages(),
+        );
+        assert_eq!(
+            fields,
+            vec![
+                ("Non-dealer ron", "6 han, 7 han".to_owned(), false),
+                (
+                    "Dealer ron",
+                    "3 han 70 fu, 3 han 80 fu, 3 han 90 fu, 3 han 100 fu, 3 han 110 fu, 4 han 40 fu, 4 han 50 fu, 4 han 60 fu, 4 han 70 fu, 4 han 80 fu, 4 han 90 fu, 4 han 100 fu, 4 han 110 fu, 5 han".to_owned(),
+                    false
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_reverse_embed_fields_no_match() {
+        let fields = create_reverse_embed_fields(
+            &"1234".parse().unwrap(),
+            Honbas::default(),
+            Language::English.messages(),
+        );
+        assert!(fields.is_empty());
+    }
 }
@@ -0,0 +1,132 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use chrono::{DateTime, TimeDelta, Utc};
+use poise::serenity_prelude::{Color, CreateEmbed};
+use poise::CreateReply;
+
+use crate::data_watcher::health::WatcherHealth;
+use crate::{ChombotPoiseContext, ChombotPoiseUserData};
+
+const LAST_ERROR_MAX_LENGTH: usize = 500;
+
+/// Display the bot and data watchers status.
+#[poise::command(slash_command, guild_only, owners_only)]
+pub async fn status<T: ChombotPoiseUserData>(ctx: ChombotPoiseContext<'_, T>) -> Result<()> {
+    let bot_status = ctx.data().bot_status();
+    let guild_count = ctx.serenity_context().cache.guild_count();
+    let features = if bot_status.features().is_empty() {
+        "none".to_owned()
+    } else {
+        bot_status.features().join(", ")
+    };
+
+    let mut fields = vec![
+        (
+            "Uptime".to_owned(),
+            format_uptime(bot_status.uptime(Utc::now())),
+            true,
+        ),
+        ("Guilds".to_owned(), guild_count.to_string(), true),
+        ("Features".to_owned(), features, false),
+    ];
+    for watcher in ctx.data().watcher_registry().list().await {
+        fields.push((
+            format!("{} ({})", watcher.name, watcher.state),
+            format_watcher_health(&watcher.health),
+            false,
+        ));
+    }
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::new()
+                .title("**BOT STATUS**")
+                .color(Color::BLUE)
+                .fields(fields),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[must_use]
+fn format_uptime(uptime: TimeDelta) -> String {
+    let days = uptime.num_days();
+    let hours = uptime.num_hours() % 24;
+    let minutes = uptime.num_minutes() % 60;
+    if days > 0 {
+        format!("{days}d {hours}h {minutes}m")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+#[must_use]
+fn format_timestamp(timestamp: Option<DateTime<Utc>>) -> String {
+    timestamp.map_or_else(
+        || "never".to_owned(),
+        |timestamp| format!("<t:{}:R>", timestamp.timestamp()),
+    )
+}
+
+#[must_use]
+pub(crate) fn format_watcher_health(health: &WatcherHealth) -> String {
+    let mut str = String::new();
+    let _ = writeln!(
+        str,
+        "Last successful fetch: {}",
+        format_timestamp(health.last_success)
+    );
+    let _ = writeln!(
+        str,
+        "Last change detected: {}",
+        format_timestamp(health.last_change)
+    );
+    let _ = writeln!(str, "Consecutive errors: {}", health.consecutive_errors);
+    if let Some(last_error) = &health.last_error {
+        let last_error: String = last_error.chars().take(LAST_ERROR_MAX_LENGTH).collect();
+        let _ = writeln!(str, "Last error: `{last_error}`");
+    }
+
+    str
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    use crate::data_watcher::health::WatcherHealth;
+    use crate::slash_commands::status::{format_uptime, format_watcher_health};
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(format_uptime(TimeDelta::seconds(59)), "0m");
+        assert_eq!(format_uptime(TimeDelta::minutes(150)), "2h 30m");
+        assert_eq!(
+            format_uptime(TimeDelta::days(3) + TimeDelta::minutes(5)),
+            "3d 0h 5m"
+        );
+    }
+
+    #[test]
+    fn test_format_watcher_health() {
+        let health = WatcherHealth {
+            last_success: Some(Utc.with_ymd_and_hms(2024, 9, 1, 12, 0, 0).unwrap()),
+            last_change: None,
+            consecutive_errors: 3,
+            last_error: Some("Could not parse the tournament list".to_owned()),
+        };
+
+        assert_eq!(
+            format_watcher_health(&health),
+            "Last successful fetch: <t:1725192000:R>\n\
+             Last change detected: never\n\
+             Consecutive errors: 3\n\
+             Last error: `Could not parse the tournament list`\n"
+        );
+    }
+}
@@ -0,0 +1,630 @@
+use std::cmp::Reverse;
+
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate, Utc};
+use log::error;
+use poise::serenity_prelude::{Color, CreateAttachment, CreateEmbed, CreateEmbedFooter};
+use poise::{ChoiceParameter, CreateReply};
+
+use crate::discord_utils::{match_list, send_paginated_embeds};
+use crate::i18n::{context_language, fill, Messages};
+use crate::tournaments_watcher::details::TournamentDetails;
+use crate::tournaments_watcher::ema::{TournamentEntry, CALENDAR_URL, EMA_SOURCE_ID};
+use crate::tournaments_watcher::history::{matching_names, tournament_timeline, HistoryEntry};
+use crate::tournaments_watcher::ics::tournaments_as_ics;
+use crate::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+use crate::tournaments_watcher::sources::SourceSubscription;
+use crate::{ChombotPoiseContext, ChombotPoiseUserData};
+
+const TOURNAMENTS_PER_PAGE: usize = 10;
+const HISTORY_ENTRIES_PER_PAGE: usize = 15;
+
+#[poise::command(
+    slash_command,
+    subcommands("list", "info", "ics", "history"),
+    name_localized("pl", "turnieje")
+)]
+pub async fn tournaments<T: ChombotPoiseUserData>(_: ChombotPoiseContext<'_, T>) -> Result<()> {
+    #![allow(clippy::unused_async)]
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ChoiceParameter)]
+pub enum TournamentTime {
+    #[default]
+    Upcoming,
+    Past,
+    All,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ChoiceParameter)]
+pub enum CalendarMonth {
+    January,
+    February,
+    March,
+    April,
+    May,
+    June,
+    July,
+    August,
+    September,
+    October,
+    November,
+    December,
+}
+
+impl CalendarMonth {
+    #[must_use]
+    const fn number(self) -> u32 {
+        self as u32 + 1
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct TournamentFilter {
+    time: TournamentTime,
+    place: Option<String>,
+    month: Option<CalendarMonth>,
+    approval_status: Option<String>,
+    has_results: Option<bool>,
+    source: Option<String>,
+}
+
+impl TournamentFilter {
+    /// Tournaments with a date that could not be parsed are treated as
+    /// upcoming, as this usually means the date has not been decided yet.
+    #[must_use]
+    fn matches(&self, entry: &TournamentEntry, today: NaiveDate) -> bool {
+        let date_range = entry.date_range();
+        let is_past = date_range.is_some_and(|range| range.end < today);
+        let time_matches = match self.time {
+            TournamentTime::Upcoming => !is_past,
+            TournamentTime::Past => is_past,
+            TournamentTime::All => true,
+        };
+        let entry_has_results = !entry.results_status.is_empty();
+        let month_matches = self.month.is_none_or(|month| {
+            date_range.is_some_and(|range| {
+                range.start.month() == month.number() || range.end.month() == month.number()
+            })
+        });
+
+        time_matches
+            && month_matches
+            && contains_ignore_case(&entry.place, self.place.as_deref())
+            && contains_ignore_case(&entry.approval_status, self.approval_status.as_deref())
+            && self
+                .has_results
+                .is_none_or(|has_results| has_results == entry_has_results)
+            && self
+                .source
+                .as_ref()
+                .is_none_or(|source| *source == entry.source)
+    }
+
+    /// Filters the entries and sorts them by date; the past tournaments are
+    /// listed starting with the most recent one.
+    #[must_use]
+    fn apply(&self, entries: Vec<TournamentEntry>, today: NaiveDate) -> Vec<TournamentEntry> {
+        let mut entries: Vec<_> = entries
+            .into_iter()
+            .filter(|entry| self.matches(entry, today))
+            .collect();
+        if self.time == TournamentTime::Past {
+            entries.sort_by_key(|entry| Reverse(entry.date_range()));
+        } else {
+            // tournaments without a known date go last
+            entries.sort_by_key(|entry| (entry.date_range().is_none(), entry.date_range()));
+        }
+
+        entries
+    }
+}
+
+pub async fn autocomplete_tournament_source<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    partial: &str,
+) -> Vec<String> {
+    #![allow(clippy::unused_async)]
+    ctx.data()
+        .tournament_sources()
+        .ids()
+        .into_iter()
+        .filter(|id| id.starts_with(partial))
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+fn contains_ignore_case(value: &str, pattern: Option<&str>) -> bool {
+    pattern.is_none_or(|pattern| value.to_lowercase().contains(&pattern.to_lowercase()))
+}
+
+/// List the tournaments from the EMA calendar and the other sources.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(
+    slash_command,
+    name_localized("pl", "lista"),
+    description_localized("pl", "Pokaż turnieje z kalendarza EMA i innych źródeł.")
+)]
+async fn list<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Upcoming or past tournaments (default: upcoming)"] time: Option<
+        TournamentTime,
+    >,
+    #[description = "Part of the country or city name"] place: Option<String>,
+    #[description = "Month the tournament takes place in"] month: Option<CalendarMonth>,
+    #[description = "Part of the MERS approval status, e.g. \"OK\" or \"pending\""]
+    approval_status: Option<String>,
+    #[description = "Whether the results are available"] has_results: Option<bool>,
+    #[description = "Tournament ruleset (default: Riichi)"] ruleset: Option<Ruleset>,
+    #[description = "Calendar the tournaments come from (default: all)"]
+    #[autocomplete = "autocomplete_tournament_source"]
+    source: Option<String>,
+) -> Result<()> {
+    ctx.defer().await?;
+
+    let messages = context_language(ctx).await.messages();
+    let filter = TournamentFilter {
+        time: time.unwrap_or_default(),
+        place,
+        month,
+        approval_status,
+        has_results,
+        source,
+    };
+    let entries = filter.apply(
+        ctx.data()
+            .tournament_sources()
+            .tournaments()
+            .await?
+            .with_rulesets(&RulesetSubscription::new(vec![
+                ruleset.unwrap_or(Ruleset::Riichi)
+            ]))
+            .0,
+        Utc::now().date_naive(),
+    );
+
+    send_paginated_embeds(ctx, &create_tournament_pages(&entries, messages)).await?;
+
+    Ok(())
+}
+
+#[must_use]
+fn create_tournament_pages(entries: &[TournamentEntry], messages: &Messages) -> Vec<CreateEmbed> {
+    let page_embed = || {
+        CreateEmbed::new()
+            .title(format!("**{}**", messages.mahjong_tournaments))
+            .url(CALENDAR_URL)
+            .color(Color::DARK_GREEN)
+    };
+
+    if entries.is_empty() {
+        return vec![page_embed().description(messages.no_tournaments_match_filters)];
+    }
+
+    let page_count = entries.len().div_ceil(TOURNAMENTS_PER_PAGE).to_string();
+    let count = entries.len().to_string();
+    entries
+        .chunks(TOURNAMENTS_PER_PAGE)
+        .enumerate()
+        .map(|(index, page)| {
+            page_embed()
+                .fields(
+                    page.iter()
+                        .map(|entry| format_tournament_field(entry, messages)),
+                )
+                .footer(CreateEmbedFooter::new(fill(
+                    messages.tournaments_page,
+                    &[
+                        ("page", &(index + 1).to_string()),
+                        ("page_count", &page_count),
+                        ("count", &count),
+                    ],
+                )))
+        })
+        .collect()
+}
+
+#[must_use]
+fn format_tournament_field(entry: &TournamentEntry, messages: &Messages) -> (String, String, bool) {
+    let mut details = vec![entry.date.clone(), entry.place.clone()];
+    if !entry.url.is_empty() {
+        details.push(entry.url.clone());
+    }
+    if !entry.approval_status.is_empty() {
+        details.push(format!("{}: {}", messages.mers, entry.approval_status));
+    }
+    if !entry.registration_start.is_empty() {
+        details.push(format!(
+            "{}: {}",
+            messages.registration_start.to_lowercase(),
+            entry.registration_start
+        ));
+    }
+    if !entry.results_status.is_empty() {
+        details.push(entry.results_status.clone());
+    }
+
+    let name = if entry.source == EMA_SOURCE_ID {
+        entry.name.clone()
+    } else {
+        format!("[{}] {}", entry.source, entry.name)
+    };
+    (name, details.join("; "), false)
+}
+
+/// Show the details of a tournament from the EMA calendar or the other
+/// sources.
+#[poise::command(
+    slash_command,
+    name_localized("pl", "szczegóły"),
+    description_localized("pl", "Pokaż szczegóły turnieju z kalendarza EMA lub innych źródeł.")
+)]
+async fn info<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Tournament name (or its part)"]
+    #[max_length = 100]
+    name: String,
+) -> Result<()> {
+    ctx.defer().await?;
+
+    let messages = context_language(ctx).await.messages();
+    let entries = ctx.data().tournament_sources().tournaments().await?.0;
+    let entry = match find_tournaments(&entries, &name).as_slice() {
+        [] => {
+            ctx.say(fill(messages.no_tournament_matches, &[("name", &name)]))
+                .await?;
+            return Ok(());
+        }
+        [entry] => (*entry).clone(),
+        matching => {
+            let names: Vec<_> = matching.iter().map(|entry| entry.name.clone()).collect();
+            ctx.say(fill(
+                messages.multiple_tournaments_match,
+                &[("name", &name), ("names", &match_list(&names, messages))],
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let details = match (ctx.data().tournament_details_scraper(), entry.url.as_str()) {
+        (Some(details_scraper), url) if !url.is_empty() => {
+            details_scraper.details(url).await.unwrap_or_else(|why| {
+                error!("Could not fetch details of {}: {why:?}", entry.name);
+                TournamentDetails::default()
+            })
+        }
+        _ => TournamentDetails::default(),
+    };
+
+    ctx.send(
+        CreateReply::default().embed(create_tournament_info_embed(&entry, &details, messages)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Export the tournaments as an iCalendar file.
+#[poise::command(
+    slash_command,
+    name_localized("pl", "kalendarz"),
+    description_localized("pl", "Wyeksportuj turnieje do pliku iCalendar.")
+)]
+async fn ics<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Tournament ruleset (default: all)"] ruleset: Option<Ruleset>,
+    #[description = "Calendar the tournaments come from (default: all)"]
+    #[autocomplete = "autocomplete_tournament_source"]
+    source: Option<String>,
+) -> Result<()> {
+    ctx.defer().await?;
+
+    let messages = context_language(ctx).await.messages();
+    let mut tournaments = ctx.data().tournament_sources().tournaments().await?;
+    if let Some(ruleset) = ruleset {
+        tournaments = tournaments.with_rulesets(&RulesetSubscription::new(vec![ruleset]));
+    }
+    if let Some(source) = source {
+        tournaments = tournaments.with_sources(&SourceSubscription::new(vec![source]));
+    }
+    let event_count = tournaments
+        .0
+        .iter()
+        .filter(|entry| entry.date_range().is_some())
+        .count();
+    let ics = tournaments_as_ics(&tournaments.0, Utc::now());
+
+    ctx.send(
+        CreateReply::default()
+            .content(fill(
+                messages.tournaments_calendar,
+                &[("count", &event_count.to_string())],
+            ))
+            .attachment(CreateAttachment::bytes(ics, "tournaments.ics")),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Show the timeline of the changes of a tournament.
+#[poise::command(
+    slash_command,
+    name_localized("pl", "historia"),
+    description_localized("pl", "Pokaż historię zmian turnieju.")
+)]
+async fn history<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Tournament name (or its part)"]
+    #[max_length = 100]
+    name: String,
+) -> Result<()> {
+    let messages = context_language(ctx).await.messages();
+    let Some(tournament_history) = ctx.data().tournament_history() else {
+        ctx.say(messages.history_not_recorded).await?;
+        return Ok(());
+    };
+    ctx.defer().await?;
+
+    let entries = tournament_history.entries().await?;
+    let tournament_name = match matching_names(&entries, &name).as_slice() {
+        [] => {
+            ctx.say(fill(messages.no_history_matches, &[("name", &name)]))
+                .await?;
+            return Ok(());
+        }
+        [tournament_name] => tournament_name.clone(),
+        matching => {
+            ctx.say(fill(
+                messages.multiple_tournaments_match,
+                &[("name", &name), ("names", &match_list(matching, messages))],
+            ))
+            .await?;
+            return Ok(());
+        }
+    };
+
+    let timeline = tournament_timeline(&entries, &tournament_name);
+    send_paginated_embeds(
+        ctx,
+        &create_history_pages(&tournament_name, &timeline, messages),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[must_use]
+fn create_history_pages(
+    name: &str,
+    timeline: &[HistoryEntry],
+    messages: &Messages,
+) -> Vec<CreateEmbed> {
+    let page_count = timeline
+        .len()
+        .div_ceil(HISTORY_ENTRIES_PER_PAGE)
+        .to_string();
+    let count = timeline.len().to_string();
+    timeline
+        .chunks(HISTORY_ENTRIES_PER_PAGE)
+        .enumerate()
+        .map(|(index, page)| {
+            let description = page
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "* <t:{}:f> {}",
+                        entry.timestamp.timestamp(),
+                        entry.describe(messages)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            CreateEmbed::new()
+                .title(fill(messages.history_title, &[("name", name)]))
+                .color(Color::DARK_GREEN)
+                .description(description)
+                .footer(CreateEmbedFooter::new(fill(
+                    messages.history_page,
+                    &[
+                        ("page", &(index + 1).to_string()),
+                        ("page_count", &page_count),
+                        ("count", &count),
+                    ],
+                )))
+        })
+        .collect()
+}
+
+/// Returns the tournament with given name or, if there is none, all the
+/// tournaments containing given text in their names.
+#[must_use]
+fn find_tournaments<'a>(entries: &'a [TournamentEntry], name: &str) -> Vec<&'a TournamentEntry> {
+    let name = name.trim().to_lowercase();
+    if let Some(entry) = entries
+        .iter()
+        .find(|entry| entry.name.to_lowercase() == name)
+    {
+        return vec![entry];
+    }
+
+    entries
+        .iter()
+        .filter(|entry| entry.name.to_lowercase().contains(&name))
+        .collect()
+}
+
+fn create_tournament_info_embed(
+    entry: &TournamentEntry,
+    details: &TournamentDetails,
+    messages: &Messages,
+) -> CreateEmbed {
+    let calendar_fields = [
+        (messages.source, entry.source.clone()),
+        (messages.rules, entry.rules.clone()),
+        (messages.date, entry.date.clone()),
+        (messages.place, entry.place.clone()),
+        (messages.mers, entry.approval_status.clone()),
+        (
+            messages.registration_start,
+            entry.registration_start.clone(),
+        ),
+        (messages.results, entry.results_status.clone()),
+    ];
+    let fields = calendar_fields
+        .into_iter()
+        .chain(details.fields(messages))
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(name, value)| (name, value, true));
+
+    let mut embed = CreateEmbed::new()
+        .title(&entry.name)
+        .color(Color::DARK_GREEN)
+        .fields(fields);
+    if !entry.url.is_empty() {
+        embed = embed.url(&entry.url);
+    }
+
+    embed
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::i18n::Language;
+    use crate::slash_commands::tournaments::{
+        find_tournaments, format_tournament_field, CalendarMonth, TournamentFilter, TournamentTime,
+    };
+    use crate::tournaments_watcher::ema::TournamentEntry;
+
+    fn entry(name: &str, date: &str, place: &str, results_status: &str) -> TournamentEntry {
+        TournamentEntry {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: date.to_owned(),
+            place: place.to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: String::new(),
+            results_status: results_status.to_owned(),
+            results_url: String::new(),
+        }
+    }
+
+    fn names(entries: &[TournamentEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_tournament_filter() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let entries = vec![
+            entry("ERMC 2024", "10-12 August 2024", "Kufstein, Austria", ""),
+            entry(
+                "Poteto Riichi Taikai",
+                "1-2 November 2023",
+                "Paris, France",
+                "Results",
+            ),
+            entry("Krakow Riichi Open", "30-2 June 2024", "Krakow, Poland", ""),
+            entry("Mystery Open", "TBA June 2024", "Warsaw, Poland", ""),
+            entry(
+                "Warsaw Riichi Cup",
+                "1-2 March 2024",
+                "Warsaw, Poland",
+                "Results",
+            ),
+        ];
+
+        let upcoming = TournamentFilter::default().apply(entries.clone(), today);
+        assert_eq!(
+            names(&upcoming),
+            vec!["Krakow Riichi Open", "ERMC 2024", "Mystery Open"]
+        );
+
+        let past = TournamentFilter {
+            time: TournamentTime::Past,
+            ..TournamentFilter::default()
+        };
+        assert_eq!(
+            names(&past.apply(entries.clone(), today)),
+            vec!["Warsaw Riichi Cup", "Poteto Riichi Taikai"]
+        );
+
+        let in_poland_with_results = TournamentFilter {
+            time: TournamentTime::All,
+            place: Some("poland".to_owned()),
+            has_results: Some(true),
+            ..TournamentFilter::default()
+        };
+        assert_eq!(
+            names(&in_poland_with_results.apply(entries.clone(), today)),
+            vec!["Warsaw Riichi Cup"]
+        );
+
+        let in_july = TournamentFilter {
+            month: Some(CalendarMonth::July),
+            ..TournamentFilter::default()
+        };
+        assert_eq!(
+            names(&in_july.apply(entries.clone(), today)),
+            vec!["Krakow Riichi Open"]
+        );
+
+        let mut club_entries = entries;
+        club_entries[1].source = "club".to_owned();
+        let from_club = TournamentFilter {
+            time: TournamentTime::All,
+            source: Some("club".to_owned()),
+            ..TournamentFilter::default()
+        };
+        assert_eq!(
+            names(&from_club.apply(club_entries, today)),
+            vec!["Poteto Riichi Taikai"]
+        );
+    }
+
+    #[test]
+    fn test_format_tournament_field() {
+        let mut entry = entry("ERMC 2024", "10-12 August 2024", "Kufstein, Austria", "");
+        entry.url = "https://ermc2024.at".to_owned();
+        entry.registration_start = "Jan. 1st 2024".to_owned();
+
+        assert_eq!(
+            format_tournament_field(&entry, Language::English.messages()),
+            (
+                "ERMC 2024".to_owned(),
+                "10-12 August 2024; Kufstein, Austria; https://ermc2024.at; MERS: OK; \
+                 registration start: Jan. 1st 2024"
+                    .to_owned(),
+                false
+            )
+        );
+        assert_eq!(
+            format_tournament_field(&entry, Language::Polish.messages()).1,
+            "10-12 August 2024; Kufstein, Austria; https://ermc2024.at; MERS: OK; \
+             start rejestracji: Jan. 1st 2024"
+        );
+    }
+
+    #[test]
+    fn test_find_tournaments() {
+        let entries = vec![
+            entry("Krakow Riichi Open", "27-29 September 2024", "Krakow", ""),
+            entry("Krakow Riichi Open 2", "1-2 November 2024", "Krakow", ""),
+            entry("ERMC 2024", "10-12 August 2024", "Kufstein, Austria", ""),
+        ];
+
+        assert_eq!(
+            find_tournaments(&entries, "krakow riichi open"),
+            vec![&entries[0]]
+        );
+        assert_eq!(find_tournaments(&entries, "krakow").len(), 2);
+        assert_eq!(find_tournaments(&entries, "ermc")[0].name, "ERMC 2024");
+        assert!(find_tournaments(&entries, "WRC").is_empty());
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::Result;
+use poise::serenity_prelude::{Color, CreateEmbed};
+use poise::CreateReply;
+
+use crate::slash_commands::status::format_watcher_health;
+use crate::{ChombotPoiseContext, ChombotPoiseUserData};
+
+#[poise::command(
+    slash_command,
+    guild_only,
+    owners_only,
+    subcommands("list", "pause", "resume", "refresh", "restart")
+)]
+pub async fn watchers<T: ChombotPoiseUserData>(_: ChombotPoiseContext<'_, T>) -> Result<()> {
+    #![allow(clippy::unused_async)]
+    Ok(())
+}
+
+async fn autocomplete_watcher_name<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    partial: &str,
+) -> Vec<String> {
+    ctx.data()
+        .watcher_registry()
+        .names()
+        .await
+        .into_iter()
+        .filter(|name| name.starts_with(partial))
+        .collect()
+}
+
+/// List all data watchers.
+#[poise::command(slash_command, guild_only, owners_only)]
+async fn list<T: ChombotPoiseUserData>(ctx: ChombotPoiseContext<'_, T>) -> Result<()> {
+    let fields = ctx
+        .data()
+        .watcher_registry()
+        .list()
+        .await
+        .into_iter()
+        .map(|watcher| {
+            (
+                format!("{} ({})", watcher.name, watcher.state),
+                format_watcher_health(&watcher.health),
+                false,
+            )
+        });
+
+    ctx.send(
+        CreateReply::default().embed(
+            CreateEmbed::new()
+                .title("**DATA WATCHERS**")
+                .color(Color::BLUE)
+                .fields(fields),
+        ),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Pause a data watcher.
+#[poise::command(slash_command, guild_only, owners_only)]
+async fn pause<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Watcher name"]
+    #[autocomplete = "autocomplete_watcher_name"]
+    name: String,
+) -> Result<()> {
+    ctx.data().watcher_registry().pause(&name).await?;
+    ctx.say(format!("Paused the `{name}` watcher.")).await?;
+
+    Ok(())
+}
+
+/// Resume a paused data watcher.
+#[poise::command(slash_command, guild_only, owners_only)]
+async fn resume<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Watcher name"]
+    #[autocomplete = "autocomplete_watcher_name"]
+    name: String,
+) -> Result<()> {
+    ctx.data().watcher_registry().resume(&name).await?;
+    ctx.say(format!("Resumed the `{name}` watcher.")).await?;
+
+    Ok(())
+}
+
+/// Make a data watcher fetch the data immediately.
+#[poise::command(slash_command, guild_only, owners_only)]
+async fn refresh<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Watcher name"]
+    #[autocomplete = "autocomplete_watcher_name"]
+    name: String,
+) -> Result<()> {
+    ctx.data().watcher_registry().refresh(&name).await?;
+    ctx.say(format!("Requested a refresh of the `{name}` watcher."))
+        .await?;
+
+    Ok(())
+}
+
+/// Restart a data watcher.
+#[poise::command(slash_command, guild_only, owners_only)]
+async fn restart<T: ChombotPoiseUserData>(
+    ctx: ChombotPoiseContext<'_, T>,
+    #[description = "Watcher name"]
+    #[autocomplete = "autocomplete_watcher_name"]
+    name: String,
+) -> Result<()> {
+    ctx.data().watcher_registry().restart(&name).await?;
+    ctx.say(format!("Restarted the `{name}` watcher.")).await?;
+
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use chrono::NaiveTime;
+use clap::Args;
+
+use crate::data_watcher::notifiers::{HttpEndpoint, HttpJsonNotifier};
+use crate::data_watcher::schedule::{PollingSchedule, QuietHours};
+use crate::i18n::Language;
+use crate::tournaments_watcher::reminders::ReminderOffsets;
+use crate::tournaments_watcher::sources::feed::FeedSource;
+use crate::tournaments_watcher::sources::TournamentSources;
+use crate::tournaments_watcher::watched::DEFAULT_REMOVAL_GRACE_FETCHES;
+use crate::tournaments_watcher::webhooks::{DiscordWebhook, TournamentsWebhookNotifier};
+
+#[derive(Args, Clone, Debug)]
+pub struct TournamentsWatcherScheduleArgs {
+    /// Tournaments watcher polling interval (in minutes)
+    #[arg(long, env, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+    pub tournaments_watcher_interval_mins: u64,
+    /// Maximum delay between retries when fetching the tournaments fails (in
+    /// minutes)
+    #[arg(long, env, default_value_t = 120)]
+    pub tournaments_watcher_max_backoff_mins: u64,
+    /// Start of the tournaments watcher quiet hours (UTC, HH:MM)
+    #[arg(long, env, requires = "tournaments_watcher_quiet_hours_end")]
+    pub tournaments_watcher_quiet_hours_start: Option<NaiveTime>,
+    /// End of the tournaments watcher quiet hours (UTC, HH:MM)
+    #[arg(long, env, requires = "tournaments_watcher_quiet_hours_start")]
+    pub tournaments_watcher_quiet_hours_end: Option<NaiveTime>,
+    /// Number of consecutive fetches a tournament has to be missing from to
+    /// be announced as removed
+    #[arg(long, env, default_value_t = DEFAULT_REMOVAL_GRACE_FETCHES)]
+    pub tournaments_watcher_removal_grace_fetches: u32,
+}
+
+impl From<&TournamentsWatcherScheduleArgs> for PollingSchedule {
+    fn from(value: &TournamentsWatcherScheduleArgs) -> Self {
+        let quiet_hours = value
+            .tournaments_watcher_quiet_hours_start
+            .zip(value.tournaments_watcher_quiet_hours_end)
+            .map(|(start, end)| QuietHours::new(start, end));
+
+        Self::new(Duration::from_mins(value.tournaments_watcher_interval_mins))
+            .with_max_backoff(Duration::from_mins(
+                value.tournaments_watcher_max_backoff_mins,
+            ))
+            .with_quiet_hours(quiet_hours)
+    }
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct TournamentRemindersArgs {
+    /// Comma-separated list of how many days before the registration start
+    /// the tournament reminders are sent
+    #[arg(long, env, value_delimiter = ',', default_values_t = [7, 1])]
+    pub tournament_reminders_registration_days: Vec<u32>,
+    /// Comma-separated list of how many days before the tournament start the
+    /// tournament reminders are sent
+    #[arg(long, env, value_delimiter = ',', default_values_t = [1])]
+    pub tournament_reminders_start_days: Vec<u32>,
+}
+
+impl From<&TournamentRemindersArgs> for ReminderOffsets {
+    fn from(value: &TournamentRemindersArgs) -> Self {
+        Self {
+            registration_start_days: value.tournament_reminders_registration_days.clone(),
+            tournament_start_days: value.tournament_reminders_start_days.clone(),
+        }
+    }
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct TournamentSourcesArgs {
+    /// Comma-separated list of tournament feeds (JSON or CSV) watched along
+    /// with the EMA calendar, in the ID=URL format
+    #[arg(long, env, value_delimiter = ',')]
+    pub tournament_feeds: Vec<FeedSource>,
+}
+
+impl From<&TournamentSourcesArgs> for TournamentSources {
+    fn from(value: &TournamentSourcesArgs) -> Self {
+        Self::new().with_feeds(&value.tournament_feeds)
+    }
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct TournamentsIcsArgs {
+    /// Address (e.g. 0.0.0.0:8080) to serve the tournament calendar in the
+    /// iCalendar format at; the calendar is not served if not set
+    #[arg(long, env)]
+    pub tournaments_ics_addr: Option<SocketAddr>,
+}
+
+#[derive(Args, Clone, Debug)]
+pub struct TournamentsWebhooksArgs {
+    /// Comma-separated list of Discord webhook URLs the tournament updates
+    /// are posted to
+    #[arg(long, env, value_delimiter = ',')]
+    pub tournaments_webhook_urls: Vec<String>,
+    /// Name the tournament updates are posted under via the webhooks
+    /// (default: the name of the webhook)
+    #[arg(long, env)]
+    pub tournaments_webhook_username: Option<String>,
+    /// Avatar URL the tournament updates are posted with via the webhooks
+    /// (default: the avatar of the webhook)
+    #[arg(long, env)]
+    pub tournaments_webhook_avatar_url: Option<String>,
+    /// Language the tournament updates are posted in via the webhooks
+    #[arg(long, env, value_enum, default_value_t = Language::English)]
+    pub tournaments_webhook_language: Language,
+    /// Comma-separated list of HTTP endpoints every tournament list change is
+    /// posted to as JSON
+    #[arg(long, env, value_delimiter = ',', requires = "tournaments_http_secret")]
+    pub tournaments_http_endpoints: Vec<String>,
+    /// Secret the JSON posted to the HTTP endpoints is signed with
+    /// (HMAC-SHA256, sent in the X-Chombot-Signature header)
+    #[arg(long, env)]
+    pub tournaments_http_secret: Option<String>,
+}
+
+impl TournamentsWebhooksArgs {
+    /// Returns the notifier posting to the Discord webhooks, if there are
+    /// any.
+    pub fn webhook_notifier(&self) -> anyhow::Result<Option<TournamentsWebhookNotifier>> {
+        if self.tournaments_webhook_urls.is_empty() {
+            return Ok(None);
+        }
+
+        let webhooks = self
+            .tournaments_webhook_urls
+            .iter()
+            .map(|url| DiscordWebhook {
+                url: url.clone(),
+                username: self.tournaments_webhook_username.clone(),
+                avatar_url: self.tournaments_webhook_avatar_url.clone(),
+            })
+            .collect();
+        Ok(Some(
+            TournamentsWebhookNotifier::new(webhooks)?
+                .with_language(self.tournaments_webhook_language),
+        ))
+    }
+
+    /// Returns the notifier posting to the HTTP endpoints, if there are any.
+    pub fn http_notifier(&self) -> anyhow::Result<Option<HttpJsonNotifier>> {
+        let Some(secret) = &self.tournaments_http_secret else {
+            return Ok(None);
+        };
+        if self.tournaments_http_endpoints.is_empty() {
+            return Ok(None);
+        }
+
+        let endpoints = self
+            .tournaments_http_endpoints
+            .iter()
+            .map(|url| HttpEndpoint {
+                url: url.clone(),
+                secret: secret.clone(),
+            })
+            .collect();
+        Ok(Some(HttpJsonNotifier::new(endpoints)?))
+    }
+}
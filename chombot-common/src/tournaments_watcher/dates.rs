@@ -0,0 +1,268 @@
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+const MIN_MONTH_ABBREVIATION_LEN: usize = 3;
+const ORDINAL_SUFFIXES: [&str; 4] = ["st", "nd", "rd", "th"];
+const RANGE_SEPARATORS: [char; 3] = ['-', '–', '—'];
+const NUMERIC_DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%d/%m/%Y", "%d.%m.%Y"];
+
+/// Inclusive range of days a tournament takes place on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    #[must_use]
+    pub const fn single_day(date: NaiveDate) -> Self {
+        Self {
+            start: date,
+            end: date,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PartialDate {
+    day: u32,
+    month: Option<u32>,
+    year: Option<i32>,
+}
+
+/// Parses the date of a tournament, as built from the EMA calendar.
+///
+/// The text consists of the days (e.g. "27-29", "30-2" or "30 Dec - 2 Jan")
+/// followed by the month header (e.g. "November 2023"). The header is used
+/// to fill in the month and the year when they are missing; ranges ending
+/// before they start are assumed to cross the month (or year) boundary.
+#[must_use]
+pub fn parse_tournament_date(text: &str) -> Option<DateRange> {
+    let (days, header_month, header_year) = split_month_header(text)?;
+
+    let mut parts = days.split(RANGE_SEPARATORS);
+    let start_part = parse_partial_date(parts.next()?)?;
+    let end_part = parts.next().map(parse_partial_date);
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let start_year = start_part.year.unwrap_or(header_year);
+    let start_month = start_part.month.unwrap_or(header_month);
+    let start = NaiveDate::from_ymd_opt(start_year, start_month, start_part.day)?;
+    let Some(end_part) = end_part else {
+        return Some(DateRange::single_day(start));
+    };
+    let end_part = end_part?;
+
+    let end = if end_part.month.is_none() && end_part.day < start.day() {
+        start
+            .with_day(1)?
+            .checked_add_months(Months::new(1))?
+            .with_day(end_part.day)?
+    } else {
+        let end_year = end_part.year.unwrap_or(start_year);
+        let end_month = end_part.month.unwrap_or(start_month);
+        let end = NaiveDate::from_ymd_opt(end_year, end_month, end_part.day)?;
+        if end_part.year.is_none() && end < start {
+            end.with_year(end_year + 1)?
+        } else {
+            end
+        }
+    };
+
+    (start <= end).then_some(DateRange { start, end })
+}
+
+/// Parses a single date written in one of the many formats used in the EMA
+/// calendar, e.g. "Sept. 1st 2024", "1 September 2024" or "01/09/2024".
+#[must_use]
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    if let Some(date) = NUMERIC_DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(text, format).ok())
+    {
+        return Some(date);
+    }
+
+    let mut day = None;
+    let mut month = None;
+    let mut year = None;
+    for token in tokenize(text) {
+        if let Some(month_number) = parse_month_name(&token) {
+            set_once(&mut month, month_number)?;
+        } else if let Some(year_number) = parse_year(&token) {
+            set_once(&mut year, year_number)?;
+        } else {
+            set_once(&mut day, parse_day(&token)?)?;
+        }
+    }
+
+    NaiveDate::from_ymd_opt(year?, month?, day?)
+}
+
+fn split_month_header(text: &str) -> Option<(&str, u32, i32)> {
+    let (rest, year) = text.trim().rsplit_once(char::is_whitespace)?;
+    let (days, month) = rest
+        .trim_end()
+        .rsplit_once(char::is_whitespace)
+        .unwrap_or(("", rest));
+    Some((
+        days.trim_end(),
+        parse_month_name(month)?,
+        year.parse().ok()?,
+    ))
+}
+
+fn parse_partial_date(text: &str) -> Option<PartialDate> {
+    let mut tokens = tokenize(text).into_iter();
+    let day = parse_day(&tokens.next()?)?;
+    let mut month = None;
+    let mut year = None;
+
+    for token in tokens {
+        if let Some(month_number) = parse_month_name(&token) {
+            set_once(&mut month, month_number)?;
+        } else if let Some(year_number) = parse_year(&token) {
+            set_once(&mut year, year_number)?;
+        } else {
+            let month_number = token
+                .parse()
+                .ok()
+                .filter(|month| (1..=12).contains(month))?;
+            set_once(&mut month, month_number)?;
+        }
+    }
+
+    Some(PartialDate { day, month, year })
+}
+
+/// Returns `None` if the value has already been set, so that the ambiguous
+/// dates (e.g. containing two month names) are rejected.
+fn set_once<T>(field: &mut Option<T>, value: T) -> Option<()> {
+    if field.is_some() {
+        return None;
+    }
+    *field = Some(value);
+    Some(())
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || matches!(c, '.' | ',' | '/'))
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn parse_month_name(token: &str) -> Option<u32> {
+    let token = token.to_lowercase();
+    if token.len() < MIN_MONTH_ABBREVIATION_LEN {
+        return None;
+    }
+
+    MONTH_NAMES
+        .iter()
+        .position(|name| name.starts_with(&token))
+        .and_then(|index| u32::try_from(index + 1).ok())
+}
+
+fn parse_year(token: &str) -> Option<i32> {
+    if token.len() == 4 {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn parse_day(token: &str) -> Option<u32> {
+    let number = ORDINAL_SUFFIXES
+        .iter()
+        .find_map(|suffix| token.strip_suffix(suffix))
+        .unwrap_or(token);
+    number.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::tournaments_watcher::dates::{parse_date, parse_tournament_date, DateRange};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    const fn range(start: NaiveDate, end: NaiveDate) -> DateRange {
+        DateRange { start, end }
+    }
+
+    #[test]
+    fn test_parse_tournament_date() {
+        assert_eq!(
+            parse_tournament_date("1-2 November 2023"),
+            Some(range(date(2023, 11, 1), date(2023, 11, 2)))
+        );
+        assert_eq!(
+            parse_tournament_date("15 June 2024"),
+            Some(range(date(2024, 6, 15), date(2024, 6, 15)))
+        );
+        assert_eq!(
+            parse_tournament_date("30 - 2 May 2024"),
+            Some(range(date(2024, 5, 30), date(2024, 6, 2)))
+        );
+        assert_eq!(
+            parse_tournament_date("31-1 December 2024"),
+            Some(range(date(2024, 12, 31), date(2025, 1, 1)))
+        );
+        assert_eq!(
+            parse_tournament_date("30 Dec – 2 Jan December 2024"),
+            Some(range(date(2024, 12, 30), date(2025, 1, 2)))
+        );
+        assert_eq!(
+            parse_tournament_date("28/2-1/3 February 2025"),
+            Some(range(date(2025, 2, 28), date(2025, 3, 1)))
+        );
+        assert_eq!(
+            parse_tournament_date("31 Oct 2025 - 2 Nov 2025 October 2025"),
+            Some(range(date(2025, 10, 31), date(2025, 11, 2)))
+        );
+    }
+
+    #[test]
+    fn test_parse_tournament_date_invalid() {
+        // taken from the real calendar
+        assert_eq!(parse_tournament_date("27-31 November 2023"), None);
+        assert_eq!(parse_tournament_date(" November 2023"), None);
+        assert_eq!(parse_tournament_date("TBA November 2023"), None);
+        assert_eq!(parse_tournament_date("1-2-3 November 2023"), None);
+        assert_eq!(parse_tournament_date("1-2 Smarch 2023"), None);
+        assert_eq!(parse_tournament_date(""), None);
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("Sept. 1st 2024"), Some(date(2024, 9, 1)));
+        assert_eq!(parse_date("1 September 2024"), Some(date(2024, 9, 1)));
+        assert_eq!(parse_date("March 22nd, 2025"), Some(date(2025, 3, 22)));
+        assert_eq!(parse_date("01/09/2024"), Some(date(2024, 9, 1)));
+        assert_eq!(parse_date("2024-09-01"), Some(date(2024, 9, 1)));
+        assert_eq!(parse_date("September 2024"), None);
+        assert_eq!(parse_date("open"), None);
+        assert_eq!(parse_date(""), None);
+    }
+}
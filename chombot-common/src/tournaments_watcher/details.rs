@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, TimeDelta, Utc};
+use itertools::Itertools;
+use reqwest::header::LOCATION;
+use reqwest::redirect::Policy;
+use reqwest::{Response, Url};
+use scraper::{ElementRef, Html, Selector};
+use tokio::net::lookup_host;
+use tokio::sync::RwLock;
+use tokio::time::timeout;
+
+use crate::i18n::Messages;
+use crate::scraping_utils::{cell_text, create_chombot_http_client_base, label_matches};
+use crate::select_all;
+
+const DEFAULT_CACHE_TTL: TimeDelta = TimeDelta::hours(6);
+const MAX_VALUE_LEN: usize = 200;
+/// Maximum size of a tournament website page (in bytes)
+const MAX_BODY_LEN: usize = 2 * 1024 * 1024;
+const MAX_REDIRECTS: usize = 5;
+/// Time limits of a single request and of every read from the connection,
+/// so that a slow website does not stall the tournaments watcher
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Time limit of fetching the details, including the redirects
+const DETAILS_TIMEOUT: Duration = Duration::from_secs(30);
+
+const CONTACT_LABELS: [&str; 5] = ["contact", "organiser", "organizer", "email", "mail"];
+const PLAYER_CAP_LABELS: [&str; 6] = [
+    "players",
+    "participants",
+    "capacity",
+    "places",
+    "spots",
+    "seats",
+];
+const ENTRY_FEE_LABELS: [&str; 3] = ["fee", "price", "cost"];
+const VENUE_LABELS: [&str; 4] = ["venue", "address", "location", "place"];
+const RULES_LABELS: [&str; 2] = ["rules", "ruleset"];
+
+/// Information about a tournament found on its website. The websites are run
+/// by the organisers and do not have a common format, so every field is
+/// optional and extracted on a best-effort basis.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TournamentDetails {
+    pub organiser_contact: Option<String>,
+    pub player_cap: Option<u32>,
+    pub entry_fee: Option<String>,
+    pub venue: Option<String>,
+    pub rules_variant: Option<String>,
+}
+
+impl TournamentDetails {
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.organiser_contact.is_none()
+            && self.player_cap.is_none()
+            && self.entry_fee.is_none()
+            && self.venue.is_none()
+            && self.rules_variant.is_none()
+    }
+
+    /// Returns the (name, value) pairs of all the known details.
+    #[must_use]
+    pub fn fields(&self, messages: &Messages) -> Vec<(&'static str, String)> {
+        [
+            (messages.entry_fee, self.entry_fee.clone()),
+            (
+                messages.player_cap,
+                self.player_cap.map(|cap| cap.to_string()),
+            ),
+            (messages.venue, self.venue.clone()),
+            (messages.rules, self.rules_variant.clone()),
+            (messages.contact, self.organiser_contact.clone()),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|value| (name, value)))
+        .collect()
+    }
+}
+
+#[must_use]
+pub fn parse_tournament_details(body: &str) -> TournamentDetails {
+    let html = Html::parse_document(body);
+    let labelled_values = labelled_values(&html);
+    let find = |labels: &[&str]| {
+        labelled_values
+            .iter()
+            .find(|(label, _)| label_matches(label, labels))
+            .map(|(_, value)| value.clone())
+    };
+
+    let mailto = select_all!("a[href^=\"mailto:\"]", html)
+        .filter_map(|element| element.value().attr("href"))
+        .map(|href| href.trim_start_matches("mailto:").to_owned())
+        .next();
+
+    TournamentDetails {
+        organiser_contact: find(&CONTACT_LABELS).or(mailto),
+        player_cap: labelled_values
+            .iter()
+            .filter(|(label, _)| label_matches(label, &PLAYER_CAP_LABELS))
+            .find_map(|(_, value)| first_number(value)),
+        entry_fee: find(&ENTRY_FEE_LABELS),
+        venue: find(&VENUE_LABELS),
+        rules_variant: find(&RULES_LABELS),
+    }
+}
+
+/// Collects the "label: value" pairs from table rows, definition lists and
+/// text paragraphs. The labels are lowercase.
+fn labelled_values(html: &Html) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    for row in select_all!("tr", html) {
+        let cells: Vec<_> = select_all!("th, td", row).collect();
+        if let [label, value] = cells.as_slice() {
+            values.push((cell_text(label), cell_text(value)));
+        }
+    }
+    for term in select_all!("dt", html) {
+        let description = term.next_siblings().find_map(ElementRef::wrap);
+        if let Some(description) = description.filter(|element| element.value().name() == "dd") {
+            values.push((cell_text(&term), cell_text(&description)));
+        }
+    }
+    for element in select_all!("p, li, h1, h2, h3, h4, h5, h6", html) {
+        if let Some((label, value)) = cell_text(&element).split_once(':') {
+            values.push((label.to_owned(), value.to_owned()));
+        }
+    }
+
+    values
+        .into_iter()
+        .filter_map(|(label, value)| {
+            let label = label.trim().trim_end_matches(':').to_lowercase();
+            let value = truncate(value.split_whitespace().join(" "));
+            (!label.is_empty() && !value.is_empty()).then_some((label, value))
+        })
+        .collect()
+}
+
+fn first_number(text: &str) -> Option<u32> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .find(|part| !part.is_empty())
+        .and_then(|number| number.parse().ok())
+}
+
+fn truncate(mut value: String) -> String {
+    if value.len() > MAX_VALUE_LEN {
+        let mut end = MAX_VALUE_LEN;
+        while !value.is_char_boundary(end) {
+            end -= 1;
+        }
+        value.truncate(end);
+        value.push('…');
+    }
+
+    value
+}
+
+/// Fetches the tournament websites and caches the extracted details.
+#[derive(Debug)]
+pub struct TournamentDetailsScraper {
+    cache: RwLock<HashMap<String, (DateTime<Utc>, TournamentDetails)>>,
+    ttl: TimeDelta,
+}
+
+impl Default for TournamentDetailsScraper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TournamentDetailsScraper {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cache: RwLock::default(),
+            ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: TimeDelta) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub async fn details(&self, url: &str) -> anyhow::Result<TournamentDetails> {
+        let now = Utc::now();
+        if let Some(details) = self.cached(url, now).await {
+            return Ok(details);
+        }
+
+        let body = timeout(DETAILS_TIMEOUT, fetch_public_page(url))
+            .await
+            .map_err(|_| anyhow!("Timed out fetching the tournament website"))??;
+        let details = parse_tournament_details(&body);
+        self.store(url, details.clone(), now).await;
+
+        Ok(details)
+    }
+
+    async fn cached(&self, url: &str, now: DateTime<Utc>) -> Option<TournamentDetails> {
+        self.cache
+            .read()
+            .await
+            .get(url)
+            .filter(|(fetched_at, _)| now - *fetched_at < self.ttl)
+            .map(|(_, details)| details.clone())
+    }
+
+    async fn store(&self, url: &str, details: TournamentDetails, now: DateTime<Utc>) {
+        let mut cache = self.cache.write().await;
+        cache.retain(|_, (fetched_at, _)| now - *fetched_at < self.ttl);
+        cache.insert(url.to_owned(), (now, details));
+    }
+}
+
+/// Fetches the page at given URL, following the redirects.
+///
+/// The website URLs come from the calendars, so they are not trusted: the
+/// pages on the loopback and private addresses are refused, and so are the
+/// ones larger than `MAX_BODY_LEN` or not served in time.
+async fn fetch_public_page(url: &str) -> anyhow::Result<String> {
+    let mut url = Url::parse(url)?;
+    for _ in 0..=MAX_REDIRECTS {
+        let addrs = public_addrs(&url).await?;
+        // the addresses are pinned, so that the host cannot resolve to
+        // another one once checked
+        let client = create_chombot_http_client_base()
+            .timeout(REQUEST_TIMEOUT)
+            .read_timeout(READ_TIMEOUT)
+            .redirect(Policy::none())
+            .resolve_to_addrs(url.host_str().unwrap_or_default(), &addrs)
+            .build()?;
+        let response = client.get(url.clone()).send().await?;
+
+        if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .ok_or_else(|| anyhow!("Redirect without a location"))?
+                .to_str()?;
+            url = url.join(location)?;
+            continue;
+        }
+        return read_body(response.error_for_status()?).await;
+    }
+
+    bail!("Too many redirects")
+}
+
+/// Resolves the host of the URL, failing if any of its addresses is not
+/// public.
+async fn public_addrs(url: &Url) -> anyhow::Result<Vec<SocketAddr>> {
+    if !matches!(url.scheme(), "http" | "https") {
+        bail!("Unsupported URL scheme: {}", url.scheme());
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("URL without a host"))?;
+    let port = url.port_or_known_default().unwrap_or_default();
+
+    let addrs: Vec<_> = lookup_host((host.trim_matches(['[', ']']), port))
+        .await?
+        .collect();
+    if addrs.is_empty() || !addrs.iter().all(|addr| is_public(addr.ip())) {
+        bail!("Refusing to fetch a page from a non-public address: {host}");
+    }
+    Ok(addrs)
+}
+
+#[must_use]
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            // 100.64.0.0/10, shared by the carrier-grade NATs
+            let shared = ip.octets()[0] == 100 && (ip.octets()[1] & 0xc0) == 64;
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || shared)
+        }
+        IpAddr::V6(ip) => ip.to_ipv4_mapped().map_or_else(
+            || {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            },
+            |ip| is_public(IpAddr::V4(ip)),
+        ),
+    }
+}
+
+/// Reads the response body, failing if it is larger than `MAX_BODY_LEN`.
+async fn read_body(mut response: Response) -> anyhow::Result<String> {
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_BODY_LEN as u64)
+    {
+        bail!("The page is larger than {MAX_BODY_LEN} bytes");
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > MAX_BODY_LEN {
+            bail!("The page is larger than {MAX_BODY_LEN} bytes");
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use chrono::{TimeDelta, TimeZone, Utc};
+
+    use crate::tournaments_watcher::details::{
+        is_public, parse_tournament_details, read_body, TournamentDetails,
+        TournamentDetailsScraper, MAX_BODY_LEN,
+    };
+
+    #[test]
+    fn test_parse_tournament_details() {
+        let details = parse_tournament_details(include_str!("test_data/tournament_details.html"));
+
+        assert_eq!(
+            details,
+            TournamentDetails {
+                organiser_contact: Some("kro@chombo.club".to_owned()),
+                player_cap: Some(80),
+                entry_fee: Some("60 EUR (50 EUR for EMA members)".to_owned()),
+                venue: Some("Hotel Chopin, ul. Przy Rondzie 2, Krakow".to_owned()),
+                rules_variant: Some("EMA Riichi Competition Rules 2016".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_tournament_details_unknown_page() {
+        let details = parse_tournament_details("<html><body><p>Coming soon!</p></body></html>");
+
+        assert!(details.is_empty());
+    }
+
+    #[test]
+    fn test_details_cache() -> std::io::Result<()> {
+        let scraper = TournamentDetailsScraper::new().with_ttl(TimeDelta::hours(1));
+        let time = Utc.with_ymd_and_hms(2024, 9, 1, 12, 0, 0).unwrap();
+        let details = TournamentDetails {
+            player_cap: Some(80),
+            ..TournamentDetails::default()
+        };
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                let url = "https://chombo.club";
+                scraper.store(url, details.clone(), time).await;
+
+                assert_eq!(
+                    scraper.cached(url, time + TimeDelta::minutes(59)).await,
+                    Some(details)
+                );
+                assert_eq!(scraper.cached(url, time + TimeDelta::hours(1)).await, None);
+                assert_eq!(scraper.cached("https://riichi.pl/", time).await, None);
+            });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_public() {
+        for ip in ["1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public(ip.parse::<IpAddr>().unwrap()), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public(ip.parse::<IpAddr>().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn test_details_refuses_private_addresses() -> std::io::Result<()> {
+        let scraper = TournamentDetailsScraper::new();
+
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(async {
+                for url in [
+                    "http://127.0.0.1:8080/",
+                    "http://[::1]/",
+                    "file:///etc/passwd",
+                ] {
+                    assert!(scraper.details(url).await.is_err(), "{url}");
+                }
+            });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_body_limit() -> std::io::Result<()> {
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                let response = hyper::Response::new(vec![b'a'; MAX_BODY_LEN]);
+                assert_eq!(
+                    read_body(response.into()).await.unwrap().len(),
+                    MAX_BODY_LEN
+                );
+
+                let response = hyper::Response::new(vec![b'a'; MAX_BODY_LEN + 1]);
+                assert!(read_body(response.into()).await.is_err());
+            });
+
+        Ok(())
+    }
+}
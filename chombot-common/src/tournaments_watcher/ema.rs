@@ -1,20 +1,36 @@
-use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 
 use anyhow::{anyhow, bail};
-use itertools::Itertools;
-use log::info;
+use async_trait::async_trait;
+use chrono::NaiveDate;
 use scraper::{ElementRef, Html, Selector};
-
-use crate::data_watcher::WatchableData;
-use crate::scraping_utils::{cell_text, create_chombot_http_client, first_nonempty_text};
+use serde::{Deserialize, Serialize};
+
+use crate::scraping_utils::{
+    absolute_url, cell_text, create_chombot_http_client, first_nonempty_text,
+};
+use crate::tournaments_watcher::dates::{parse_date, parse_tournament_date, DateRange};
+use crate::tournaments_watcher::filters::NotificationFilter;
+use crate::tournaments_watcher::identity::match_entries;
+use crate::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+use crate::tournaments_watcher::sources::{SourceSubscription, TournamentSource};
 use crate::{select_all, select_one};
 
-const CALENDAR_URL: &str = "http://mahjong-europe.org/ranking/Calendar.html";
+pub(crate) const CALENDAR_URL: &str = "http://mahjong-europe.org/ranking/Calendar.html";
 const HEADER_CLASS_PREFIX: &str = "TCTT_contenuEntete";
-const RCR_RULES_NAME: &str = "Riichi";
 const TABLE_COLUMN_NUM: usize = 7;
+const RESULTS_STATUS: &str = "Results";
+/// Matches both "cancelled" and "canceled".
+const CANCELLED_MARKER: &str = "cancel";
+/// Source ID of the tournaments from the EMA calendar.
+pub const EMA_SOURCE_ID: &str = "ema";
+
+/// The snapshots made before the other sources were supported contain the
+/// EMA tournaments only.
+fn default_source() -> String {
+    EMA_SOURCE_ID.to_owned()
+}
 
 macro_rules! diff_option_for {
     ($old_object:ident, $new_object:ident, $field_name:ident) => {
@@ -22,7 +38,7 @@ macro_rules! diff_option_for {
     };
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Tournaments(pub Vec<TournamentEntry>);
 
 impl Tournaments {
@@ -32,39 +48,35 @@ impl Tournaments {
         &self.0
     }
 
+    /// Returns only the tournaments with given rulesets (and the ones with
+    /// unknown rulesets).
     #[must_use]
-    fn into_rcr_only(self) -> Self {
+    pub fn with_rulesets(self, rulesets: &RulesetSubscription) -> Self {
         let filtered = self
             .0
             .into_iter()
-            .filter(|entry| entry.rules == RCR_RULES_NAME)
+            .filter(|entry| rulesets.includes(entry.ruleset()))
             .collect();
         Self(filtered)
     }
-}
-
-impl WatchableData for Tournaments {
-    type Diff = TournamentStatuses;
 
-    fn should_notify(&self, new: &Self) -> Option<Self::Diff> {
-        if self == new {
-            None
-        } else {
-            let diff = tournaments_diff(self, new);
-            if diff.0.is_empty() {
-                // if the only difference is that some tournaments were removed,
-                // we shouldn't create an empty message about it
-                None
-            } else {
-                Some(diff)
-            }
-        }
+    #[must_use]
+    pub fn with_sources(self, sources: &SourceSubscription) -> Self {
+        let filtered = self
+            .0
+            .into_iter()
+            .filter(|entry| sources.contains(&entry.source))
+            .collect();
+        Self(filtered)
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TournamentEntry {
     pub name: String,
+    /// ID of the source the tournament comes from
+    #[serde(default = "default_source")]
+    pub source: String,
     pub url: String,
     pub rules: String,
     pub date: String,
@@ -72,11 +84,42 @@ pub struct TournamentEntry {
     pub approval_status: String,
     pub registration_start: String,
     pub results_status: String,
+    /// Link to the tournament results on the EMA website (empty if none)
+    #[serde(default)]
+    pub results_url: String,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+impl TournamentEntry {
+    /// Returns `None` if the ruleset is not known to the bot.
+    #[must_use]
+    pub fn ruleset(&self) -> Option<Ruleset> {
+        Ruleset::from_rules_name(&self.rules)
+    }
+
+    /// Returns the days the tournament takes place on, or `None` if the date
+    /// could not be parsed (in which case `date` should be displayed as-is).
+    #[must_use]
+    pub fn date_range(&self) -> Option<DateRange> {
+        parse_tournament_date(&self.date)
+    }
+
+    /// Returns the registration start date, or `None` if it is not known or
+    /// could not be parsed (in which case `registration_start` should be
+    /// displayed as-is).
+    #[must_use]
+    pub fn registration_start_date(&self) -> Option<NaiveDate> {
+        parse_date(&self.registration_start)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TournamentChange {
     pub name: String,
+    #[serde(default = "default_source")]
+    pub source: String,
+    /// Ruleset of the tournament after the change (`None` if not known)
+    #[serde(default)]
+    pub ruleset: Option<Ruleset>,
     pub url: Option<String>,
     pub rules: Option<String>,
     pub date: Option<String>,
@@ -84,18 +127,165 @@ pub struct TournamentChange {
     pub approval_status: Option<String>,
     pub registration_start: Option<String>,
     pub results_status: Option<String>,
+    #[serde(default)]
+    pub results_url: Option<String>,
+    /// Listing of the tournament before the change (`None` for the changes
+    /// archived before it was recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous: Option<Box<TournamentEntry>>,
+}
+
+impl TournamentChange {
+    /// Returns `true` if none of the displayed fields have changed.
+    #[must_use]
+    const fn is_empty(&self) -> bool {
+        self.url.is_none()
+            && self.rules.is_none()
+            && self.date.is_none()
+            && self.place.is_none()
+            && self.approval_status.is_none()
+            && self.registration_start.is_none()
+            && self.results_status.is_none()
+    }
+
+    /// Returns `true` if the tournament has just been marked as cancelled.
+    #[must_use]
+    pub fn is_cancellation(&self) -> bool {
+        [
+            &self.date,
+            &self.place,
+            &self.approval_status,
+            &self.results_status,
+        ]
+        .into_iter()
+        .flatten()
+        .any(|value| value.to_lowercase().contains(CANCELLED_MARKER))
+    }
+
+    /// Returns the link to the results if they have just been published.
+    #[must_use]
+    pub fn published_results_url(&self) -> Option<&str> {
+        self.results_status
+            .as_ref()
+            .filter(|status| status.trim().eq_ignore_ascii_case(RESULTS_STATUS))
+            .and(self.results_url.as_deref())
+    }
+
+    /// Returns the listing of the tournament after the change, or `None` if
+    /// the previous listing is not known.
+    #[must_use]
+    pub fn updated_entry(&self) -> Option<TournamentEntry> {
+        let mut entry = self.previous.as_deref()?.clone();
+        entry.name.clone_from(&self.name);
+        entry.source.clone_from(&self.source);
+        for (field, value) in [
+            (&mut entry.url, &self.url),
+            (&mut entry.rules, &self.rules),
+            (&mut entry.date, &self.date),
+            (&mut entry.place, &self.place),
+            (&mut entry.approval_status, &self.approval_status),
+            (&mut entry.registration_start, &self.registration_start),
+            (&mut entry.results_status, &self.results_status),
+            (&mut entry.results_url, &self.results_url),
+        ] {
+            if let Some(value) = value {
+                field.clone_from(value);
+            }
+        }
+        Some(entry)
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TournamentStatuses(pub Vec<TournamentStatus>);
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+impl TournamentStatuses {
+    /// Returns only the statuses of the tournaments with given rulesets (and
+    /// the ones with unknown rulesets).
+    #[must_use]
+    pub fn with_rulesets(&self, rulesets: &RulesetSubscription) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|status| rulesets.includes(status.ruleset()))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Returns only the statuses of the tournaments from given sources.
+    #[must_use]
+    pub fn with_sources(&self, sources: &SourceSubscription) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|status| sources.contains(status.source()))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Returns only the statuses passing given notification filter.
+    #[must_use]
+    pub fn with_filter(&self, filter: &NotificationFilter) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|status| filter.matches(status))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TournamentStatus {
     New(TournamentEntry),
     Changed(TournamentChange),
+    /// The tournament is listed under a new name; `change` holds the new name
+    /// along with the other changed fields
+    Renamed {
+        old_name: String,
+        change: TournamentChange,
+    },
+    /// The tournament is no longer listed in its source
+    Removed(TournamentEntry),
 }
 
 impl TournamentStatus {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::New(entry) | Self::Removed(entry) => &entry.name,
+            Self::Changed(change) | Self::Renamed { change, .. } => &change.name,
+        }
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &str {
+        match self {
+            Self::New(entry) | Self::Removed(entry) => &entry.source,
+            Self::Changed(change) | Self::Renamed { change, .. } => &change.source,
+        }
+    }
+
+    #[must_use]
+    pub fn ruleset(&self) -> Option<Ruleset> {
+        match self {
+            Self::New(entry) | Self::Removed(entry) => entry.ruleset(),
+            Self::Changed(change) | Self::Renamed { change, .. } => change.ruleset,
+        }
+    }
+
+    /// Returns the change of the tournament, if it was listed before.
+    #[must_use]
+    pub const fn change(&self) -> Option<&TournamentChange> {
+        match self {
+            Self::Changed(change) | Self::Renamed { change, .. } => Some(change),
+            Self::New(_) | Self::Removed(_) => None,
+        }
+    }
+
     #[must_use]
     fn for_entries(
         old_entry: Option<&TournamentEntry>,
@@ -108,6 +298,8 @@ impl TournamentStatus {
 
             let tournament_change = TournamentChange {
                 name: new_entry.name.clone(),
+                source: new_entry.source.clone(),
+                ruleset: new_entry.ruleset(),
                 url: diff_option_for!(old_entry, new_entry, url),
                 rules: diff_option_for!(old_entry, new_entry, rules),
                 date: diff_option_for!(old_entry, new_entry, date),
@@ -115,7 +307,21 @@ impl TournamentStatus {
                 approval_status: diff_option_for!(old_entry, new_entry, approval_status),
                 registration_start: diff_option_for!(old_entry, new_entry, registration_start),
                 results_status: diff_option_for!(old_entry, new_entry, results_status),
+                results_url: diff_option_for!(old_entry, new_entry, results_url)
+                    .filter(|results_url| !results_url.is_empty()),
+                previous: Some(Box::new(old_entry.clone())),
             };
+            if old_entry.name != new_entry.name {
+                return Some(Self::Renamed {
+                    old_name: old_entry.name.clone(),
+                    change: tournament_change,
+                });
+            }
+            if tournament_change.is_empty() {
+                // only the results link was added, e.g. by parsing an older
+                // snapshot
+                return None;
+            }
 
             Some(Self::Changed(tournament_change))
         } else {
@@ -129,43 +335,20 @@ pub fn tournaments_diff(
     entries_old: &Tournaments,
     entries_new: &Tournaments,
 ) -> TournamentStatuses {
-    check_rcr_ruleset(entries_old);
-    check_rcr_ruleset(entries_new);
-
-    let mut old_entries_map: HashMap<String, &TournamentEntry> = HashMap::new();
-    for entry in unique_entries(entries_old.get()) {
-        old_entries_map.insert(entry.name.clone(), entry);
-    }
-
-    let mut statuses = Vec::new();
-    for new_entry in unique_entries(entries_new.get()) {
-        let old_entry = old_entries_map.get(&new_entry.name).copied();
-        let status = TournamentStatus::for_entries(old_entry, new_entry);
-        if let Some(status) = status {
-            statuses.push(status);
-        }
-    }
+    let matches = match_entries(entries_old.get(), entries_new.get());
+    let statuses = entries_new
+        .get()
+        .iter()
+        .zip(matches)
+        .filter_map(|(new_entry, old_index)| {
+            let old_entry = old_index.map(|old_index| &entries_old.get()[old_index]);
+            TournamentStatus::for_entries(old_entry, new_entry)
+        })
+        .collect();
 
     TournamentStatuses(statuses)
 }
 
-fn check_rcr_ruleset(entries_old: &Tournaments) {
-    assert!(
-        entries_old
-            .get()
-            .iter()
-            .all(|entry| entry.rules == RCR_RULES_NAME),
-        "Only a Riichi ruleset is supported, please filter your data first"
-    );
-}
-
-fn unique_entries<'a, T>(entries: T) -> impl Iterator<Item = &'a TournamentEntry>
-where
-    T: IntoIterator<Item = &'a TournamentEntry>,
-{
-    entries.into_iter().unique_by(|entry| &entry.name)
-}
-
 #[must_use]
 fn get_diff_option(value_old: &str, value_new: &str) -> Option<String> {
     if value_old == value_new {
@@ -219,9 +402,15 @@ fn make_entry(last_header: &str, cells: &[ElementRef]) -> anyhow::Result<Tournam
             texts.len()
         );
     }
+    let results_url = select_all!("a", cells[6])
+        .next()
+        .and_then(|element| element.value().attr("href"))
+        .map(|href| absolute_url(CALENDAR_URL, href))
+        .unwrap_or_default();
 
     let entry = TournamentEntry {
         name: texts[0].clone(),
+        source: EMA_SOURCE_ID.to_owned(),
         url,
         rules: texts[1].clone(),
         date: format!("{} {}", texts[2], last_header),
@@ -229,18 +418,24 @@ fn make_entry(last_header: &str, cells: &[ElementRef]) -> anyhow::Result<Tournam
         approval_status: texts[4].clone(),
         registration_start: texts[5].clone(),
         results_status: texts[6].clone(),
+        results_url,
     };
     Ok(entry)
 }
 
-pub async fn get_rcr_tournaments() -> Result<Option<Tournaments>, TournamentsFetchError> {
-    let tournaments = get_tournaments().await?.into_rcr_only();
-    info!(
-        "Got {} RCR tournaments: {:?}",
-        tournaments.get().len(),
-        tournaments.get()
-    );
-    Ok(Some(tournaments))
+/// The EMA calendar, listing the tournaments counted in the EMA ranking.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EmaCalendarSource;
+
+#[async_trait]
+impl TournamentSource for EmaCalendarSource {
+    fn id(&self) -> &str {
+        EMA_SOURCE_ID
+    }
+
+    async fn tournaments(&self) -> Result<Tournaments, TournamentsFetchError> {
+        get_tournaments().await
+    }
 }
 
 pub async fn get_tournaments() -> Result<Tournaments, TournamentsFetchError> {
@@ -262,14 +457,25 @@ pub enum TournamentsFetchError {
     ParseError(anyhow::Error),
 }
 
+impl TournamentsFetchError {
+    /// Adds the ID of the source that failed to the error.
+    #[must_use]
+    pub fn for_source(self, source_id: &str) -> Self {
+        match self {
+            Self::FetchError(err) => Self::FetchError(err.context(format!("source `{source_id}`"))),
+            Self::ParseError(err) => Self::ParseError(err.context(format!("source `{source_id}`"))),
+        }
+    }
+}
+
 impl Display for TournamentsFetchError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::FetchError(err) => {
-                write!(f, "Could not fetch the tournament list: {err}")
+                write!(f, "Could not fetch the tournament list: {err:#}")
             }
             Self::ParseError(err) => {
-                write!(f, "Could not parse the tournament list: {err}")
+                write!(f, "Could not parse the tournament list: {err:#}")
             }
         }
     }
@@ -279,23 +485,76 @@ impl Error for TournamentsFetchError {}
 
 #[cfg(test)]
 mod tests {
+    use chrono::NaiveDate;
+
+    use crate::tournaments_watcher::dates::DateRange;
     use crate::tournaments_watcher::ema::{
         parse_tournaments, tournaments_diff, TournamentChange, TournamentEntry, TournamentStatus,
+        Tournaments,
     };
+    use crate::tournaments_watcher::rulesets::Ruleset;
+
+    #[test]
+    fn parses_dates_from_real_data() {
+        let data = include_str!("test_data/calendar_2.html");
+        let entries = parse_tournaments(data).unwrap();
+        let poteto = entries
+            .get()
+            .iter()
+            .find(|entry| entry.name == "Poteto Riichi Taikai 2023")
+            .unwrap();
+
+        assert_eq!(
+            poteto.date_range(),
+            Some(DateRange {
+                start: NaiveDate::from_ymd_opt(2023, 11, 1).unwrap(),
+                end: NaiveDate::from_ymd_opt(2023, 11, 2).unwrap(),
+            })
+        );
+        assert_eq!(
+            poteto.registration_start_date(),
+            NaiveDate::from_ymd_opt(2024, 9, 1)
+        );
+    }
+
+    #[test]
+    fn parses_results_links() {
+        let entries = parse_tournaments(include_str!("test_data/calendar_results.html")).unwrap();
+        let results_urls: Vec<_> = entries
+            .get()
+            .iter()
+            .map(|entry| entry.results_url.as_str())
+            .collect();
+
+        assert_eq!(
+            results_urls,
+            vec![
+                "http://mahjong-europe.org/ranking/Tournament/TR_RCR_350.html",
+                "",
+            ]
+        );
+    }
 
     #[test]
     fn builds_diff_from_real_data() {
         let data_1 = include_str!("test_data/calendar_1.html");
         let data_2 = include_str!("test_data/calendar_2.html");
-        let entries_1 = parse_tournaments(data_1).unwrap().into_rcr_only();
-        let entries_2 = parse_tournaments(data_2).unwrap().into_rcr_only();
+        let entries_1 = parse_tournaments(data_1).unwrap();
+        let entries_2 = parse_tournaments(data_2).unwrap();
         let diffs = tournaments_diff(&entries_1, &entries_2);
+        let poteto_old = entries_1
+            .get()
+            .iter()
+            .find(|entry| entry.name == "Poteto Riichi Taikai 2023")
+            .unwrap();
 
         assert_eq!(
             diffs.0,
             vec![
                 TournamentStatus::Changed(TournamentChange {
                     name: "Poteto Riichi Taikai 2023".to_owned(),
+                    source: "ema".to_owned(),
+                    ruleset: Some(Ruleset::Riichi),
                     url: None,
                     rules: None,
                     date: Some("1-2 November 2023".to_owned()),
@@ -303,9 +562,12 @@ mod tests {
                     approval_status: None,
                     registration_start: Some("Sept. 1st 2024".to_owned()),
                     results_status: Some("Results".to_owned()),
+                    results_url: None,
+                    previous: Some(Box::new(poteto_old.clone())),
                 }),
                 TournamentStatus::New(TournamentEntry {
                     name: "Krakow Riichi Open".to_owned(),
+                    source: "ema".to_owned(),
                     url: "https://chombo.club".to_owned(),
                     rules: "Riichi".to_owned(),
                     date: "27-31 November 2023".to_owned(),
@@ -313,8 +575,54 @@ mod tests {
                     approval_status: "OK".to_owned(),
                     registration_start: String::new(),
                     results_status: String::new(),
+                    results_url: String::new(),
                 }),
             ]
         );
     }
+
+    #[test]
+    fn builds_diff_for_renamed_tournament() {
+        let entry = |name: &str, place: &str| TournamentEntry {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            url: "https://chombo.club".to_owned(),
+            rules: "Riichi".to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: place.to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        };
+        let entries_old = Tournaments(vec![entry("Krakow Open", "Krakow")]);
+        let entries_new = Tournaments(vec![
+            entry("Krakow Riichi Open 2025", "Krakow"),
+            entry("Krakow Riichi Open 2025", "Warsaw"),
+        ]);
+
+        assert_eq!(
+            tournaments_diff(&entries_old, &entries_new).0,
+            vec![
+                TournamentStatus::Renamed {
+                    old_name: "Krakow Open".to_owned(),
+                    change: TournamentChange {
+                        name: "Krakow Riichi Open 2025".to_owned(),
+                        source: "ema".to_owned(),
+                        ruleset: Some(Ruleset::Riichi),
+                        url: None,
+                        rules: None,
+                        date: None,
+                        place: None,
+                        approval_status: None,
+                        registration_start: None,
+                        results_status: None,
+                        results_url: None,
+                        previous: Some(Box::new(entry("Krakow Open", "Krakow"))),
+                    },
+                },
+                TournamentStatus::New(entry("Krakow Riichi Open 2025", "Warsaw")),
+            ]
+        );
+    }
 }
@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use poise::serenity_prelude::{Color, CreateEmbed};
+
+use crate::data::{
+    DISCORD_EMBED_FIELD_LIMIT, DISCORD_EMBED_FIELD_VALUE_LIMIT, DISCORD_EMBED_TITLE_LIMIT,
+    DISCORD_MESSAGE_EMBEDS_SIZE_LIMIT, DISCORD_MESSAGE_EMBED_LIMIT,
+};
+use crate::i18n::Messages;
+use crate::tournaments_watcher::details::TournamentDetails;
+use crate::tournaments_watcher::ema::{
+    TournamentChange, TournamentEntry, TournamentStatus, TournamentStatuses,
+};
+use crate::tournaments_watcher::notifier::status_tags;
+
+/// Embed describing a single tournament status in a tournaments update.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StatusEmbed {
+    pub title: String,
+    /// Website of the tournament
+    pub url: Option<String>,
+    pub color: Color,
+    /// (name, value) pairs; the changed values are displayed as "old → new"
+    pub fields: Vec<(String, String)>,
+}
+
+impl StatusEmbed {
+    #[must_use]
+    pub fn for_status(
+        status: &TournamentStatus,
+        details: Option<&TournamentDetails>,
+        messages: &Messages,
+    ) -> Self {
+        let tags = status_tags(status);
+        let embed = match status {
+            TournamentStatus::New(entry) => Self {
+                title: format!("{tags}{}: {}", messages.tournament_new, entry.name),
+                url: website(&entry.url),
+                color: Color::DARK_GREEN,
+                fields: entry_fields(entry, messages)
+                    .into_iter()
+                    .chain(
+                        details
+                            .into_iter()
+                            .flat_map(|details| details.fields(messages))
+                            .map(|(name, value)| (name.to_owned(), value)),
+                    )
+                    .collect(),
+            },
+            TournamentStatus::Changed(change) => {
+                let (label, color) = if change.is_cancellation() {
+                    (messages.tournament_cancelled, Color::RED)
+                } else {
+                    (messages.tournament_changed, Color::GOLD)
+                };
+                Self {
+                    title: format!("{tags}{label}: {}", change.name),
+                    url: change_website(change),
+                    color,
+                    fields: change_fields(change, messages),
+                }
+            }
+            TournamentStatus::Renamed { old_name, change } => Self {
+                title: format!(
+                    "{tags}{}: {old_name} → {}",
+                    messages.tournament_renamed, change.name
+                ),
+                url: change_website(change),
+                color: Color::BLUE,
+                fields: change_fields(change, messages),
+            },
+            TournamentStatus::Removed(entry) => Self {
+                title: format!("{tags}{}: {}", messages.tournament_removed, entry.name),
+                url: website(&entry.url),
+                color: Color::DARK_GREY,
+                fields: [(messages.date, &entry.date), (messages.place, &entry.place)]
+                    .into_iter()
+                    .filter(|(_, value)| !value.is_empty())
+                    .map(|(name, value)| (name.to_owned(), value.clone()))
+                    .collect(),
+            },
+        };
+
+        embed.truncated()
+    }
+
+    /// Shortens the title and the fields to fit in the Discord limits.
+    #[must_use]
+    fn truncated(mut self) -> Self {
+        self.title = truncate(&self.title, DISCORD_EMBED_TITLE_LIMIT);
+        self.fields.truncate(DISCORD_EMBED_FIELD_LIMIT);
+        for (_, value) in &mut self.fields {
+            *value = truncate(value, DISCORD_EMBED_FIELD_VALUE_LIMIT);
+        }
+        self
+    }
+
+    /// Returns the number of characters counted towards the size limit of
+    /// the embeds of a message.
+    #[must_use]
+    fn size(&self) -> usize {
+        self.title.chars().count()
+            + self
+                .fields
+                .iter()
+                .map(|(name, value)| name.chars().count() + value.chars().count())
+                .sum::<usize>()
+    }
+}
+
+impl From<StatusEmbed> for CreateEmbed {
+    fn from(status_embed: StatusEmbed) -> Self {
+        let mut embed = Self::new()
+            .title(status_embed.title)
+            .color(status_embed.color)
+            .fields(
+                status_embed
+                    .fields
+                    .into_iter()
+                    .map(|(name, value)| (name, value, true)),
+            );
+        if let Some(url) = status_embed.url {
+            embed = embed.url(url);
+        }
+
+        embed
+    }
+}
+
+/// Returns the embeds of the tournament statuses, or `None` if they do not
+/// fit in a single message.
+#[must_use]
+pub(crate) fn status_embeds(
+    tournament_statuses: &TournamentStatuses,
+    details: &HashMap<String, TournamentDetails>,
+    messages: &Messages,
+) -> Option<Vec<StatusEmbed>> {
+    if tournament_statuses.0.len() > DISCORD_MESSAGE_EMBED_LIMIT {
+        return None;
+    }
+
+    let embeds: Vec<_> = tournament_statuses
+        .0
+        .iter()
+        .map(|status| StatusEmbed::for_status(status, details.get(status.name()), messages))
+        .collect();
+    let size: usize = embeds.iter().map(StatusEmbed::size).sum();
+    (size <= DISCORD_MESSAGE_EMBEDS_SIZE_LIMIT).then_some(embeds)
+}
+
+#[must_use]
+fn entry_fields(entry: &TournamentEntry, messages: &Messages) -> Vec<(String, String)> {
+    [
+        (messages.date, entry.date.clone()),
+        (messages.place, entry.place.clone()),
+        (messages.mers, entry.approval_status.clone()),
+        (
+            messages.registration_start,
+            entry.registration_start.clone(),
+        ),
+        (
+            messages.results,
+            results_link(&entry.results_status, &entry.results_url),
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, value)| !value.is_empty())
+    .map(|(name, value)| (name.to_owned(), value))
+    .collect()
+}
+
+#[must_use]
+fn change_fields(change: &TournamentChange, messages: &Messages) -> Vec<(String, String)> {
+    let previous = change.previous.as_deref();
+    let results = change.results_status.as_ref().map(|results_status| {
+        results_link(
+            results_status,
+            change.results_url.as_deref().unwrap_or_default(),
+        )
+    });
+    [
+        (
+            messages.website,
+            &change.url,
+            previous.map(|entry| &entry.url),
+        ),
+        (
+            messages.rules,
+            &change.rules,
+            previous.map(|entry| &entry.rules),
+        ),
+        (
+            messages.date,
+            &change.date,
+            previous.map(|entry| &entry.date),
+        ),
+        (
+            messages.place,
+            &change.place,
+            previous.map(|entry| &entry.place),
+        ),
+        (
+            messages.mers,
+            &change.approval_status,
+            previous.map(|entry| &entry.approval_status),
+        ),
+        (
+            messages.registration_start,
+            &change.registration_start,
+            previous.map(|entry| &entry.registration_start),
+        ),
+        (
+            messages.results,
+            &results,
+            previous.map(|entry| &entry.results_status),
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(name, new_value, old_value)| {
+        let value = changed_value(old_value.map(String::as_str), new_value.as_deref()?)?;
+        Some((name.to_owned(), value))
+    })
+    .collect()
+}
+
+/// Returns the "old → new" description of a changed value, or `None` if
+/// there is nothing to display.
+#[must_use]
+fn changed_value(old_value: Option<&str>, new_value: &str) -> Option<String> {
+    match (old_value.filter(|value| !value.is_empty()), new_value) {
+        (None, "") => None,
+        (Some(old_value), "") => Some(format!("~~{old_value}~~")),
+        (None, new_value) => Some(new_value.to_owned()),
+        (Some(old_value), new_value) => Some(format!("{old_value} → {new_value}")),
+    }
+}
+
+#[must_use]
+fn results_link(results_status: &str, results_url: &str) -> String {
+    if results_status.is_empty() || results_url.is_empty() {
+        results_status.to_owned()
+    } else {
+        format!("[{results_status}]({results_url})")
+    }
+}
+
+/// Returns the website of the tournament after the change, if known.
+#[must_use]
+fn change_website(change: &TournamentChange) -> Option<String> {
+    match &change.url {
+        Some(url) => website(url),
+        None => website(&change.previous.as_ref()?.url),
+    }
+}
+
+/// Returns the URL if Discord accepts it as an embed link.
+#[must_use]
+fn website(url: &str) -> Option<String> {
+    (url.starts_with("http://") || url.starts_with("https://")).then(|| url.to_owned())
+}
+
+#[must_use]
+pub(crate) fn truncate(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_owned();
+    }
+    text.chars()
+        .take(max_len - 1)
+        .chain(std::iter::once('…'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use poise::serenity_prelude::Color;
+
+    use crate::i18n::Language;
+    use crate::tournaments_watcher::ema::{
+        TournamentChange, TournamentEntry, TournamentStatus, TournamentStatuses,
+    };
+    use crate::tournaments_watcher::embeds::{status_embeds, StatusEmbed};
+    use crate::tournaments_watcher::rulesets::Ruleset;
+
+    fn entry(name: &str) -> TournamentEntry {
+        TournamentEntry {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            url: "https://chombo.club".to_owned(),
+            rules: "Riichi".to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_change_embed() {
+        let change = TournamentChange {
+            name: "Krakow Riichi Open".to_owned(),
+            source: "pzm".to_owned(),
+            ruleset: Some(Ruleset::Riichi),
+            url: Some(String::new()),
+            rules: None,
+            date: Some("4-6 October 2024".to_owned()),
+            place: None,
+            approval_status: Some("OK".to_owned()),
+            registration_start: None,
+            results_status: None,
+            results_url: None,
+            previous: Some(Box::new(entry("Krakow Riichi Open"))),
+        };
+
+        assert_eq!(
+            StatusEmbed::for_status(
+                &TournamentStatus::Changed(change.clone()),
+                None,
+                Language::English.messages()
+            ),
+            StatusEmbed {
+                title: "[pzm] CHANGED: Krakow Riichi Open".to_owned(),
+                url: None,
+                color: Color::GOLD,
+                fields: vec![
+                    ("Website".to_owned(), "~~https://chombo.club~~".to_owned()),
+                    (
+                        "Date".to_owned(),
+                        "27-29 September 2024 → 4-6 October 2024".to_owned()
+                    ),
+                    ("MERS".to_owned(), "OK".to_owned()),
+                ],
+            }
+        );
+
+        let polish_embed = StatusEmbed::for_status(
+            &TournamentStatus::Changed(change),
+            None,
+            Language::Polish.messages(),
+        );
+        assert_eq!(polish_embed.title, "[pzm] ZMIANA: Krakow Riichi Open");
+        assert_eq!(polish_embed.fields[1].0, "Data");
+    }
+
+    #[test]
+    fn test_status_embeds_overflow() {
+        let statuses = |count| {
+            TournamentStatuses(
+                (0..count)
+                    .map(|index| TournamentStatus::New(entry(&format!("Riichi League #{index}"))))
+                    .collect(),
+            )
+        };
+
+        let embeds =
+            status_embeds(&statuses(10), &HashMap::new(), Language::English.messages()).unwrap();
+        assert_eq!(embeds.len(), 10);
+        assert_eq!(embeds[3].title, "NEW: Riichi League #3");
+        assert_eq!(embeds[3].url.as_deref(), Some("https://chombo.club"));
+        assert_eq!(embeds[3].color, Color::DARK_GREEN);
+        assert!(
+            status_embeds(&statuses(11), &HashMap::new(), Language::English.messages()).is_none()
+        );
+    }
+}
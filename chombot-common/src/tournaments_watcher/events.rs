@@ -0,0 +1,701 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use anyhow::bail;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveTime, Utc};
+use log::error;
+use poise::serenity_prelude::{
+    Context, CreateScheduledEvent, EditScheduledEvent, GuildId, Http, ScheduledEventId,
+    ScheduledEventType, UserId,
+};
+use tokio::sync::RwLock;
+
+use crate::data_watcher::DataUpdateNotifier;
+use crate::tournaments_watcher::dates::{parse_tournament_date, DateRange};
+use crate::tournaments_watcher::ema::{
+    TournamentChange, TournamentEntry, TournamentStatus, TournamentStatuses,
+};
+use crate::tournaments_watcher::rulesets::RulesetSubscription;
+use crate::tournaments_watcher::sources::SourceSubscription;
+use crate::tournaments_watcher::watched::WatchedTournaments;
+
+/// Time of the day (UTC) the events start at; the calendars list the dates
+/// only.
+const EVENT_START_TIME: NaiveTime = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+/// Time of the day (UTC) the events end at.
+const EVENT_END_TIME: NaiveTime = NaiveTime::from_hms_opt(18, 0, 0).unwrap();
+/// Location of the events for the tournaments without a known place (the
+/// external events require one).
+const UNKNOWN_LOCATION: &str = "TBA";
+const MAX_NAME_LEN: usize = 100;
+const MAX_LOCATION_LEN: usize = 100;
+const MAX_DESCRIPTION_LEN: usize = 1000;
+
+/// Discord scheduled event listing a tournament.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TournamentEvent {
+    pub name: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub location: String,
+    pub description: String,
+}
+
+impl TournamentEvent {
+    /// Returns `None` if the tournament date is not known or the tournament
+    /// has already started.
+    #[must_use]
+    pub fn for_entry(entry: &TournamentEntry, now: DateTime<Utc>) -> Option<Self> {
+        let (start_time, end_time) = event_times(entry.date_range()?, now)?;
+
+        let mut description = Vec::new();
+        if !entry.url.is_empty() {
+            description.push(entry.url.clone());
+        }
+        description.push(format!("Rules: {}", entry.rules));
+        if !entry.approval_status.is_empty() {
+            description.push(format!("MERS: {}", entry.approval_status));
+        }
+        if !entry.registration_start.is_empty() {
+            description.push(format!("Registration start: {}", entry.registration_start));
+        }
+
+        Some(Self {
+            name: event_name(&entry.name),
+            start_time,
+            end_time,
+            location: event_location(&entry.place),
+            description: truncate(&description.join("\n"), MAX_DESCRIPTION_LEN),
+        })
+    }
+}
+
+/// Changes to apply to the event of a tournament that has changed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TournamentEventUpdate {
+    pub name: Option<String>,
+    pub times: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    pub location: Option<String>,
+}
+
+impl TournamentEventUpdate {
+    /// The new date is skipped if it could not be parsed or the tournament
+    /// has already started.
+    #[must_use]
+    pub fn for_change(change: &TournamentChange, renamed: bool, now: DateTime<Utc>) -> Self {
+        Self {
+            name: renamed.then(|| event_name(&change.name)),
+            times: change
+                .date
+                .as_deref()
+                .and_then(parse_tournament_date)
+                .and_then(|date_range| event_times(date_range, now)),
+            location: change.place.as_deref().map(event_location),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.name.is_none() && self.times.is_none() && self.location.is_none()
+    }
+}
+
+#[must_use]
+fn event_times(
+    date_range: DateRange,
+    now: DateTime<Utc>,
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start_time = date_range.start.and_time(EVENT_START_TIME).and_utc();
+    let end_time = date_range.end.and_time(EVENT_END_TIME).and_utc();
+    (start_time > now).then_some((start_time, end_time))
+}
+
+#[must_use]
+fn event_name(tournament_name: &str) -> String {
+    truncate(tournament_name, MAX_NAME_LEN)
+}
+
+#[must_use]
+fn event_location(place: &str) -> String {
+    if place.is_empty() {
+        UNKNOWN_LOCATION.to_owned()
+    } else {
+        truncate(place, MAX_LOCATION_LEN)
+    }
+}
+
+#[must_use]
+fn truncate(text: &str, max_len: usize) -> String {
+    text.chars().take(max_len).collect()
+}
+
+/// Scheduled event created by the bot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExistingEvent {
+    pub id: ScheduledEventId,
+    pub name: String,
+}
+
+/// Guild scheduled events API.
+#[async_trait]
+pub trait ScheduledEventSink: Send + Sync {
+    /// Returns the upcoming events created by the bot.
+    async fn events(&self, guild_id: GuildId) -> anyhow::Result<Vec<ExistingEvent>>;
+
+    async fn create_event(&self, guild_id: GuildId, event: &TournamentEvent) -> anyhow::Result<()>;
+
+    async fn update_event(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+        update: &TournamentEventUpdate,
+    ) -> anyhow::Result<()>;
+
+    async fn delete_event(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+    ) -> anyhow::Result<()>;
+}
+
+/// Creates the events as external Discord scheduled events; requires the
+/// "Manage Events" permission.
+#[derive(Clone)]
+pub struct DiscordScheduledEventSink {
+    http: Arc<Http>,
+    /// ID of the bot user, used to tell the events created by the bot apart
+    bot_user_id: UserId,
+}
+
+impl DiscordScheduledEventSink {
+    #[must_use]
+    pub const fn new(http: Arc<Http>, bot_user_id: UserId) -> Self {
+        Self { http, bot_user_id }
+    }
+}
+
+impl From<&Context> for DiscordScheduledEventSink {
+    fn from(ctx: &Context) -> Self {
+        Self::new(ctx.http.clone(), ctx.cache.current_user().id)
+    }
+}
+
+#[async_trait]
+impl ScheduledEventSink for DiscordScheduledEventSink {
+    async fn events(&self, guild_id: GuildId) -> anyhow::Result<Vec<ExistingEvent>> {
+        let events = guild_id.scheduled_events(&self.http, false).await?;
+        Ok(events
+            .into_iter()
+            .filter(|event| event.creator_id == Some(self.bot_user_id))
+            .map(|event| ExistingEvent {
+                id: event.id,
+                name: event.name,
+            })
+            .collect())
+    }
+
+    async fn create_event(&self, guild_id: GuildId, event: &TournamentEvent) -> anyhow::Result<()> {
+        let builder =
+            CreateScheduledEvent::new(ScheduledEventType::External, &event.name, event.start_time)
+                .end_time(event.end_time)
+                .location(&event.location)
+                .description(&event.description);
+        guild_id
+            .create_scheduled_event(self.http.as_ref(), builder)
+            .await?;
+        Ok(())
+    }
+
+    async fn update_event(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+        update: &TournamentEventUpdate,
+    ) -> anyhow::Result<()> {
+        let mut builder = EditScheduledEvent::new();
+        if let Some(name) = &update.name {
+            builder = builder.name(name);
+        }
+        if let Some((start_time, end_time)) = update.times {
+            builder = builder.start_time(start_time).end_time(end_time);
+        }
+        if let Some(location) = &update.location {
+            builder = builder.location(location);
+        }
+        guild_id
+            .edit_scheduled_event(self.http.as_ref(), event_id, builder)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_event(
+        &self,
+        guild_id: GuildId,
+        event_id: ScheduledEventId,
+    ) -> anyhow::Result<()> {
+        Ok(guild_id
+            .delete_scheduled_event(&self.http, event_id)
+            .await?)
+    }
+}
+
+/// Guild to keep the tournament events in, along with the rulesets and
+/// sources of the tournaments to create the events for.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TournamentEventGuild {
+    pub guild_id: GuildId,
+    pub rulesets: RulesetSubscription,
+    pub sources: SourceSubscription,
+}
+
+impl TournamentEventGuild {
+    #[must_use]
+    pub fn new(guild_id: GuildId) -> Self {
+        Self {
+            guild_id,
+            rulesets: RulesetSubscription::default(),
+            sources: SourceSubscription::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_rulesets(mut self, rulesets: RulesetSubscription) -> Self {
+        self.rulesets = rulesets;
+        self
+    }
+
+    #[must_use]
+    pub fn with_sources(mut self, sources: SourceSubscription) -> Self {
+        self.sources = sources;
+        self
+    }
+}
+
+#[async_trait]
+pub trait TournamentEventGuildProvider: Send + Sync {
+    async fn tournament_event_guilds(&self) -> Vec<TournamentEventGuild>;
+}
+
+#[async_trait]
+impl TournamentEventGuildProvider for TournamentEventGuild {
+    async fn tournament_event_guilds(&self) -> Vec<Self> {
+        vec![self.clone()]
+    }
+}
+
+#[async_trait]
+impl<T: TournamentEventGuildProvider> TournamentEventGuildProvider for Arc<RwLock<T>> {
+    async fn tournament_event_guilds(&self) -> Vec<TournamentEventGuild> {
+        self.read().await.tournament_event_guilds().await
+    }
+}
+
+/// Keeps a scheduled event for every upcoming tournament: creates it when
+/// the tournament is announced, moves it when the date or place changes and
+/// deletes it when the tournament is cancelled or removed.
+///
+/// The events are found by their names, so the events created before the
+/// guild enabled the feature (or edited by hand) are left alone. A tournament
+/// that gets a date after being announced without one gets its event then.
+#[derive(Clone)]
+pub struct TournamentEventsNotifier {
+    guild_provider: Arc<dyn TournamentEventGuildProvider>,
+    event_sink: Arc<dyn ScheduledEventSink>,
+}
+
+impl TournamentEventsNotifier {
+    #[must_use]
+    pub fn new(
+        guild_provider: Arc<dyn TournamentEventGuildProvider>,
+        event_sink: Arc<dyn ScheduledEventSink>,
+    ) -> Self {
+        Self {
+            guild_provider,
+            event_sink,
+        }
+    }
+
+    async fn sync_guild(
+        &self,
+        guild_id: GuildId,
+        tournament_statuses: &TournamentStatuses,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let events = self.event_sink.events(guild_id).await?;
+
+        let mut failed_statuses = 0;
+        for status in &tournament_statuses.0 {
+            if let Err(why) = self.sync_status(guild_id, &events, status, now).await {
+                error!(
+                    "Could not update the event of {} in {guild_id}: {why:?}",
+                    status.name()
+                );
+                failed_statuses += 1;
+            }
+        }
+
+        if failed_statuses > 0 {
+            bail!("Could not update the events of {failed_statuses} tournament(s)");
+        }
+        Ok(())
+    }
+
+    async fn sync_status(
+        &self,
+        guild_id: GuildId,
+        events: &[ExistingEvent],
+        status: &TournamentStatus,
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let event_id = |tournament_name: &str| {
+            let name = event_name(tournament_name);
+            events
+                .iter()
+                .find(|event| event.name == name)
+                .map(|event| event.id)
+        };
+
+        match status {
+            TournamentStatus::New(entry) => {
+                if event_id(&entry.name).is_some() {
+                    return Ok(());
+                }
+                if let Some(event) = TournamentEvent::for_entry(entry, now) {
+                    self.event_sink.create_event(guild_id, &event).await?;
+                }
+            }
+            TournamentStatus::Changed(change) | TournamentStatus::Renamed { change, .. } => {
+                let (old_name, renamed) = match status {
+                    TournamentStatus::Renamed { old_name, .. } => (old_name, true),
+                    _ => (&change.name, false),
+                };
+                let Some(event_id) = event_id(old_name) else {
+                    // the tournament had no event, most likely because its
+                    // date was not known
+                    if change.is_cancellation()
+                        || change
+                            .date
+                            .as_deref()
+                            .and_then(parse_tournament_date)
+                            .is_none()
+                    {
+                        return Ok(());
+                    }
+                    let event = change
+                        .updated_entry()
+                        .and_then(|entry| TournamentEvent::for_entry(&entry, now));
+                    if let Some(event) = event {
+                        self.event_sink.create_event(guild_id, &event).await?;
+                    }
+                    return Ok(());
+                };
+                if change.is_cancellation() {
+                    self.event_sink.delete_event(guild_id, event_id).await?;
+                    return Ok(());
+                }
+                let update = TournamentEventUpdate::for_change(change, renamed, now);
+                if !update.is_empty() {
+                    self.event_sink
+                        .update_event(guild_id, event_id, &update)
+                        .await?;
+                }
+            }
+            TournamentStatus::Removed(entry) => {
+                if let Some(event_id) = event_id(&entry.name) {
+                    self.event_sink.delete_event(guild_id, event_id).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for TournamentEventsNotifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TournamentEventsNotifier")
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl DataUpdateNotifier<Option<WatchedTournaments>> for TournamentEventsNotifier {
+    async fn notify(&self, diff: &TournamentStatuses) -> anyhow::Result<()> {
+        let now = Utc::now();
+        let mut failed_guilds = 0;
+        for guild in self.guild_provider.tournament_event_guilds().await {
+            let guild_id = guild.guild_id;
+            let guild_diff = diff
+                .with_rulesets(&guild.rulesets)
+                .with_sources(&guild.sources);
+            if guild_diff.0.is_empty() {
+                continue;
+            }
+            if let Err(why) = self.sync_guild(guild_id, &guild_diff, now).await {
+                error!("Could not update the tournament events in {guild_id}: {why:?}");
+                failed_guilds += 1;
+            }
+        }
+
+        if failed_guilds > 0 {
+            bail!("Could not update the tournament events in {failed_guilds} guild(s)");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, TimeZone, Utc};
+    use poise::serenity_prelude::{GuildId, ScheduledEventId};
+    use tokio::sync::Mutex;
+
+    use crate::tournaments_watcher::ema::{
+        TournamentChange, TournamentEntry, TournamentStatus, TournamentStatuses,
+    };
+    use crate::tournaments_watcher::events::{
+        ExistingEvent, ScheduledEventSink, TournamentEvent, TournamentEventGuild,
+        TournamentEventUpdate, TournamentEventsNotifier,
+    };
+    use crate::tournaments_watcher::rulesets::Ruleset;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    enum EventCall {
+        Create(TournamentEvent),
+        Update(ScheduledEventId, TournamentEventUpdate),
+        Delete(ScheduledEventId),
+    }
+
+    #[derive(Default)]
+    struct RecordingEventSink {
+        events: Vec<ExistingEvent>,
+        /// Event the deletion of which fails
+        undeletable_event_id: Option<ScheduledEventId>,
+        calls: Mutex<Vec<EventCall>>,
+    }
+
+    #[async_trait]
+    impl ScheduledEventSink for RecordingEventSink {
+        async fn events(&self, _guild_id: GuildId) -> anyhow::Result<Vec<ExistingEvent>> {
+            Ok(self.events.clone())
+        }
+
+        async fn create_event(
+            &self,
+            _guild_id: GuildId,
+            event: &TournamentEvent,
+        ) -> anyhow::Result<()> {
+            self.calls
+                .lock()
+                .await
+                .push(EventCall::Create(event.clone()));
+            Ok(())
+        }
+
+        async fn update_event(
+            &self,
+            _guild_id: GuildId,
+            event_id: ScheduledEventId,
+            update: &TournamentEventUpdate,
+        ) -> anyhow::Result<()> {
+            self.calls
+                .lock()
+                .await
+                .push(EventCall::Update(event_id, update.clone()));
+            Ok(())
+        }
+
+        async fn delete_event(
+            &self,
+            _guild_id: GuildId,
+            event_id: ScheduledEventId,
+        ) -> anyhow::Result<()> {
+            if self.undeletable_event_id == Some(event_id) {
+                anyhow::bail!("Missing permissions");
+            }
+            self.calls.lock().await.push(EventCall::Delete(event_id));
+            Ok(())
+        }
+    }
+
+    fn entry(name: &str, date: &str) -> TournamentEntry {
+        TournamentEntry {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            url: "https://chombo.club".to_owned(),
+            rules: "Riichi".to_owned(),
+            date: date.to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    fn change(name: &str) -> TournamentChange {
+        TournamentChange {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            ruleset: Some(Ruleset::Riichi),
+            url: None,
+            rules: None,
+            date: None,
+            place: None,
+            approval_status: None,
+            registration_start: None,
+            results_status: None,
+            results_url: None,
+            previous: None,
+        }
+    }
+
+    fn time(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 9, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_event_for_entry() {
+        let now = time(1, 12);
+
+        assert_eq!(
+            TournamentEvent::for_entry(&entry("Krakow Riichi Open", "27-29 September 2024"), now),
+            Some(TournamentEvent {
+                name: "Krakow Riichi Open".to_owned(),
+                start_time: time(27, 9),
+                end_time: time(29, 18),
+                location: "Krakow".to_owned(),
+                description: "https://chombo.club\nRules: Riichi\nMERS: OK".to_owned(),
+            })
+        );
+        assert_eq!(
+            TournamentEvent::for_entry(&entry("Krakow Riichi Open", "TBA"), now),
+            None
+        );
+        assert_eq!(
+            TournamentEvent::for_entry(&entry("Krakow Riichi Open", "1 September 2024"), now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sync_events() -> std::io::Result<()> {
+        let krakow_event_id = ScheduledEventId::new(1);
+        let poteto_event_id = ScheduledEventId::new(2);
+        let event_sink = Arc::new(RecordingEventSink {
+            events: vec![
+                ExistingEvent {
+                    id: krakow_event_id,
+                    name: "Krakow Open".to_owned(),
+                },
+                ExistingEvent {
+                    id: poteto_event_id,
+                    name: "Poteto Riichi Taikai".to_owned(),
+                },
+            ],
+            ..RecordingEventSink::default()
+        });
+        let notifier = TournamentEventsNotifier::new(
+            Arc::new(TournamentEventGuild::new(GuildId::new(1))),
+            event_sink.clone(),
+        );
+        let mut renamed = change("Krakow Riichi Open");
+        renamed.date = Some("28-29 September 2024".to_owned());
+        let mut cancelled = change("Poteto Riichi Taikai");
+        cancelled.approval_status = Some("Cancelled".to_owned());
+        let new_entry = entry("Warsaw Riichi Open", "5-6 October 2024");
+        let mut dated = change("Poznan Riichi Open");
+        dated.date = Some("12-13 October 2024".to_owned());
+        dated.previous = Some(Box::new(entry("Poznan Riichi Open", "TBA")));
+        let statuses = TournamentStatuses(vec![
+            TournamentStatus::Renamed {
+                old_name: "Krakow Open".to_owned(),
+                change: renamed,
+            },
+            TournamentStatus::Changed(cancelled),
+            TournamentStatus::New(new_entry.clone()),
+            TournamentStatus::Changed(change("Unknown Open")),
+            TournamentStatus::Changed(dated),
+        ]);
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                notifier
+                    .sync_guild(GuildId::new(1), &statuses, time(1, 12))
+                    .await
+                    .unwrap();
+
+                assert_eq!(
+                    *event_sink.calls.lock().await,
+                    vec![
+                        EventCall::Update(
+                            krakow_event_id,
+                            TournamentEventUpdate {
+                                name: Some("Krakow Riichi Open".to_owned()),
+                                times: Some((time(28, 9), time(29, 18))),
+                                location: None,
+                            }
+                        ),
+                        EventCall::Delete(poteto_event_id),
+                        EventCall::Create(
+                            TournamentEvent::for_entry(&new_entry, time(1, 12)).unwrap()
+                        ),
+                        EventCall::Create(
+                            TournamentEvent::for_entry(
+                                &entry("Poznan Riichi Open", "12-13 October 2024"),
+                                time(1, 12)
+                            )
+                            .unwrap()
+                        ),
+                    ]
+                );
+            });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sync_events_after_error() -> std::io::Result<()> {
+        let poteto_event_id = ScheduledEventId::new(2);
+        let event_sink = Arc::new(RecordingEventSink {
+            events: vec![ExistingEvent {
+                id: poteto_event_id,
+                name: "Poteto Riichi Taikai".to_owned(),
+            }],
+            undeletable_event_id: Some(poteto_event_id),
+            ..RecordingEventSink::default()
+        });
+        let notifier = TournamentEventsNotifier::new(
+            Arc::new(TournamentEventGuild::new(GuildId::new(1))),
+            event_sink.clone(),
+        );
+        let new_entry = entry("Warsaw Riichi Open", "5-6 October 2024");
+        let statuses = TournamentStatuses(vec![
+            TournamentStatus::Removed(entry("Poteto Riichi Taikai", "5-6 October 2024")),
+            TournamentStatus::New(new_entry.clone()),
+        ]);
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                assert!(notifier
+                    .sync_guild(GuildId::new(1), &statuses, time(1, 12))
+                    .await
+                    .is_err());
+
+                assert_eq!(
+                    *event_sink.calls.lock().await,
+                    vec![EventCall::Create(
+                        TournamentEvent::for_entry(&new_entry, time(1, 12)).unwrap()
+                    )]
+                );
+            });
+
+        Ok(())
+    }
+}
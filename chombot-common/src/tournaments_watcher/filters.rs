@@ -0,0 +1,300 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+use poise::serenity_prelude::RoleId;
+use poise::ChoiceParameter;
+use serde::{Deserialize, Serialize};
+
+use crate::tournaments_watcher::ema::TournamentStatus;
+
+/// Kinds of the tournament updates a channel can be notified about.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ChoiceParameter,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationKind {
+    /// Newly listed tournaments
+    #[name = "New tournaments"]
+    New,
+    #[name = "Date changes"]
+    Date,
+    /// MERS approval changes, including the cancellations
+    #[name = "Approval changes"]
+    Approval,
+    #[name = "Registration start"]
+    Registration,
+    #[name = "Results"]
+    Results,
+    /// Website, rules and place changes, renames and removals
+    #[name = "Other changes"]
+    Other,
+}
+
+impl NotificationKind {
+    pub const ALL: [Self; 6] = [
+        Self::New,
+        Self::Date,
+        Self::Approval,
+        Self::Registration,
+        Self::Results,
+        Self::Other,
+    ];
+
+    /// Returns the kinds of given tournament update; a change of several
+    /// fields has several kinds.
+    #[must_use]
+    pub fn of_status(status: &TournamentStatus) -> Vec<Self> {
+        let change = match status {
+            TournamentStatus::New(_) => return vec![Self::New],
+            TournamentStatus::Removed(_) => return vec![Self::Other],
+            TournamentStatus::Changed(change) | TournamentStatus::Renamed { change, .. } => change,
+        };
+
+        let mut kinds: Vec<_> = [
+            (Self::Date, change.date.is_some()),
+            (Self::Approval, change.approval_status.is_some()),
+            (Self::Registration, change.registration_start.is_some()),
+            (Self::Results, change.results_status.is_some()),
+            (
+                Self::Other,
+                matches!(status, TournamentStatus::Renamed { .. })
+                    || change.url.is_some()
+                    || change.rules.is_some()
+                    || change.place.is_some(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(kind, changed)| changed.then_some(kind))
+        .collect();
+        if kinds.is_empty() {
+            kinds.push(Self::Other);
+        }
+
+        kinds
+    }
+}
+
+impl Display for NotificationKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Tournament updates a channel is notified about, along with the roles
+/// mentioned in them. Everything is posted by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationFilter {
+    /// Kinds of the updates to post (all of them if empty)
+    #[serde(default)]
+    pub kinds: Vec<NotificationKind>,
+    /// Places (e.g. countries or cities) of the tournaments to post about
+    /// (all of them if empty); matched case-insensitively against the
+    /// tournament places
+    #[serde(default)]
+    pub places: Vec<String>,
+    /// Roles mentioned in the updates of given kind
+    #[serde(default)]
+    pub role_ids: BTreeMap<NotificationKind, RoleId>,
+}
+
+impl NotificationFilter {
+    #[must_use]
+    pub fn with_kinds(mut self, mut kinds: Vec<NotificationKind>) -> Self {
+        kinds.sort_unstable();
+        kinds.dedup();
+        if kinds.len() == NotificationKind::ALL.len() {
+            kinds.clear();
+        }
+        self.kinds = kinds;
+        self
+    }
+
+    #[must_use]
+    pub fn with_places(mut self, places: Vec<String>) -> Self {
+        self.places = places;
+        self
+    }
+
+    #[must_use]
+    pub fn with_role_id(mut self, kind: NotificationKind, role_id: Option<RoleId>) -> Self {
+        if let Some(role_id) = role_id {
+            self.role_ids.insert(kind, role_id);
+        } else {
+            self.role_ids.remove(&kind);
+        }
+        self
+    }
+
+    /// Returns the kinds of the updates posted to the channel.
+    #[must_use]
+    pub fn enabled_kinds(&self) -> Vec<NotificationKind> {
+        if self.kinds.is_empty() {
+            NotificationKind::ALL.to_vec()
+        } else {
+            self.kinds.clone()
+        }
+    }
+
+    /// Returns the kinds of the tournament update the channel is notified
+    /// about; empty if the update should not be posted.
+    #[must_use]
+    pub fn matching_kinds(&self, status: &TournamentStatus) -> Vec<NotificationKind> {
+        if !self.matches_place(status) {
+            return Vec::new();
+        }
+
+        let enabled_kinds = self.enabled_kinds();
+        NotificationKind::of_status(status)
+            .into_iter()
+            .filter(|kind| enabled_kinds.contains(kind))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn matches(&self, status: &TournamentStatus) -> bool {
+        !self.matching_kinds(status).is_empty()
+    }
+
+    /// Returns the mentions of the roles to ping about given updates,
+    /// separated with spaces.
+    #[must_use]
+    pub fn mentions<'a>(&self, statuses: impl IntoIterator<Item = &'a TournamentStatus>) -> String {
+        statuses
+            .into_iter()
+            .flat_map(|status| self.matching_kinds(status))
+            .filter_map(|kind| self.role_ids.get(&kind))
+            .unique()
+            .map(|role_id| format!("<@&{role_id}>"))
+            .join(" ")
+    }
+
+    #[must_use]
+    fn matches_place(&self, status: &TournamentStatus) -> bool {
+        if self.places.is_empty() {
+            return true;
+        }
+        let places: Vec<_> = match status {
+            TournamentStatus::New(entry) | TournamentStatus::Removed(entry) => {
+                vec![&entry.place]
+            }
+            TournamentStatus::Changed(change) | TournamentStatus::Renamed { change, .. } => change
+                .place
+                .iter()
+                .chain(change.previous.as_ref().map(|entry| &entry.place))
+                .collect(),
+        }
+        .into_iter()
+        .filter(|place| !place.is_empty())
+        .collect();
+        if places.is_empty() {
+            // the place of the tournament is not known, so it is better to
+            // post the update than to miss it
+            return true;
+        }
+
+        places.iter().any(|place| {
+            let place = place.to_lowercase();
+            self.places
+                .iter()
+                .any(|filter_place| place.contains(&filter_place.trim().to_lowercase()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use poise::serenity_prelude::RoleId;
+
+    use crate::tournaments_watcher::ema::{TournamentChange, TournamentEntry, TournamentStatus};
+    use crate::tournaments_watcher::filters::{NotificationFilter, NotificationKind};
+    use crate::tournaments_watcher::rulesets::Ruleset;
+
+    fn entry(name: &str, place: &str) -> TournamentEntry {
+        TournamentEntry {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: place.to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    fn results(name: &str, place: &str) -> TournamentStatus {
+        TournamentStatus::Changed(TournamentChange {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            ruleset: Some(Ruleset::Riichi),
+            url: None,
+            rules: None,
+            date: None,
+            place: None,
+            approval_status: None,
+            registration_start: Some("1 August 2024".to_owned()),
+            results_status: Some("Results".to_owned()),
+            results_url: None,
+            previous: Some(Box::new(entry(name, place))),
+        })
+    }
+
+    #[test]
+    fn test_filter_kinds() {
+        let filter = NotificationFilter::default()
+            .with_kinds(vec![NotificationKind::Results, NotificationKind::New]);
+
+        assert!(filter.matches(&TournamentStatus::New(entry("Krakow Open", "Krakow"))));
+        assert!(filter.matches(&results("Paris Open", "Paris")));
+        assert!(!filter.matches(&TournamentStatus::Removed(entry("Krakow Open", "Krakow"))));
+        assert_eq!(
+            NotificationFilter::default()
+                .with_kinds(NotificationKind::ALL.to_vec())
+                .kinds,
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_filter_places() {
+        let filter = NotificationFilter::default().with_places(vec!["poland".to_owned()]);
+
+        assert!(filter.matches(&TournamentStatus::New(entry(
+            "Krakow Open",
+            "Krakow, Poland"
+        ))));
+        assert!(filter.matches(&results("Krakow Open", "Krakow (Poland)")));
+        assert!(!filter.matches(&TournamentStatus::New(entry("Paris Open", "Paris, France"))));
+    }
+
+    #[test]
+    fn test_mentions() {
+        let filter = NotificationFilter::default()
+            .with_role_id(NotificationKind::Registration, Some(RoleId::new(69)))
+            .with_role_id(NotificationKind::Results, Some(RoleId::new(2137)))
+            .with_role_id(NotificationKind::New, Some(RoleId::new(420)))
+            .with_role_id(NotificationKind::New, None);
+
+        assert_eq!(
+            filter.mentions(&[
+                TournamentStatus::New(entry("Krakow Open", "Krakow")),
+                results("Paris Open", "Paris"),
+                results("Warsaw Open", "Warsaw"),
+            ]),
+            "<@&69> <@&2137>"
+        );
+    }
+}
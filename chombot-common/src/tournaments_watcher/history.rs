@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::error;
+use serde::Deserialize;
+
+use crate::i18n::{fill, Messages};
+use crate::tournaments_watcher::ema::{TournamentChange, TournamentStatus, TournamentStatuses};
+
+/// Timeline of the tournament list changes, read from the archive the
+/// tournaments watcher appends every change to.
+#[derive(Clone, Debug)]
+pub struct TournamentHistory {
+    archive_path: PathBuf,
+}
+
+impl TournamentHistory {
+    #[must_use]
+    pub const fn new(archive_path: PathBuf) -> Self {
+        Self { archive_path }
+    }
+
+    /// Returns the changes of all the tournaments, oldest first. The archive
+    /// lines that could not be parsed are skipped.
+    pub async fn entries(&self) -> anyhow::Result<Vec<HistoryEntry>> {
+        match tokio::fs::read_to_string(&self.archive_path).await {
+            Ok(archive) => Ok(parse_archive(&archive)),
+            Err(why) if why.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(why) => Err(why.into()),
+        }
+    }
+}
+
+/// Change of a single tournament.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub status: TournamentStatus,
+}
+
+impl HistoryEntry {
+    #[must_use]
+    pub fn describe(&self, messages: &Messages) -> String {
+        match &self.status {
+            TournamentStatus::New(entry) => {
+                let mut description = fill(messages.history_listed, &[("date", &entry.date)]);
+                if !entry.place.is_empty() {
+                    let _ = write!(description, "; {}", entry.place);
+                }
+                description
+            }
+            TournamentStatus::Changed(change) => describe_change(change, messages).join("; "),
+            TournamentStatus::Renamed { old_name, change } => {
+                let mut descriptions =
+                    vec![fill(messages.history_renamed, &[("old_name", old_name)])];
+                descriptions.extend(describe_change(change, messages));
+                descriptions.join("; ")
+            }
+            TournamentStatus::Removed(_) => messages.history_removed.to_owned(),
+        }
+    }
+}
+
+#[must_use]
+fn describe_change(change: &TournamentChange, messages: &Messages) -> Vec<String> {
+    let mut descriptions = Vec::new();
+    if change.is_cancellation() {
+        descriptions.push(messages.history_cancelled.to_owned());
+    }
+    if let Some(url) = change.url.as_ref().filter(|url| !url.is_empty()) {
+        descriptions.push(fill(messages.history_website, &[("url", url)]));
+    }
+    if let Some(rules) = &change.rules {
+        descriptions.push(fill(messages.history_rules, &[("rules", rules)]));
+    }
+    if let Some(date) = &change.date {
+        descriptions.push(fill(messages.history_date, &[("date", date)]));
+    }
+    if let Some(place) = &change.place {
+        descriptions.push(fill(messages.history_place, &[("place", place)]));
+    }
+    if let Some(approval_status) = &change.approval_status {
+        descriptions.push(format!("{}: {approval_status}", messages.mers_approval));
+    }
+    if let Some(registration_start) = &change.registration_start {
+        descriptions.push(format!(
+            "{}: {registration_start}",
+            messages.registration_start.to_lowercase()
+        ));
+    }
+    if let Some(results_status) = &change.results_status {
+        let mut description = format!("{}: {results_status}", messages.results.to_lowercase());
+        if let Some(results_url) = &change.results_url {
+            let _ = write!(description, " ({results_url})");
+        }
+        descriptions.push(description);
+    }
+
+    descriptions
+}
+
+/// Line of the archive written by `JsonFileAppendNotifier`.
+#[derive(Deserialize)]
+struct ArchiveLine {
+    timestamp: DateTime<Utc>,
+    diff: TournamentStatuses,
+}
+
+#[must_use]
+fn parse_archive(archive: &str) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    for line in archive.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<ArchiveLine>(line) {
+            Ok(archive_line) => {
+                entries.extend(archive_line.diff.0.into_iter().map(|status| HistoryEntry {
+                    timestamp: archive_line.timestamp,
+                    status,
+                }));
+            }
+            Err(why) => error!("Could not parse a tournament archive line: {why:?}"),
+        }
+    }
+
+    entries
+}
+
+/// Returns the names of the tournaments in the history matching given name
+/// (case-insensitively) or, if there are none, containing it.
+#[must_use]
+pub fn matching_names(entries: &[HistoryEntry], name: &str) -> Vec<String> {
+    let name = name.trim().to_lowercase();
+    let mut names: Vec<_> = entries
+        .iter()
+        .map(|entry| entry.status.name().to_owned())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    if let Some(exact) = names
+        .iter()
+        .find(|entry_name| entry_name.to_lowercase() == name)
+    {
+        return vec![exact.clone()];
+    }
+    names
+        .into_iter()
+        .filter(|entry_name| entry_name.to_lowercase().contains(&name))
+        .collect()
+}
+
+/// Returns the changes of the tournament with given name, including the ones
+/// made under its previous and later names.
+#[must_use]
+pub fn tournament_timeline(entries: &[HistoryEntry], name: &str) -> Vec<HistoryEntry> {
+    let mut names = HashSet::from([name.to_owned()]);
+    loop {
+        let mut added = false;
+        for entry in entries {
+            if let TournamentStatus::Renamed { old_name, change } = &entry.status {
+                if names.contains(old_name) || names.contains(&change.name) {
+                    added |= names.insert(old_name.clone());
+                    added |= names.insert(change.name.clone());
+                }
+            }
+        }
+        if !added {
+            break;
+        }
+    }
+
+    entries
+        .iter()
+        .filter(|entry| names.contains(entry.status.name()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::i18n::Language;
+    use crate::tournaments_watcher::history::{matching_names, parse_archive, tournament_timeline};
+
+    const ARCHIVE: &str = concat!(
+        r#"{"timestamp":"2024-09-01T12:00:00Z","diff":[{"New":{"name":"Krakow Open","url":"","rules":"Riichi","date":"27-29 September 2024","place":"Krakow","approval_status":"","registration_start":"","results_status":""}}]}"#,
+        "\n",
+        "not json\n",
+        r#"{"timestamp":"2024-09-02T12:00:00Z","diff":[{"Renamed":{"old_name":"Krakow Open","change":{"name":"Krakow Riichi Open","url":null,"rules":null,"date":null,"place":null,"approval_status":"OK","registration_start":null,"results_status":null}}},{"New":{"name":"Poteto Riichi Taikai","url":"","rules":"Riichi","date":"TBA","place":"","approval_status":"","registration_start":"","results_status":""}}]}"#,
+        "\n",
+        r#"{"timestamp":"2024-09-30T12:00:00Z","diff":[{"Changed":{"name":"Krakow Riichi Open","url":null,"rules":null,"date":null,"place":null,"approval_status":null,"registration_start":null,"results_status":"Results","results_url":"http://mahjong-europe.org/ranking/Tournament/TR_RCR_400.html"}}]}"#,
+        "\n",
+    );
+
+    #[test]
+    fn test_tournament_timeline() {
+        let entries = parse_archive(ARCHIVE);
+        assert_eq!(entries.len(), 4);
+
+        let timeline = tournament_timeline(&entries, "Krakow Open");
+        assert_eq!(
+            timeline
+                .iter()
+                .map(|entry| (entry.timestamp, entry.describe(Language::English.messages())))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    Utc.with_ymd_and_hms(2024, 9, 1, 12, 0, 0).unwrap(),
+                    "listed: 27-29 September 2024; Krakow".to_owned()
+                ),
+                (
+                    Utc.with_ymd_and_hms(2024, 9, 2, 12, 0, 0).unwrap(),
+                    "renamed from _Krakow Open_; MERS approval: OK".to_owned()
+                ),
+                (
+                    Utc.with_ymd_and_hms(2024, 9, 30, 12, 0, 0).unwrap(),
+                    "results: Results (http://mahjong-europe.org/ranking/Tournament/TR_RCR_400.html)"
+                        .to_owned()
+                ),
+            ]
+        );
+        assert_eq!(
+            timeline[1].describe(Language::Polish.messages()),
+            "zmiana nazwy z _Krakow Open_; Zatwierdzenie MERS: OK"
+        );
+        assert_eq!(
+            tournament_timeline(&entries, "Krakow Riichi Open"),
+            timeline
+        );
+    }
+
+    #[test]
+    fn test_matching_names() {
+        let entries = parse_archive(ARCHIVE);
+
+        assert_eq!(
+            matching_names(&entries, "krakow open"),
+            vec!["Krakow Open".to_owned()]
+        );
+        assert_eq!(
+            matching_names(&entries, "riichi"),
+            vec![
+                "Krakow Riichi Open".to_owned(),
+                "Poteto Riichi Taikai".to_owned()
+            ]
+        );
+        assert!(matching_names(&entries, "Warsaw").is_empty());
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Days, NaiveDate, Utc};
+use slug::slugify;
+
+use crate::tournaments_watcher::ema::TournamentEntry;
+
+const PRODUCT_ID: &str = "-//riichi//chombot//EN";
+const CALENDAR_NAME: &str = "Mahjong tournaments";
+const UID_DOMAIN: &str = "chombot";
+/// Maximum length of a content line (in octets, excluding the line break).
+const MAX_LINE_LEN: usize = 75;
+
+/// Builds an iCalendar (RFC 5545) file with an all-day event for every
+/// tournament. The tournaments without a known date are skipped.
+#[must_use]
+pub fn tournaments_as_ics(entries: &[TournamentEntry], timestamp: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        format!("PRODID:{PRODUCT_ID}"),
+        "CALSCALE:GREGORIAN".to_owned(),
+        "METHOD:PUBLISH".to_owned(),
+        format!("X-WR-CALNAME:{CALENDAR_NAME}"),
+    ];
+    for entry in entries {
+        lines.extend(entry_as_event(entry, timestamp));
+    }
+    lines.push("END:VCALENDAR".to_owned());
+
+    let mut ics = String::new();
+    for line in lines {
+        ics += &fold_line(&line);
+        ics += "\r\n";
+    }
+    ics
+}
+
+#[must_use]
+fn entry_as_event(entry: &TournamentEntry, timestamp: DateTime<Utc>) -> Vec<String> {
+    let Some(date_range) = entry.date_range() else {
+        return Vec::new();
+    };
+    // DTEND is exclusive for the all-day events
+    let end = date_range
+        .end
+        .checked_add_days(Days::new(1))
+        .unwrap_or(date_range.end);
+
+    let mut description = format!("Rules: {}", entry.rules);
+    for (name, value) in [
+        ("MERS", &entry.approval_status),
+        ("Registration start", &entry.registration_start),
+        ("Results", &entry.results_status),
+    ] {
+        if !value.is_empty() {
+            let _ = write!(description, "\n{name}: {value}");
+        }
+    }
+
+    // the yearly tournaments are often listed under the same name, so the
+    // start date keeps the UIDs of the editions apart
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_owned(),
+        format!(
+            "UID:{}-{}-{}@{UID_DOMAIN}",
+            entry.source,
+            slugify(&entry.name),
+            format_date(date_range.start)
+        ),
+        format!("DTSTAMP:{}", timestamp.format("%Y%m%dT%H%M%SZ")),
+        format!("DTSTART;VALUE=DATE:{}", format_date(date_range.start)),
+        format!("DTEND;VALUE=DATE:{}", format_date(end)),
+        format!("SUMMARY:{}", escape_text(&entry.name)),
+    ];
+    if !entry.place.is_empty() {
+        lines.push(format!("LOCATION:{}", escape_text(&entry.place)));
+    }
+    if !entry.url.is_empty() {
+        lines.push(format!("URL:{}", entry.url));
+    }
+    lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+    lines.push("END:VEVENT".to_owned());
+
+    lines
+}
+
+#[must_use]
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+#[must_use]
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped += "\\n",
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits the lines longer than 75 octets; the continuation lines start with
+/// a space. Multi-byte characters are never split.
+#[must_use]
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut current_len = 0;
+    for c in line.chars() {
+        if current_len + c.len_utf8() > MAX_LINE_LEN {
+            folded += "\r\n ";
+            current_len = 1;
+        }
+        folded.push(c);
+        current_len += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::tournaments_watcher::ema::TournamentEntry;
+    use crate::tournaments_watcher::ics::{fold_line, tournaments_as_ics};
+
+    fn entry(name: &str, date: &str) -> TournamentEntry {
+        TournamentEntry {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: date.to_owned(),
+            place: String::new(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_tournaments_as_ics() {
+        let mut krakow_riichi_open = entry("Krakow Riichi Open", "30-2 November 2024");
+        krakow_riichi_open.url = "https://chombo.club".to_owned();
+        krakow_riichi_open.place = "Krakow, Poland".to_owned();
+        krakow_riichi_open.approval_status = "OK".to_owned();
+        let ics = tournaments_as_ics(
+            &[krakow_riichi_open, entry("Mystery Open", "TBA")],
+            Utc.with_ymd_and_hms(2024, 9, 1, 12, 0, 0).unwrap(),
+        );
+
+        assert_eq!(
+            ics.split("\r\n").collect::<Vec<_>>(),
+            vec![
+                "BEGIN:VCALENDAR",
+                "VERSION:2.0",
+                "PRODID:-//riichi//chombot//EN",
+                "CALSCALE:GREGORIAN",
+                "METHOD:PUBLISH",
+                "X-WR-CALNAME:Mahjong tournaments",
+                "BEGIN:VEVENT",
+                "UID:ema-krakow-riichi-open-20241130@chombot",
+                "DTSTAMP:20240901T120000Z",
+                "DTSTART;VALUE=DATE:20241130",
+                "DTEND;VALUE=DATE:20241203",
+                "SUMMARY:Krakow Riichi Open",
+                "LOCATION:Krakow\\, Poland",
+                "URL:https://chombo.club",
+                "DESCRIPTION:Rules: Riichi\\nMERS: OK",
+                "END:VEVENT",
+                "END:VCALENDAR",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_uids_of_editions() {
+        let ics = tournaments_as_ics(
+            &[
+                entry("Krakow Riichi Open", "27-29 September 2024"),
+                entry("Krakow Riichi Open", "26-28 September 2025"),
+            ],
+            Utc.with_ymd_and_hms(2024, 9, 1, 12, 0, 0).unwrap(),
+        );
+
+        let uids: Vec<_> = ics
+            .split("\r\n")
+            .filter(|line| line.starts_with("UID:"))
+            .collect();
+        assert_eq!(uids.len(), 2);
+        assert_ne!(uids[0], uids[1]);
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let line = format!("SUMMARY:{}", "ą".repeat(40));
+        let folded = fold_line(&line);
+
+        assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+        assert_eq!(fold_line("SUMMARY:Short"), "SUMMARY:Short");
+    }
+}
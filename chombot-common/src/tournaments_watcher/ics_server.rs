@@ -0,0 +1,300 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use clap::ValueEnum;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use log::{error, info};
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+
+use crate::tournaments_watcher::ema::{Tournaments, TournamentsFetchError};
+use crate::tournaments_watcher::ics::tournaments_as_ics;
+use crate::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+use crate::tournaments_watcher::sources::{SourceSubscription, TournamentSources};
+
+pub const ICS_PATH: &str = "/tournaments.ics";
+const DEFAULT_CACHE_TTL: TimeDelta = TimeDelta::minutes(10);
+
+/// Serves the tournament calendar as an iCalendar file, so that it can be
+/// subscribed to from Google Calendar or phone calendar apps.
+///
+/// The calendar can be narrowed down with the `rules` and `sources` query
+/// parameters, e.g. `/tournaments.ics?rules=riichi,mcr&sources=ema`.
+#[derive(Debug)]
+pub struct TournamentsIcsServer {
+    sources: TournamentSources,
+    cache: RwLock<Option<(DateTime<Utc>, Tournaments)>>,
+    ttl: TimeDelta,
+}
+
+impl TournamentsIcsServer {
+    #[must_use]
+    pub fn new(sources: TournamentSources) -> Self {
+        Self {
+            sources,
+            cache: RwLock::default(),
+            ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: TimeDelta) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Accepts the connections forever.
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> anyhow::Result<()> {
+        info!(
+            "Serving the tournament calendar at http://{}{ICS_PATH}",
+            listener.local_addr()?
+        );
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(why) => {
+                    error!("Could not accept a tournament calendar connection: {why:?}");
+                    continue;
+                }
+            };
+            let server = self.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let server = server.clone();
+                    async move {
+                        Ok::<_, Infallible>(
+                            server
+                                .response(
+                                    request.method(),
+                                    request.uri().path(),
+                                    request.uri().query(),
+                                )
+                                .await,
+                        )
+                    }
+                });
+                if let Err(why) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    error!("Could not serve the tournament calendar: {why:?}");
+                }
+            });
+        }
+    }
+
+    async fn response(
+        &self,
+        method: &Method,
+        path: &str,
+        query: Option<&str>,
+    ) -> Response<Full<Bytes>> {
+        if path != ICS_PATH {
+            return text_response(StatusCode::NOT_FOUND, "Not found");
+        }
+        if method != Method::GET && method != Method::HEAD {
+            return text_response(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed");
+        }
+        let filter = match CalendarFilter::from_query(query.unwrap_or_default()) {
+            Ok(filter) => filter,
+            Err(why) => return text_response(StatusCode::BAD_REQUEST, &why),
+        };
+
+        let tournaments = match self.tournaments().await {
+            Ok(tournaments) => tournaments,
+            Err(why) => {
+                error!("Could not fetch the tournaments for the calendar: {why:?}");
+                return text_response(StatusCode::BAD_GATEWAY, "Could not fetch the tournaments");
+            }
+        };
+        let ics = tournaments_as_ics(&filter.apply(tournaments).0, Utc::now());
+
+        Response::builder()
+            .header(CONTENT_TYPE, "text/calendar; charset=utf-8")
+            .header(CONTENT_DISPOSITION, "inline; filename=\"tournaments.ics\"")
+            .body(Full::new(Bytes::from(ics)))
+            .expect("Invalid calendar response")
+    }
+
+    async fn tournaments(&self) -> Result<Tournaments, TournamentsFetchError> {
+        let now = Utc::now();
+        if let Some((_, tournaments)) = self
+            .cache
+            .read()
+            .await
+            .as_ref()
+            .filter(|(fetched_at, _)| now - *fetched_at < self.ttl)
+        {
+            return Ok(tournaments.clone());
+        }
+
+        let tournaments = self.sources.tournaments().await?;
+        *self.cache.write().await = Some((now, tournaments.clone()));
+        Ok(tournaments)
+    }
+}
+
+fn text_response(status: StatusCode, text: &str) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Full::new(Bytes::from(text.to_owned())))
+        .expect("Invalid text response")
+}
+
+/// Rulesets and sources of the tournaments included in the calendar; all of
+/// them by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct CalendarFilter {
+    rulesets: Option<RulesetSubscription>,
+    sources: Option<SourceSubscription>,
+}
+
+impl CalendarFilter {
+    /// Parses the query string; the unknown parameters are ignored, as the
+    /// calendar apps tend to add their own ones.
+    fn from_query(query: &str) -> Result<Self, String> {
+        let mut filter = Self::default();
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            let values = value
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty());
+            match key.as_ref() {
+                "rules" => {
+                    let rulesets = values
+                        .map(|value| Ruleset::from_str(value, true))
+                        .collect::<Result<_, _>>()
+                        .map_err(|_| format!("Unknown ruleset in `{value}`"))?;
+                    filter.rulesets = Some(RulesetSubscription::new(rulesets));
+                }
+                "sources" => {
+                    filter.sources = Some(SourceSubscription::new(
+                        values.map(ToOwned::to_owned).collect(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(filter)
+    }
+
+    #[must_use]
+    fn apply(&self, mut tournaments: Tournaments) -> Tournaments {
+        if let Some(rulesets) = &self.rulesets {
+            tournaments = tournaments.with_rulesets(rulesets);
+        }
+        if let Some(sources) = &self.sources {
+            tournaments = tournaments.with_sources(sources);
+        }
+        tournaments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use http_body_util::BodyExt;
+    use hyper::{Method, StatusCode};
+
+    use crate::tournaments_watcher::ema::{TournamentEntry, Tournaments, TournamentsFetchError};
+    use crate::tournaments_watcher::ics_server::{CalendarFilter, TournamentsIcsServer, ICS_PATH};
+    use crate::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+    use crate::tournaments_watcher::sources::{
+        SourceSubscription, TournamentSource, TournamentSources,
+    };
+
+    struct TestSource;
+
+    #[async_trait]
+    impl TournamentSource for TestSource {
+        fn id(&self) -> &'static str {
+            "ema"
+        }
+
+        async fn tournaments(&self) -> Result<Tournaments, TournamentsFetchError> {
+            let entry = |name: &str, rules: &str| TournamentEntry {
+                name: name.to_owned(),
+                source: "ema".to_owned(),
+                url: String::new(),
+                rules: rules.to_owned(),
+                date: "27-29 September 2024".to_owned(),
+                place: "Krakow".to_owned(),
+                approval_status: String::new(),
+                registration_start: String::new(),
+                results_status: String::new(),
+                results_url: String::new(),
+            };
+            Ok(Tournaments(vec![
+                entry("Krakow Riichi Open", "Riichi"),
+                entry("Krakow MCR Open", "MCR"),
+            ]))
+        }
+    }
+
+    #[test]
+    fn test_calendar_filter() {
+        assert_eq!(
+            CalendarFilter::from_query(""),
+            Ok(CalendarFilter::default())
+        );
+        assert_eq!(
+            CalendarFilter::from_query("rules=MCR,riichi&sources=pzm"),
+            Ok(CalendarFilter {
+                rulesets: Some(RulesetSubscription::new(vec![
+                    Ruleset::Riichi,
+                    Ruleset::Mcr
+                ])),
+                sources: Some(SourceSubscription::new(vec!["pzm".to_owned()])),
+            })
+        );
+        assert_eq!(
+            CalendarFilter::from_query("rules=riichi%2Cmcr&year=2024"),
+            Ok(CalendarFilter {
+                rulesets: Some(RulesetSubscription::new(vec![
+                    Ruleset::Riichi,
+                    Ruleset::Mcr
+                ])),
+                sources: None,
+            })
+        );
+        assert!(CalendarFilter::from_query("rules=zung").is_err());
+    }
+
+    #[test]
+    fn test_calendar_response() -> std::io::Result<()> {
+        let server = Arc::new(TournamentsIcsServer::new(
+            TournamentSources::new().with_source(Arc::new(TestSource)),
+        ));
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                let response = server
+                    .response(&Method::GET, ICS_PATH, Some("rules=mcr"))
+                    .await;
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                let body = String::from_utf8(body.to_vec()).unwrap();
+                assert!(body.contains("SUMMARY:Krakow MCR Open\r\n"));
+                assert!(!body.contains("Krakow Riichi Open"));
+
+                let response = server.response(&Method::GET, "/", None).await;
+                assert_eq!(response.status(), StatusCode::NOT_FOUND);
+                let response = server.response(&Method::POST, ICS_PATH, None).await;
+                assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+            });
+
+        Ok(())
+    }
+}
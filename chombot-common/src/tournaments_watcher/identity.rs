@@ -0,0 +1,156 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use crate::tournaments_watcher::ema::TournamentEntry;
+
+/// Similarity the names of a renamed tournament have to exceed if it shares
+/// only one of the website, date and place with its previous listing. Names
+/// like "Krakow Open" and "Warsaw Open" are not similar enough.
+const NAME_SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// Matches the tournaments from two versions of the calendar.
+///
+/// Returns, for every new entry, the index of the old entry listing the same
+/// tournament (`None` if the tournament is new). The tournaments are
+/// identified by their names first; a tournament with a different name is
+/// considered renamed if it has the same rules and shares the website, date
+/// or place with a similarly named one (or at least two of them regardless
+/// of the name). Every old entry is matched at most once, so the tournaments
+/// with the same name are told apart by their website, date and place.
+#[must_use]
+pub fn match_entries(old: &[TournamentEntry], new: &[TournamentEntry]) -> Vec<Option<usize>> {
+    let mut candidates = Vec::new();
+    for (new_index, new_entry) in new.iter().enumerate() {
+        for (old_index, old_entry) in old.iter().enumerate() {
+            if let Some(score) = match_score(old_entry, new_entry) {
+                candidates.push((score, old_index, new_index));
+            }
+        }
+    }
+    // stable sort, so that the ties are resolved by the calendar order
+    candidates.sort_by_key(|&(score, _, _)| Reverse(score));
+
+    let mut matches = vec![None; new.len()];
+    let mut matched_old = HashSet::new();
+    for (_, old_index, new_index) in candidates {
+        if matches[new_index].is_none() && !matched_old.contains(&old_index) {
+            matches[new_index] = Some(old_index);
+            matched_old.insert(old_index);
+        }
+    }
+
+    matches
+}
+
+/// Returns how likely the entries list the same tournament (the higher, the
+/// more likely), or `None` if they list different tournaments.
+#[must_use]
+fn match_score(old: &TournamentEntry, new: &TournamentEntry) -> Option<(bool, usize, u32)> {
+    if old.source != new.source {
+        return None;
+    }
+
+    let same_name = old.name == new.name;
+    let shared_fields = [
+        (&old.url, &new.url),
+        (&old.date, &new.date),
+        (&old.place, &new.place),
+    ]
+    .into_iter()
+    .filter(|(old_value, new_value)| !old_value.is_empty() && old_value == new_value)
+    .count();
+    let similarity = name_similarity(&old.name, &new.name);
+
+    let is_match = same_name
+        || (old.rules == new.rules
+            && (shared_fields >= 2
+                || (shared_fields == 1 && similarity > NAME_SIMILARITY_THRESHOLD)));
+    // the similarity is in [0, 1], so the conversion is lossless enough for
+    // ordering the candidates
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let similarity_permille = (similarity * 1000.0).round() as u32;
+    is_match.then_some((same_name, shared_fields, similarity_permille))
+}
+
+/// Returns the Sørensen–Dice coefficient of the words of the names, i.e.
+/// 1.0 for the names with the same words and 0.0 for the names without any
+/// common words.
+#[must_use]
+fn name_similarity(name_1: &str, name_2: &str) -> f64 {
+    let words_1 = name_words(name_1);
+    let words_2 = name_words(name_2);
+    if words_1.is_empty() && words_2.is_empty() {
+        return 1.0;
+    }
+
+    let common = words_1.intersection(&words_2).count();
+    #[allow(clippy::cast_precision_loss)]
+    let similarity = (2 * common) as f64 / (words_1.len() + words_2.len()) as f64;
+    similarity
+}
+
+#[must_use]
+fn name_words(name: &str) -> HashSet<String> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tournaments_watcher::ema::TournamentEntry;
+    use crate::tournaments_watcher::identity::{match_entries, name_similarity};
+
+    fn entry(name: &str, date: &str, place: &str) -> TournamentEntry {
+        TournamentEntry {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: date.to_owned(),
+            place: place.to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_name_similarity() {
+        assert!((name_similarity("Krakow Open", "KRAKOW  open") - 1.0).abs() < f64::EPSILON);
+        assert!(name_similarity("Krakow Open", "Krakow Riichi Open 2025") > 0.5);
+        assert!(name_similarity("Krakow Open", "Poteto Riichi Taikai") < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_match_renamed() {
+        let old = [
+            entry("Krakow Open", "27-29 September 2024", "Krakow"),
+            entry("Poteto Riichi Taikai", "1-2 November 2024", "Paris"),
+        ];
+        let new = [
+            entry("Paris Mahjong Cup", "1-2 November 2024", "Paris"),
+            entry("Krakow Riichi Open 2025", "TBA", "Krakow"),
+            entry("Warsaw Open", "27-29 September 2024", "Warsaw"),
+        ];
+
+        assert_eq!(match_entries(&old, &new), vec![Some(1), Some(0), None]);
+    }
+
+    #[test]
+    fn test_match_same_names() {
+        let old = [
+            entry("Riichi League", "1 March 2024", "Krakow"),
+            entry("Riichi League", "1 March 2024", "Warsaw"),
+        ];
+        let new = [
+            entry("Riichi League", "8 March 2024", "Warsaw"),
+            entry("Riichi League", "1 March 2024", "Krakow"),
+            entry("Riichi League", "1 March 2024", "Poznan"),
+        ];
+
+        assert_eq!(match_entries(&old, &new), vec![Some(1), Some(0), None]);
+    }
+}
@@ -1,2 +1,19 @@
+pub mod args;
+pub mod dates;
+pub mod details;
 pub mod ema;
+pub mod embeds;
+pub mod events;
+pub mod filters;
+pub mod history;
+pub mod ics;
+pub mod ics_server;
+pub mod identity;
 pub mod notifier;
+pub mod reminders;
+pub mod results;
+pub mod rulesets;
+pub mod sources;
+pub mod threads;
+pub mod watched;
+pub mod webhooks;
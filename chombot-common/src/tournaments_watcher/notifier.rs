@@ -1,38 +1,152 @@
+use std::collections::HashMap;
 use std::fmt::Write;
 use std::iter;
 use std::iter::Once;
 use std::sync::Arc;
 
+use anyhow::bail;
 use async_trait::async_trait;
+use itertools::Itertools;
 use log::error;
-use poise::serenity_prelude::{ChannelId, Context};
-use tokio::sync::RwLock;
+use poise::serenity_prelude::{ChannelId, CreateEmbed};
+use tokio::sync::{Mutex, RwLock};
 
+use crate::data_watcher::snapshot::DataSnapshotStore;
 use crate::data_watcher::DataUpdateNotifier;
-use crate::discord_utils::send_with_overflow;
-use crate::tournaments_watcher::ema::{TournamentStatus, TournamentStatuses, Tournaments};
+use crate::ema_ranking::watcher::LinkedPlayer;
+use crate::i18n::{Language, Messages};
+use crate::message_sink::MessageSink;
+use crate::tournaments_watcher::details::{TournamentDetails, TournamentDetailsScraper};
+use crate::tournaments_watcher::ema::{
+    TournamentChange, TournamentStatus, TournamentStatuses, CALENDAR_URL, EMA_SOURCE_ID,
+};
+use crate::tournaments_watcher::embeds::{status_embeds, StatusEmbed};
+use crate::tournaments_watcher::filters::NotificationFilter;
+use crate::tournaments_watcher::results::{get_tournament_results, TournamentResults};
+use crate::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+use crate::tournaments_watcher::sources::SourceSubscription;
+use crate::tournaments_watcher::threads::{
+    thread_name, TournamentThread, TournamentThreadKey, TournamentThreadSink, TournamentThreads,
+};
+use crate::tournaments_watcher::watched::WatchedTournaments;
 
-const MESSAGE_PREFIX: &str =
-    "**TOURNAMENTS UPDATE** (http://mahjong-europe.org/ranking/Calendar.html)\n\n";
+/// Returns the header of the tournament update messages, linking the EMA
+/// calendar if any of the tournaments come from it. The other sources are
+/// data feeds, so there is no page to link for them.
+#[must_use]
+pub(crate) fn message_prefix(
+    tournament_statuses: &TournamentStatuses,
+    messages: &Messages,
+) -> String {
+    let from_ema = tournament_statuses
+        .0
+        .iter()
+        .any(|status| status.source() == EMA_SOURCE_ID);
+    if from_ema {
+        format!("**{}** ({CALENDAR_URL})\n\n", messages.tournaments_update)
+    } else {
+        format!("**{}**\n\n", messages.tournaments_update)
+    }
+}
 
 #[async_trait]
 pub trait TournamentsUpdateNotifier<R: Send + Sync> {
     async fn notify(&self, ranking: &R);
 }
 
+/// Channel to send the tournament updates to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TournamentWatcherChannel {
+    pub channel_id: ChannelId,
+    /// Rulesets of the tournaments to notify about
+    pub rulesets: RulesetSubscription,
+    /// IDs of the sources of the tournaments to notify about
+    pub sources: SourceSubscription,
+    /// Guild members whose placements are included in the results
+    /// announcements
+    pub linked_players: Vec<LinkedPlayer>,
+    /// Kinds and places of the updates to post, and the roles to ping
+    pub filter: NotificationFilter,
+    /// Whether the updates of every tournament are posted in its own thread
+    /// (always the case in forum channels)
+    pub threads: bool,
+    /// Language the updates are posted in
+    pub language: Language,
+}
+
+impl TournamentWatcherChannel {
+    #[must_use]
+    pub fn new(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            rulesets: RulesetSubscription::default(),
+            sources: SourceSubscription::default(),
+            linked_players: Vec::new(),
+            filter: NotificationFilter::default(),
+            threads: false,
+            language: Language::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_rulesets(mut self, rulesets: RulesetSubscription) -> Self {
+        self.rulesets = rulesets;
+        self
+    }
+
+    #[must_use]
+    pub fn with_sources(mut self, sources: SourceSubscription) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    #[must_use]
+    pub fn with_linked_players(mut self, linked_players: Vec<LinkedPlayer>) -> Self {
+        self.linked_players = linked_players;
+        self
+    }
+
+    #[must_use]
+    pub fn with_filter(mut self, filter: NotificationFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_threads(mut self, threads: bool) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+}
+
 #[async_trait]
 pub trait TournamentWatcherChannelListProvider: Send + Sync {
-    type TournamentWatcherChannelList: IntoIterator<Item = ChannelId> + Send;
+    type TournamentWatcherChannelList: IntoIterator<Item = TournamentWatcherChannel> + Send;
 
     async fn tournament_watcher_channels(&self) -> Self::TournamentWatcherChannelList;
 }
 
 #[async_trait]
 impl TournamentWatcherChannelListProvider for ChannelId {
+    type TournamentWatcherChannelList = Once<TournamentWatcherChannel>;
+
+    async fn tournament_watcher_channels(&self) -> Self::TournamentWatcherChannelList {
+        iter::once(TournamentWatcherChannel::new(*self))
+    }
+}
+
+#[async_trait]
+impl TournamentWatcherChannelListProvider for TournamentWatcherChannel {
     type TournamentWatcherChannelList = Once<Self>;
 
     async fn tournament_watcher_channels(&self) -> Self::TournamentWatcherChannelList {
-        iter::once(*self)
+        iter::once(self.clone())
     }
 }
 
@@ -49,122 +163,634 @@ impl<T: TournamentWatcherChannelListProvider> TournamentWatcherChannelListProvid
 
 pub struct TournamentsChannelMessageNotifier<T> {
     channel_list_provider: T,
+    message_sink: Arc<dyn MessageSink>,
+    thread_sink: Option<Arc<dyn TournamentThreadSink>>,
+    threads: Mutex<TournamentThreads>,
+    thread_store: Option<Box<dyn DataSnapshotStore<TournamentThreads>>>,
+    details_scraper: Option<Arc<TournamentDetailsScraper>>,
+    results_announcements: bool,
 }
 
 impl<T: TournamentWatcherChannelListProvider> TournamentsChannelMessageNotifier<T> {
     #[must_use]
-    pub const fn new(channel_list_provider: T) -> Self {
+    pub fn new(channel_list_provider: T, message_sink: Arc<dyn MessageSink>) -> Self {
         Self {
             channel_list_provider,
+            message_sink,
+            thread_sink: None,
+            threads: Mutex::default(),
+            thread_store: None,
+            details_scraper: None,
+            results_announcements: false,
         }
     }
 
+    /// Makes the notifier post the updates in the tournament threads in the
+    /// forum channels and the channels with the threads enabled. Without it,
+    /// the updates are always posted as channel messages.
+    #[must_use]
+    pub fn with_thread_sink(mut self, thread_sink: Arc<dyn TournamentThreadSink>) -> Self {
+        self.thread_sink = Some(thread_sink);
+        self
+    }
+
+    /// Makes the notifier persist the tournament threads with given store;
+    /// without it, they are only remembered until the bot restarts.
+    #[must_use]
+    pub fn with_thread_store(
+        mut self,
+        thread_store: impl DataSnapshotStore<TournamentThreads> + 'static,
+    ) -> Self {
+        let threads = thread_store.load().unwrap_or_else(|why| {
+            error!("Could not load the tournament threads: {why:?}");
+            None
+        });
+        self.threads = Mutex::new(threads.unwrap_or_default());
+        self.thread_store = Some(Box::new(thread_store));
+        self
+    }
+
+    /// Makes the notifier include the details scraped from the websites of
+    /// the new tournaments.
+    #[must_use]
+    pub fn with_details_scraper(mut self, details_scraper: Arc<TournamentDetailsScraper>) -> Self {
+        self.details_scraper = Some(details_scraper);
+        self
+    }
+
+    /// Makes the notifier announce the final standings of the tournaments
+    /// whose results have just been published.
     #[must_use]
-    fn build_message(tournament_statuses: &TournamentStatuses) -> String {
-        format!("{}{}", MESSAGE_PREFIX, build_message(tournament_statuses))
+    pub const fn with_results_announcements(mut self) -> Self {
+        self.results_announcements = true;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn build_message(
+        tournament_statuses: &TournamentStatuses,
+        details: &HashMap<String, TournamentDetails>,
+        messages: &Messages,
+    ) -> String {
+        format!(
+            "{}{}",
+            message_prefix(tournament_statuses, messages),
+            build_message(tournament_statuses, details, messages)
+        )
+    }
+
+    /// Returns the thread sink if the updates are posted in the tournament
+    /// threads in the channel.
+    async fn channel_thread_sink(
+        &self,
+        channel: &TournamentWatcherChannel,
+    ) -> anyhow::Result<Option<&Arc<dyn TournamentThreadSink>>> {
+        let Some(thread_sink) = &self.thread_sink else {
+            return Ok(None);
+        };
+        if channel.threads || thread_sink.is_forum(channel.channel_id).await? {
+            Ok(Some(thread_sink))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Posts all the updates in a single message.
+    async fn send_to_channel(
+        &self,
+        channel: &TournamentWatcherChannel,
+        channel_diff: &TournamentStatuses,
+        details: &HashMap<String, TournamentDetails>,
+        results: &[TournamentResults],
+    ) -> anyhow::Result<()> {
+        let channel_id = channel.channel_id;
+        let messages = channel.language.messages();
+        let mentions = channel.filter.mentions(&channel_diff.0);
+        let results_text = results
+            .iter()
+            .filter(|tournament_results| {
+                channel_diff
+                    .0
+                    .iter()
+                    .any(|status| status.name() == tournament_results.name)
+            })
+            .map(|tournament_results| {
+                tournament_results.as_message(&channel.linked_players, messages)
+            })
+            .join("\n");
+        if let Some(embeds) = status_embeds(channel_diff, details, messages) {
+            return self
+                .send_embeds(channel, channel_diff, embeds, &results_text)
+                .await;
+        }
+
+        // too much to fit in the embeds; fall back to plain text
+        let mut text = with_mentions(
+            &mentions,
+            &Self::build_message(channel_diff, details, messages),
+        );
+        if !results_text.is_empty() {
+            text.push('\n');
+            text += &results_text;
+        }
+        self.message_sink.send_message(channel_id, &text).await
+    }
+
+    /// Posts the update of every tournament in its thread, starting the
+    /// threads of the tournaments without one. The threads of the renamed
+    /// tournaments are renamed as well.
+    ///
+    /// The threads are looked up by the tournament; the ones started before
+    /// the threads were remembered are matched by name instead.
+    async fn send_to_threads(
+        &self,
+        thread_sink: &dyn TournamentThreadSink,
+        channel: &TournamentWatcherChannel,
+        channel_diff: &TournamentStatuses,
+        details: &HashMap<String, TournamentDetails>,
+        results: &[TournamentResults],
+    ) -> anyhow::Result<()> {
+        let channel_id = channel.channel_id;
+        let mut threads = self.threads.lock().await;
+        let mut unmapped_threads: Option<Vec<TournamentThread>> = None;
+        let mut failed_threads = 0;
+        for status in &channel_diff.0 {
+            let name = thread_name(status.name());
+            let result = async {
+                let (previous_key, key) = thread_keys(channel_id, status);
+                let mentions = channel.filter.mentions([status]);
+                let embed = StatusEmbed::for_status(
+                    status,
+                    details.get(status.name()),
+                    channel.language.messages(),
+                );
+
+                let mut thread = previous_key
+                    .as_ref()
+                    .and_then(|previous_key| threads.get(previous_key))
+                    .map(|thread_id| {
+                        let renamed = matches!(status, TournamentStatus::Renamed { .. });
+                        (thread_id, renamed)
+                    });
+                if thread.is_none() && !matches!(status, TournamentStatus::New(_)) {
+                    if unmapped_threads.is_none() {
+                        unmapped_threads = Some(thread_sink.threads(channel_id).await?);
+                    }
+                    let previous_name = match status {
+                        TournamentStatus::Renamed { old_name, .. } => thread_name(old_name),
+                        _ => name.clone(),
+                    };
+                    thread = unmapped_threads
+                        .iter()
+                        .flatten()
+                        .find(|thread| thread.name == previous_name || thread.name == name)
+                        .map(|thread| (thread.id, thread.name != name));
+                }
+                let thread_id = if let Some((thread_id, renamed)) = thread {
+                    if renamed {
+                        thread_sink.rename_thread(thread_id, &name).await?;
+                    }
+                    self.message_sink
+                        .send_embeds(thread_id, &mentions, vec![embed.into()])
+                        .await?;
+                    thread_id
+                } else {
+                    thread_sink
+                        .create_thread(channel_id, &name, &mentions, vec![embed.into()])
+                        .await?
+                };
+
+                if let Some(previous_key) = &previous_key {
+                    threads.remove(previous_key);
+                }
+                if let Some(key) = key {
+                    threads.insert(key, thread_id);
+                }
+                if let Some(thread_store) = &self.thread_store {
+                    thread_store.save(&threads)?;
+                }
+
+                let status_results = results
+                    .iter()
+                    .filter(|tournament_results| tournament_results.name == status.name());
+                for tournament_results in status_results {
+                    self.message_sink
+                        .send_message(
+                            thread_id,
+                            &tournament_results
+                                .as_message(&channel.linked_players, channel.language.messages()),
+                        )
+                        .await?;
+                }
+                anyhow::Ok(())
+            }
+            .await;
+            // a failed thread must not keep the other tournaments from being
+            // posted
+            if let Err(why) = result {
+                error!("Could not post the update of {name} in {channel_id}: {why:?}");
+                failed_threads += 1;
+            }
+        }
+        drop(threads);
+
+        if failed_threads > 0 {
+            bail!("Could not post the updates in {failed_threads} thread(s)");
+        }
+        Ok(())
+    }
+
+    async fn send_embeds(
+        &self,
+        channel: &TournamentWatcherChannel,
+        channel_diff: &TournamentStatuses,
+        embeds: Vec<StatusEmbed>,
+        results_text: &str,
+    ) -> anyhow::Result<()> {
+        let channel_id = channel.channel_id;
+        let mentions = channel.filter.mentions(&channel_diff.0);
+        let prefix = message_prefix(channel_diff, channel.language.messages());
+        let embeds = embeds.into_iter().map(CreateEmbed::from).collect();
+        let text = with_mentions(&mentions, prefix.trim_end());
+        self.message_sink
+            .send_embeds(channel_id, &text, embeds)
+            .await?;
+        if !results_text.is_empty() {
+            self.message_sink
+                .send_message(channel_id, results_text)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the details of the new tournaments, keyed by the tournament
+    /// name. The tournaments whose details could not be fetched are skipped.
+    async fn new_tournament_details(
+        &self,
+        tournament_statuses: &TournamentStatuses,
+    ) -> HashMap<String, TournamentDetails> {
+        let mut details = HashMap::new();
+        let Some(details_scraper) = &self.details_scraper else {
+            return details;
+        };
+
+        for status in &tournament_statuses.0 {
+            let TournamentStatus::New(entry) = status else {
+                continue;
+            };
+            if entry.url.is_empty() {
+                continue;
+            }
+            match details_scraper.details(&entry.url).await {
+                Ok(entry_details) => {
+                    details.insert(entry.name.clone(), entry_details);
+                }
+                Err(why) => error!("Could not fetch details of {}: {why:?}", entry.name),
+            }
+        }
+
+        details
+    }
+
+    /// Returns the final standings of the tournaments whose results have just
+    /// been published. The tournaments whose results could not be fetched
+    /// are skipped.
+    async fn published_results(
+        &self,
+        tournament_statuses: &TournamentStatuses,
+    ) -> Vec<TournamentResults> {
+        let mut results = Vec::new();
+        if !self.results_announcements {
+            return results;
+        }
+
+        for status in &tournament_statuses.0 {
+            let Some(change) = status.change() else {
+                continue;
+            };
+            let Some(results_url) = change.published_results_url() else {
+                continue;
+            };
+            match get_tournament_results(&change.name, results_url).await {
+                Ok(tournament_results) => results.push(tournament_results),
+                Err(why) => error!("Could not fetch results of {}: {why:?}", change.name),
+            }
+        }
+
+        results
+    }
+}
+
+/// Returns the thread identities of the tournament before and after the
+/// update; `None` if the previous listing of the changed tournament is not
+/// known.
+#[must_use]
+fn thread_keys(
+    channel_id: ChannelId,
+    status: &TournamentStatus,
+) -> (Option<TournamentThreadKey>, Option<TournamentThreadKey>) {
+    match status {
+        TournamentStatus::New(entry) | TournamentStatus::Removed(entry) => {
+            let key = TournamentThreadKey::new(channel_id, entry);
+            (Some(key.clone()), Some(key))
+        }
+        TournamentStatus::Changed(change) | TournamentStatus::Renamed { change, .. } => (
+            change
+                .previous
+                .as_deref()
+                .map(|entry| TournamentThreadKey::new(channel_id, entry)),
+            change
+                .updated_entry()
+                .map(|entry| TournamentThreadKey::new(channel_id, &entry)),
+        ),
+    }
+}
+
+/// Prepends the role mentions (if any) to the message.
+#[must_use]
+fn with_mentions(mentions: &str, text: &str) -> String {
+    if mentions.is_empty() {
+        text.to_owned()
+    } else {
+        format!("{mentions} {text}")
     }
 }
 
 #[must_use]
-fn build_message(tournaments: &TournamentStatuses) -> String {
+pub(crate) fn build_message(
+    tournaments: &TournamentStatuses,
+    details: &HashMap<String, TournamentDetails>,
+    messages: &Messages,
+) -> String {
     let mut str = String::new();
     for diff in &tournaments.0 {
-        let _ = writeln!(str, "* {}", diff_as_message(diff));
+        let _ = write!(str, "* {}", diff_as_message(diff, messages));
+        if let TournamentStatus::New(entry) = diff {
+            if let Some(entry_details) = details.get(&entry.name) {
+                for (name, value) in entry_details.fields(messages) {
+                    let _ = write!(str, "; {}: {value}", name.to_lowercase());
+                }
+            }
+        }
+        str.push('\n');
     }
 
     str
 }
 
+/// Returns the "[source] [ruleset] " prefix of the tournament status; empty
+/// for the riichi tournaments from the EMA calendar.
 #[must_use]
-fn diff_as_message(diff: &TournamentStatus) -> String {
+pub(crate) fn status_tags(diff: &TournamentStatus) -> String {
     let mut str = String::new();
+    if diff.source() != EMA_SOURCE_ID {
+        let _ = write!(str, "[{}] ", diff.source());
+    }
+    if let Some(ruleset) = diff.ruleset().filter(|&ruleset| ruleset != Ruleset::Riichi) {
+        let _ = write!(str, "[{ruleset}] ");
+    }
+
+    str
+}
+
+#[must_use]
+fn diff_as_message(diff: &TournamentStatus, messages: &Messages) -> String {
+    let mut str = status_tags(diff);
 
     match diff {
         TournamentStatus::New(entry) => {
-            let _ = write!(str, "**NEW**: _{}_", entry.name);
+            let _ = write!(str, "**{}**: _{}_", messages.tournament_new, entry.name);
             if !entry.url.is_empty() {
                 let _ = write!(str, " ({})", entry.url);
             }
             str += "; ";
             let _ = write!(str, "{}; ", entry.date);
-            let _ = write!(str, "{}; ", entry.place);
-            let _ = write!(str, "MERS: {}", entry.approval_status);
+            let _ = write!(str, "{}", entry.place);
+            if !entry.approval_status.is_empty() {
+                let _ = write!(str, "; {}: {}", messages.mers, entry.approval_status);
+            }
             if !entry.registration_start.is_empty() {
-                let _ = write!(str, "; registration start: {}", entry.registration_start);
+                let _ = write!(
+                    str,
+                    "; {}: {}",
+                    messages.registration_start.to_lowercase(),
+                    entry.registration_start
+                );
             }
             if !entry.results_status.is_empty() {
                 let _ = write!(str, "; {}", entry.results_status);
             }
         }
         TournamentStatus::Changed(change) => {
-            let _ = write!(str, "**CHANGED**: _{}_; ", change.name);
+            let label = if change.is_cancellation() {
+                messages.tournament_cancelled
+            } else {
+                messages.tournament_changed
+            };
+            let _ = write!(str, "**{label}**: _{}_; ", change.name);
+            write_change_fields(&mut str, change, messages);
+        }
+        TournamentStatus::Renamed { old_name, change } => {
+            let _ = write!(
+                str,
+                "**{}**: _{old_name}_ → _{}_; ",
+                messages.tournament_renamed, change.name
+            );
+            write_change_fields(&mut str, change, messages);
+        }
+        TournamentStatus::Removed(entry) => {
+            let _ = write!(
+                str,
+                "**{}**: _{}_; {}; {}",
+                messages.tournament_removed, entry.name, entry.date, entry.place
+            );
+        }
+    }
 
-            if let Some(url) = &change.url {
-                if !url.is_empty() {
-                    let _ = write!(str, "website: {url}; ");
-                }
-            }
-            if let Some(date) = &change.date {
-                let _ = write!(str, "date: {date}; ");
-            }
-            if let Some(place) = &change.place {
-                let _ = write!(str, "place: {place}; ");
-            }
-            if let Some(approval_status) = &change.approval_status {
-                let _ = write!(str, "MERS approval: {approval_status}; ");
-            }
-            if let Some(registration_start) = &change.registration_start {
-                let _ = write!(str, "registration start: {registration_start}; ");
-            }
-            if let Some(results) = &change.results_status {
-                let _ = write!(str, "results: \"{results}\"; ");
-            }
+    str
+}
 
-            {
-                const SUFFIX: &str = "; ";
-                if str.ends_with(SUFFIX) {
-                    str.truncate(str.len() - SUFFIX.len());
-                }
-            }
+fn write_change_fields(str: &mut String, change: &TournamentChange, messages: &Messages) {
+    if let Some(url) = &change.url {
+        if !url.is_empty() {
+            let _ = write!(str, "{}: {url}; ", messages.website.to_lowercase());
         }
     }
+    if let Some(date) = &change.date {
+        let _ = write!(str, "{}: {date}; ", messages.date.to_lowercase());
+    }
+    if let Some(place) = &change.place {
+        let _ = write!(str, "{}: {place}; ", messages.place.to_lowercase());
+    }
+    if let Some(approval_status) = &change.approval_status {
+        let _ = write!(str, "{}: {approval_status}; ", messages.mers_approval);
+    }
+    if let Some(registration_start) = &change.registration_start {
+        let _ = write!(
+            str,
+            "{}: {registration_start}; ",
+            messages.registration_start.to_lowercase()
+        );
+    }
+    if let Some(results) = &change.results_status {
+        let _ = write!(str, "{}: \"{results}\"; ", messages.results.to_lowercase());
+    }
 
-    str
+    {
+        const SUFFIX: &str = "; ";
+        if str.ends_with(SUFFIX) {
+            str.truncate(str.len() - SUFFIX.len());
+        }
+    }
 }
 
 #[async_trait]
-impl<T: TournamentWatcherChannelListProvider> DataUpdateNotifier<Option<Tournaments>>
+impl<T: TournamentWatcherChannelListProvider> DataUpdateNotifier<Option<WatchedTournaments>>
     for TournamentsChannelMessageNotifier<T>
 {
-    async fn notify(&self, diff: TournamentStatuses, ctx: &Context) {
-        let text = Self::build_message(&diff);
+    async fn notify(&self, diff: &TournamentStatuses) -> anyhow::Result<()> {
+        let details = self.new_tournament_details(diff).await;
+        let results = self.published_results(diff).await;
 
-        let channel_list: Vec<ChannelId> = self
+        let channel_list: Vec<_> = self
             .channel_list_provider
             .tournament_watcher_channels()
             .await
             .into_iter()
             .collect();
-        for channel_id in channel_list {
-            if let Err(why) = send_with_overflow(channel_id, ctx, &text).await {
-                error!("Could not send Tournaments update: {why:?}");
+        let mut failed_channels = 0;
+        for channel in channel_list {
+            let channel_id = channel.channel_id;
+            let channel_diff = diff
+                .with_rulesets(&channel.rulesets)
+                .with_sources(&channel.sources)
+                .with_filter(&channel.filter);
+            if channel_diff.0.is_empty() {
+                continue;
+            }
+            let result = match self.channel_thread_sink(&channel).await {
+                Ok(Some(thread_sink)) => {
+                    self.send_to_threads(
+                        thread_sink.as_ref(),
+                        &channel,
+                        &channel_diff,
+                        &details,
+                        &results,
+                    )
+                    .await
+                }
+                Ok(None) => {
+                    self.send_to_channel(&channel, &channel_diff, &details, &results)
+                        .await
+                }
+                Err(why) => Err(why),
+            };
+            if let Err(why) = result {
+                error!("Could not send Tournaments update to {channel_id}: {why:?}");
+                failed_channels += 1;
             }
         }
+
+        if failed_channels > 0 {
+            bail!("Could not send Tournaments update to {failed_channels} channel(s)");
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::{Arc, Mutex};
+
+    use anyhow::bail;
+    use async_trait::async_trait;
+    use chrono::{TimeDelta, TimeZone, Utc};
+    use poise::serenity_prelude::{ChannelId, CreateEmbed, RoleId};
+    use tempfile::TempDir;
+    use tokio::sync::mpsc::unbounded_channel;
+    use tokio_util::sync::CancellationToken;
+
+    use crate::data_watcher::clock::ManualClock;
+    use crate::data_watcher::health::WatcherHealthRef;
+    use crate::data_watcher::registry::WatcherControl;
+    use crate::data_watcher::snapshot::JsonFileSnapshotStore;
+    use crate::data_watcher::{DataUpdateNotifier, DataWatcher, WatchableData};
+    use crate::i18n::Language;
+    use crate::message_sink::RecordingMessageSink;
+    use crate::tournaments_watcher::details::TournamentDetails;
     use crate::tournaments_watcher::ema::{
-        TournamentChange, TournamentEntry, TournamentStatus, TournamentStatuses,
+        parse_tournaments, TournamentChange, TournamentEntry, TournamentStatus, TournamentStatuses,
     };
-    use crate::tournaments_watcher::notifier::build_message;
+    use crate::tournaments_watcher::embeds::{status_embeds, StatusEmbed};
+    use crate::tournaments_watcher::filters::{NotificationFilter, NotificationKind};
+    use crate::tournaments_watcher::notifier::{
+        build_message, message_prefix, TournamentWatcherChannel, TournamentsChannelMessageNotifier,
+    };
+    use crate::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+    use crate::tournaments_watcher::sources::SourceSubscription;
+    use crate::tournaments_watcher::threads::{TournamentThread, TournamentThreadSink};
+    use crate::tournaments_watcher::watched::WatchedTournaments;
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum ThreadCall {
+        Create(ChannelId, String, String, Vec<CreateEmbed>),
+        Rename(ChannelId, String),
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingThreadSink {
+        threads: Vec<TournamentThread>,
+        failing_renames: bool,
+        calls: Mutex<Vec<ThreadCall>>,
+    }
+
+    #[async_trait]
+    impl TournamentThreadSink for RecordingThreadSink {
+        async fn is_forum(&self, _channel_id: ChannelId) -> anyhow::Result<bool> {
+            Ok(false)
+        }
+
+        async fn threads(&self, _channel_id: ChannelId) -> anyhow::Result<Vec<TournamentThread>> {
+            Ok(self.threads.clone())
+        }
+
+        async fn create_thread(
+            &self,
+            channel_id: ChannelId,
+            name: &str,
+            text: &str,
+            embeds: Vec<CreateEmbed>,
+        ) -> anyhow::Result<ChannelId> {
+            let mut calls = self.calls.lock().unwrap();
+            calls.push(ThreadCall::Create(
+                channel_id,
+                name.to_owned(),
+                text.to_owned(),
+                embeds,
+            ));
+            Ok(ChannelId::new(100 + calls.len() as u64))
+        }
+
+        async fn rename_thread(&self, thread_id: ChannelId, name: &str) -> anyhow::Result<()> {
+            if self.failing_renames {
+                bail!("Missing permissions");
+            }
+            self.calls
+                .lock()
+                .unwrap()
+                .push(ThreadCall::Rename(thread_id, name.to_owned()));
+            Ok(())
+        }
+    }
 
     #[test]
     fn test() {
         let diffs = vec![
             TournamentStatus::Changed(TournamentChange {
                 name: "Poteto Riichi Taikai 2023".to_owned(),
+                source: "ema".to_owned(),
+                ruleset: Some(Ruleset::Riichi),
                 url: None,
                 rules: None,
                 date: Some("1-2 November 2023".to_owned()),
@@ -172,9 +798,12 @@ mod tests {
                 approval_status: None,
                 registration_start: None,
                 results_status: Some("Results".to_owned()),
+                results_url: None,
+                previous: None,
             }),
             TournamentStatus::New(TournamentEntry {
                 name: "Krakow Riichi Open".to_owned(),
+                source: "ema".to_owned(),
                 url: "https://chombo.club".to_owned(),
                 rules: "RCR".to_owned(),
                 date: "27-31 November 2023".to_owned(),
@@ -182,9 +811,12 @@ mod tests {
                 approval_status: "OK".to_owned(),
                 registration_start: "Sept. 1st 2024".to_owned(),
                 results_status: String::new(),
+                results_url: String::new(),
             }),
             TournamentStatus::Changed(TournamentChange {
                 name: "ERMC 2024".to_owned(),
+                source: "ema".to_owned(),
+                ruleset: Some(Ruleset::Riichi),
                 url: Some("abc.com".to_owned()),
                 rules: None,
                 date: None,
@@ -192,9 +824,13 @@ mod tests {
                 approval_status: Some("OK".to_owned()),
                 registration_start: Some("Sept. 1st 2024".to_owned()),
                 results_status: None,
+                results_url: None,
+                previous: None,
             }),
             TournamentStatus::Changed(TournamentChange {
                 name: "ERMC 2025".to_owned(),
+                source: "ema".to_owned(),
+                ruleset: Some(Ruleset::Riichi),
                 url: Some(String::new()),
                 rules: None,
                 date: None,
@@ -202,12 +838,559 @@ mod tests {
                 approval_status: None,
                 registration_start: None,
                 results_status: None,
+                results_url: None,
+                previous: None,
             }),
         ];
 
         assert_eq!(
-            build_message(&TournamentStatuses(diffs)),
+            build_message(
+                &TournamentStatuses(diffs),
+                &HashMap::new(),
+                Language::English.messages()
+            ),
             include_str!("test_data/expected_message.txt")
         );
     }
+
+    #[test]
+    fn test_message_with_details() {
+        let entry = TournamentEntry {
+            name: "Krakow Riichi Open".to_owned(),
+            source: "ema".to_owned(),
+            url: "https://chombo.club".to_owned(),
+            rules: "RCR".to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        };
+        let details = HashMap::from([(
+            entry.name.clone(),
+            TournamentDetails {
+                player_cap: Some(80),
+                entry_fee: Some("60 EUR".to_owned()),
+                ..TournamentDetails::default()
+            },
+        )]);
+
+        assert_eq!(
+            build_message(
+                &TournamentStatuses(vec![TournamentStatus::New(entry)]),
+                &details,
+                Language::English.messages()
+            ),
+            "* **NEW**: _Krakow Riichi Open_ (https://chombo.club); 27-29 September 2024; \
+             Krakow; MERS: OK; entry fee: 60 EUR; player cap: 80\n"
+        );
+    }
+
+    #[test]
+    fn test_removal_and_cancellation_message() {
+        let removed = TournamentEntry {
+            name: "Poteto Riichi Taikai".to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: "1-2 November 2024".to_owned(),
+            place: "Paris".to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        };
+        let cancelled = TournamentChange {
+            name: "Krakow Riichi Open".to_owned(),
+            source: "ema".to_owned(),
+            ruleset: Some(Ruleset::Riichi),
+            url: None,
+            rules: None,
+            date: None,
+            place: None,
+            approval_status: Some("Cancelled".to_owned()),
+            registration_start: None,
+            results_status: None,
+            results_url: None,
+            previous: None,
+        };
+
+        assert_eq!(
+            build_message(
+                &TournamentStatuses(vec![
+                    TournamentStatus::Changed(cancelled),
+                    TournamentStatus::Removed(removed),
+                ]),
+                &HashMap::new(),
+                Language::English.messages()
+            ),
+            "* **CANCELLED**: _Krakow Riichi Open_; MERS approval: Cancelled\n\
+             * **REMOVED**: _Poteto Riichi Taikai_; 1-2 November 2024; Paris\n"
+        );
+    }
+
+    #[test]
+    fn test_rename_message() {
+        let renamed = TournamentChange {
+            name: "Krakow Riichi Open 2025".to_owned(),
+            source: "ema".to_owned(),
+            ruleset: Some(Ruleset::Riichi),
+            url: None,
+            rules: None,
+            date: Some("26-28 September 2025".to_owned()),
+            place: None,
+            approval_status: None,
+            registration_start: None,
+            results_status: None,
+            results_url: None,
+            previous: None,
+        };
+
+        assert_eq!(
+            build_message(
+                &TournamentStatuses(vec![TournamentStatus::Renamed {
+                    old_name: "Krakow Open".to_owned(),
+                    change: renamed,
+                }]),
+                &HashMap::new(),
+                Language::English.messages()
+            ),
+            "* **RENAMED**: _Krakow Open_ → _Krakow Riichi Open 2025_; date: 26-28 September 2025\n"
+        );
+    }
+
+    #[test]
+    fn test_message_prefix() {
+        let feed_entry = TournamentEntry {
+            name: "Poznan MCR Cup".to_owned(),
+            source: "pl".to_owned(),
+            url: String::new(),
+            rules: "MCR".to_owned(),
+            date: "5 April 2025".to_owned(),
+            place: "Poznan".to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        };
+        let ema_entry = TournamentEntry {
+            name: "Krakow Riichi Open".to_owned(),
+            source: "ema".to_owned(),
+            ..feed_entry.clone()
+        };
+
+        assert_eq!(
+            message_prefix(
+                &TournamentStatuses(vec![TournamentStatus::New(feed_entry.clone())]),
+                Language::English.messages()
+            ),
+            "**TOURNAMENTS UPDATE**\n\n"
+        );
+        assert_eq!(
+            message_prefix(
+                &TournamentStatuses(vec![
+                    TournamentStatus::New(feed_entry),
+                    TournamentStatus::New(ema_entry)
+                ]),
+                Language::English.messages()
+            ),
+            "**TOURNAMENTS UPDATE** (http://mahjong-europe.org/ranking/Calendar.html)\n\n"
+        );
+    }
+
+    #[test]
+    fn test_filter_by_rulesets() {
+        let entry = |name: &str, rules: &str| TournamentEntry {
+            name: name.to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: rules.to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        };
+        let statuses = TournamentStatuses(vec![
+            TournamentStatus::New(entry("Krakow Riichi Open", "Riichi")),
+            TournamentStatus::New(entry("Krakow MCR Open", "MCR")),
+        ]);
+
+        assert_eq!(
+            build_message(
+                &statuses.with_rulesets(&RulesetSubscription::new(vec![Ruleset::Mcr])),
+                &HashMap::new(),
+                Language::English.messages()
+            ),
+            "* [MCR] **NEW**: _Krakow MCR Open_; 27-29 September 2024; Krakow; MERS: OK\n"
+        );
+        assert_eq!(
+            statuses.with_rulesets(&RulesetSubscription::default()).0,
+            vec![TournamentStatus::New(entry("Krakow Riichi Open", "Riichi"))]
+        );
+
+        let mixed = TournamentStatus::New(entry("Krakow Mahjong Festival", "Riichi & MCR"));
+        assert_eq!(
+            TournamentStatuses(vec![mixed.clone()])
+                .with_rulesets(&RulesetSubscription::default())
+                .0,
+            vec![mixed]
+        );
+    }
+
+    #[test]
+    fn test_filter_by_sources() {
+        let entry = |name: &str, source: &str| TournamentEntry {
+            name: name.to_owned(),
+            source: source.to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        };
+        let statuses = TournamentStatuses(vec![
+            TournamentStatus::New(entry("Krakow Riichi Open", "ema")),
+            TournamentStatus::New(entry("Krakow Riichi League", "pzm")),
+        ]);
+
+        assert_eq!(
+            build_message(
+                &statuses.with_sources(&SourceSubscription::new(vec!["pzm".to_owned()])),
+                &HashMap::new(),
+                Language::English.messages()
+            ),
+            "* [pzm] **NEW**: _Krakow Riichi League_; 27-29 September 2024; Krakow\n"
+        );
+        assert_eq!(
+            statuses.with_sources(&SourceSubscription::default()).0,
+            vec![TournamentStatus::New(entry("Krakow Riichi Open", "ema"))]
+        );
+    }
+
+    #[test]
+    fn test_watcher_sends_message_on_change() -> std::io::Result<()> {
+        let calendar_1 = WatchedTournaments::new(
+            parse_tournaments(include_str!("test_data/calendar_1.html")).unwrap(),
+        );
+        let calendar_2 = WatchedTournaments::new(
+            parse_tournaments(include_str!("test_data/calendar_2.html")).unwrap(),
+        );
+        let expected_diff = calendar_1
+            .should_notify(&calendar_2)
+            .unwrap()
+            .with_rulesets(&RulesetSubscription::default());
+        let responses = Arc::new(Mutex::new(VecDeque::from([calendar_1, calendar_2])));
+
+        let channel_id = ChannelId::new(1);
+        let clock = Arc::new(ManualClock::new(
+            Utc.with_ymd_and_hms(2024, 9, 1, 12, 0, 0).unwrap(),
+        ));
+        let message_sink = Arc::new(RecordingMessageSink::new());
+        let mut watcher = DataWatcher::new(move || {
+            let response = responses.lock().unwrap().pop_front();
+            std::future::ready(response.map(Some).ok_or_else(|| "No more data".to_owned()))
+        })
+        .with_clock(clock.clone())
+        .with_notifier(TournamentsChannelMessageNotifier::new(
+            channel_id,
+            message_sink.clone(),
+        ));
+
+        let (_commands_sender, commands) = unbounded_channel();
+        let cancellation_token = CancellationToken::new();
+        let health = WatcherHealthRef::default();
+        let control = WatcherControl {
+            commands,
+            cancellation_token: cancellation_token.clone(),
+            health: health.clone(),
+        };
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                let handle = tokio::spawn(async move { watcher.run(control).await });
+
+                while health.read().await.last_success.is_none() {
+                    tokio::task::yield_now().await;
+                }
+                assert!(message_sink.embeds().await.is_empty());
+
+                clock.advance(TimeDelta::minutes(10));
+                while message_sink.embeds().await.is_empty() {
+                    tokio::task::yield_now().await;
+                }
+                assert_eq!(
+                    message_sink.embeds().await,
+                    vec![(
+                        channel_id,
+                        "**TOURNAMENTS UPDATE** (http://mahjong-europe.org/ranking/Calendar.html)"
+                            .to_owned(),
+                        status_embeds(
+                            &expected_diff,
+                            &HashMap::new(),
+                            Language::English.messages()
+                        )
+                        .unwrap()
+                        .into_iter()
+                        .map(CreateEmbed::from)
+                        .collect()
+                    )]
+                );
+                assert!(message_sink.messages().await.is_empty());
+
+                cancellation_token.cancel();
+                handle.await.unwrap();
+            });
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_threads() -> std::io::Result<()> {
+        let renamed = TournamentStatus::Renamed {
+            old_name: "Krakow Open".to_owned(),
+            change: TournamentChange {
+                name: "Krakow Riichi Open".to_owned(),
+                source: "ema".to_owned(),
+                ruleset: Some(Ruleset::Riichi),
+                url: None,
+                rules: None,
+                date: Some("26-28 September 2025".to_owned()),
+                place: None,
+                approval_status: None,
+                registration_start: None,
+                results_status: None,
+                results_url: None,
+                previous: None,
+            },
+        };
+        let new = TournamentStatus::New(TournamentEntry {
+            name: "Paris Open".to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: "1-2 November 2025".to_owned(),
+            place: "Paris".to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        });
+
+        let channel_id = ChannelId::new(1);
+        let message_sink = Arc::new(RecordingMessageSink::new());
+        let thread_sink = Arc::new(RecordingThreadSink {
+            threads: vec![TournamentThread {
+                id: ChannelId::new(10),
+                name: "Krakow Open".to_owned(),
+            }],
+            ..RecordingThreadSink::default()
+        });
+        let notifier = TournamentsChannelMessageNotifier::new(
+            TournamentWatcherChannel::new(channel_id)
+                .with_threads(true)
+                .with_filter(
+                    NotificationFilter::default()
+                        .with_role_id(NotificationKind::New, Some(RoleId::new(69))),
+                ),
+            message_sink.clone(),
+        )
+        .with_thread_sink(thread_sink.clone());
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                notifier
+                    .notify(&TournamentStatuses(vec![renamed.clone(), new.clone()]))
+                    .await
+                    .unwrap();
+
+                assert_eq!(
+                    message_sink.embeds().await,
+                    vec![(
+                        ChannelId::new(10),
+                        String::new(),
+                        vec![
+                            StatusEmbed::for_status(&renamed, None, Language::English.messages())
+                                .into()
+                        ]
+                    )]
+                );
+            });
+        assert_eq!(
+            *thread_sink.calls.lock().unwrap(),
+            vec![
+                ThreadCall::Rename(ChannelId::new(10), "Krakow Riichi Open".to_owned()),
+                ThreadCall::Create(
+                    channel_id,
+                    "Paris Open".to_owned(),
+                    "<@&69>".to_owned(),
+                    vec![StatusEmbed::for_status(&new, None, Language::English.messages()).into()]
+                ),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_threads_after_failure() -> std::io::Result<()> {
+        let renamed = TournamentStatus::Renamed {
+            old_name: "Krakow Open".to_owned(),
+            change: TournamentChange {
+                name: "Krakow Riichi Open".to_owned(),
+                source: "ema".to_owned(),
+                ruleset: Some(Ruleset::Riichi),
+                url: None,
+                rules: None,
+                date: Some("26-28 September 2025".to_owned()),
+                place: None,
+                approval_status: None,
+                registration_start: None,
+                results_status: None,
+                results_url: None,
+                previous: None,
+            },
+        };
+        let new = TournamentStatus::New(TournamentEntry {
+            name: "Paris Open".to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: "1-2 November 2025".to_owned(),
+            place: "Paris".to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        });
+
+        let channel_id = ChannelId::new(1);
+        let message_sink = Arc::new(RecordingMessageSink::new());
+        let thread_sink = Arc::new(RecordingThreadSink {
+            threads: vec![TournamentThread {
+                id: ChannelId::new(10),
+                name: "Krakow Open".to_owned(),
+            }],
+            failing_renames: true,
+            ..RecordingThreadSink::default()
+        });
+        let notifier = TournamentsChannelMessageNotifier::new(
+            TournamentWatcherChannel::new(channel_id).with_threads(true),
+            message_sink.clone(),
+        )
+        .with_thread_sink(thread_sink.clone());
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                assert!(notifier
+                    .notify(&TournamentStatuses(vec![renamed, new.clone()]))
+                    .await
+                    .is_err());
+                assert!(message_sink.embeds().await.is_empty());
+            });
+        assert_eq!(
+            *thread_sink.calls.lock().unwrap(),
+            vec![ThreadCall::Create(
+                channel_id,
+                "Paris Open".to_owned(),
+                String::new(),
+                vec![StatusEmbed::for_status(&new, None, Language::English.messages()).into()]
+            )]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_threads_of_editions() -> std::io::Result<()> {
+        let entry = TournamentEntry {
+            name: "Krakow Riichi Open".to_owned(),
+            source: "ema".to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: "26-28 September 2025".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        };
+        let new = TournamentStatus::New(entry.clone());
+        let changed = TournamentStatus::Changed(TournamentChange {
+            name: entry.name.clone(),
+            source: entry.source.clone(),
+            ruleset: Some(Ruleset::Riichi),
+            url: None,
+            rules: None,
+            date: None,
+            place: Some("Warsaw".to_owned()),
+            approval_status: None,
+            registration_start: None,
+            results_status: None,
+            results_url: None,
+            previous: Some(Box::new(entry)),
+        });
+
+        let dir = TempDir::new()?;
+        let channel = TournamentWatcherChannel::new(ChannelId::new(1)).with_threads(true);
+        let message_sink = Arc::new(RecordingMessageSink::new());
+        // the thread of the previous edition
+        let thread_sink = Arc::new(RecordingThreadSink {
+            threads: vec![TournamentThread {
+                id: ChannelId::new(10),
+                name: "Krakow Riichi Open".to_owned(),
+            }],
+            ..RecordingThreadSink::default()
+        });
+        let notifier = || {
+            TournamentsChannelMessageNotifier::new(channel.clone(), message_sink.clone())
+                .with_thread_sink(thread_sink.clone())
+                .with_thread_store(JsonFileSnapshotStore::new(dir.path().join("threads.json")))
+        };
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async {
+                notifier()
+                    .notify(&TournamentStatuses(vec![new.clone()]))
+                    .await
+                    .unwrap();
+                // the thread is remembered across restarts
+                notifier()
+                    .notify(&TournamentStatuses(vec![changed.clone()]))
+                    .await
+                    .unwrap();
+
+                assert_eq!(
+                    message_sink.embeds().await,
+                    vec![(
+                        ChannelId::new(101),
+                        String::new(),
+                        vec![
+                            StatusEmbed::for_status(&changed, None, Language::English.messages())
+                                .into()
+                        ]
+                    )]
+                );
+            });
+        assert_eq!(
+            *thread_sink.calls.lock().unwrap(),
+            vec![ThreadCall::Create(
+                ChannelId::new(1),
+                "Krakow Riichi Open".to_owned(),
+                String::new(),
+                vec![StatusEmbed::for_status(&new, None, Language::English.messages()).into()]
+            )]
+        );
+
+        Ok(())
+    }
 }
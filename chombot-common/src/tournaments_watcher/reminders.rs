@@ -0,0 +1,421 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use anyhow::bail;
+use async_trait::async_trait;
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
+use log::error;
+use poise::serenity_prelude::{ChannelId, RoleId};
+use tokio::sync::RwLock;
+
+use crate::data_watcher::{DataUpdateNotifier, WatchableData};
+use crate::i18n::{fill, Language, Messages};
+use crate::message_sink::MessageSink;
+use crate::tournaments_watcher::ema::{TournamentEntry, TournamentsFetchError};
+use crate::tournaments_watcher::rulesets::RulesetSubscription;
+use crate::tournaments_watcher::sources::{SourceSubscription, TournamentSources};
+
+/// Time of the day (UTC) at which the reminders are sent.
+const REMINDER_TIME: NaiveTime = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+
+/// How many days before an event the reminders should be sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReminderOffsets {
+    pub registration_start_days: Vec<u32>,
+    pub tournament_start_days: Vec<u32>,
+}
+
+impl Default for ReminderOffsets {
+    fn default() -> Self {
+        Self {
+            registration_start_days: vec![7, 1],
+            tournament_start_days: vec![1],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReminderKind {
+    RegistrationStart,
+    TournamentStart,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TournamentReminder {
+    pub kind: ReminderKind,
+    pub days_before: u32,
+    pub entry: TournamentEntry,
+}
+
+impl TournamentReminder {
+    #[must_use]
+    fn as_message(&self, messages: &Messages) -> String {
+        let when = match self.days_before {
+            0 => messages.reminder_today.to_owned(),
+            1 => messages.reminder_tomorrow.to_owned(),
+            days => fill(messages.reminder_in_days, &[("days", &days.to_string())]),
+        };
+        let mut str = match self.kind {
+            ReminderKind::RegistrationStart => fill(
+                messages.registration_opens,
+                &[
+                    ("name", &self.entry.name),
+                    ("when", &when),
+                    ("date", &self.entry.registration_start),
+                ],
+            ),
+            ReminderKind::TournamentStart => fill(
+                messages.tournament_starts,
+                &[
+                    ("name", &self.entry.name),
+                    ("when", &when),
+                    ("date", &self.entry.date),
+                    ("place", &self.entry.place),
+                ],
+            ),
+        };
+        if !self.entry.url.is_empty() {
+            let _ = write!(str, " {}", self.entry.url);
+        }
+
+        str
+    }
+}
+
+/// The tournament list fetched at given time. Every check yields the
+/// reminders that became due since the previous one; the reminders that
+/// became due while the bot was not running are not sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TournamentReminderCheck {
+    pub checked_at: DateTime<Utc>,
+    pub tournaments: Vec<TournamentEntry>,
+    pub offsets: ReminderOffsets,
+}
+
+impl TournamentReminderCheck {
+    #[must_use]
+    fn due_reminders(&self, since: DateTime<Utc>) -> Vec<TournamentReminder> {
+        let is_due = |date: NaiveDate, days_before: u32| {
+            date.checked_sub_days(Days::new(days_before.into()))
+                .map(|day| day.and_time(REMINDER_TIME).and_utc())
+                .is_some_and(|due_at| since < due_at && due_at <= self.checked_at)
+        };
+
+        let mut reminders = Vec::new();
+        for entry in &self.tournaments {
+            let dates = [
+                (
+                    ReminderKind::RegistrationStart,
+                    entry.registration_start_date(),
+                    &self.offsets.registration_start_days,
+                ),
+                (
+                    ReminderKind::TournamentStart,
+                    entry.date_range().map(|range| range.start),
+                    &self.offsets.tournament_start_days,
+                ),
+            ];
+            for (kind, date, offsets) in dates {
+                let Some(date) = date else {
+                    continue;
+                };
+                reminders.extend(
+                    offsets
+                        .iter()
+                        .filter(|&&days_before| is_due(date, days_before))
+                        .map(|&days_before| TournamentReminder {
+                            kind,
+                            days_before,
+                            entry: entry.clone(),
+                        }),
+                );
+            }
+        }
+
+        reminders
+    }
+}
+
+impl WatchableData for TournamentReminderCheck {
+    type Diff = Vec<TournamentReminder>;
+
+    fn should_notify(&self, new: &Self) -> Option<Self::Diff> {
+        let reminders = new.due_reminders(self.checked_at);
+        if reminders.is_empty() {
+            None
+        } else {
+            Some(reminders)
+        }
+    }
+}
+
+pub async fn get_tournament_reminder_check(
+    offsets: ReminderOffsets,
+    sources: TournamentSources,
+) -> Result<Option<TournamentReminderCheck>, TournamentsFetchError> {
+    let tournaments = sources.tournaments().await?;
+    Ok(Some(TournamentReminderCheck {
+        checked_at: Utc::now(),
+        tournaments: tournaments.0,
+        offsets,
+    }))
+}
+
+/// Channel to send the reminders to, along with the role to ping and the
+/// rulesets and sources of the tournaments to remind about.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReminderTarget {
+    pub channel_id: ChannelId,
+    pub role_id: Option<RoleId>,
+    pub rulesets: RulesetSubscription,
+    pub sources: SourceSubscription,
+    /// Language the reminders are sent in
+    pub language: Language,
+}
+
+impl ReminderTarget {
+    #[must_use]
+    pub fn new(channel_id: ChannelId) -> Self {
+        Self {
+            channel_id,
+            role_id: None,
+            rulesets: RulesetSubscription::default(),
+            sources: SourceSubscription::default(),
+            language: Language::default(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_role_id(mut self, role_id: Option<RoleId>) -> Self {
+        self.role_id = role_id;
+        self
+    }
+
+    #[must_use]
+    pub fn with_rulesets(mut self, rulesets: RulesetSubscription) -> Self {
+        self.rulesets = rulesets;
+        self
+    }
+
+    #[must_use]
+    pub fn with_sources(mut self, sources: SourceSubscription) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    #[must_use]
+    fn wants(&self, entry: &TournamentEntry) -> bool {
+        self.rulesets.includes(entry.ruleset()) && self.sources.contains(&entry.source)
+    }
+}
+
+#[async_trait]
+pub trait TournamentReminderTargetProvider: Send + Sync {
+    async fn tournament_reminder_targets(&self) -> Vec<ReminderTarget>;
+}
+
+#[async_trait]
+impl TournamentReminderTargetProvider for ReminderTarget {
+    async fn tournament_reminder_targets(&self) -> Vec<Self> {
+        vec![self.clone()]
+    }
+}
+
+#[async_trait]
+impl<T: TournamentReminderTargetProvider> TournamentReminderTargetProvider for Arc<RwLock<T>> {
+    async fn tournament_reminder_targets(&self) -> Vec<ReminderTarget> {
+        self.read().await.tournament_reminder_targets().await
+    }
+}
+
+pub struct TournamentRemindersNotifier<T> {
+    target_provider: T,
+    message_sink: Arc<dyn MessageSink>,
+}
+
+impl<T: TournamentReminderTargetProvider> TournamentRemindersNotifier<T> {
+    #[must_use]
+    pub fn new(target_provider: T, message_sink: Arc<dyn MessageSink>) -> Self {
+        Self {
+            target_provider,
+            message_sink,
+        }
+    }
+}
+
+#[must_use]
+fn build_message(
+    reminders: &[TournamentReminder],
+    role_id: Option<RoleId>,
+    messages: &Messages,
+) -> String {
+    let mut str = String::new();
+    if let Some(role_id) = role_id {
+        let _ = write!(str, "<@&{role_id}> ");
+    }
+    let _ = write!(str, "**{}**\n\n", messages.tournament_reminder);
+    for reminder in reminders {
+        let _ = writeln!(str, "* {}", reminder.as_message(messages));
+    }
+
+    str
+}
+
+#[async_trait]
+impl<T: TournamentReminderTargetProvider> DataUpdateNotifier<Option<TournamentReminderCheck>>
+    for TournamentRemindersNotifier<T>
+{
+    async fn notify(&self, diff: &Vec<TournamentReminder>) -> anyhow::Result<()> {
+        let mut failed_channels = 0;
+        for target in self.target_provider.tournament_reminder_targets().await {
+            let reminders: Vec<_> = diff
+                .iter()
+                .filter(|reminder| target.wants(&reminder.entry))
+                .cloned()
+                .collect();
+            if reminders.is_empty() {
+                continue;
+            }
+            let text = build_message(&reminders, target.role_id, target.language.messages());
+            if let Err(why) = self
+                .message_sink
+                .send_message(target.channel_id, &text)
+                .await
+            {
+                error!(
+                    "Could not send tournament reminder to {}: {why:?}",
+                    target.channel_id
+                );
+                failed_channels += 1;
+            }
+        }
+
+        if failed_channels > 0 {
+            bail!("Could not send tournament reminder to {failed_channels} channel(s)");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use poise::serenity_prelude::{ChannelId, RoleId};
+
+    use crate::data_watcher::WatchableData;
+    use crate::i18n::Language;
+    use crate::tournaments_watcher::ema::TournamentEntry;
+    use crate::tournaments_watcher::reminders::{
+        build_message, ReminderKind, ReminderOffsets, ReminderTarget, TournamentReminder,
+        TournamentReminderCheck,
+    };
+
+    fn krakow_riichi_open() -> TournamentEntry {
+        TournamentEntry {
+            name: "Krakow Riichi Open".to_owned(),
+            source: "ema".to_owned(),
+            url: "https://chombo.club".to_owned(),
+            rules: "Riichi".to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: "Sept. 1st 2024".to_owned(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    fn check(day: u32, hour: u32) -> TournamentReminderCheck {
+        TournamentReminderCheck {
+            checked_at: Utc.with_ymd_and_hms(2024, 8, day, hour, 0, 0).unwrap(),
+            tournaments: vec![krakow_riichi_open()],
+            offsets: ReminderOffsets {
+                registration_start_days: vec![7, 1],
+                tournament_start_days: vec![30],
+            },
+        }
+    }
+
+    #[test]
+    fn test_due_reminders() {
+        assert_eq!(check(25, 8).should_notify(&check(25, 8)), None);
+        assert_eq!(check(24, 10).should_notify(&check(25, 8)), None);
+        assert_eq!(
+            check(25, 8).should_notify(&check(25, 10)),
+            Some(vec![TournamentReminder {
+                kind: ReminderKind::RegistrationStart,
+                days_before: 7,
+                entry: krakow_riichi_open(),
+            }])
+        );
+        assert_eq!(
+            check(28, 8).should_notify(&check(31, 10)),
+            Some(vec![
+                TournamentReminder {
+                    kind: ReminderKind::RegistrationStart,
+                    days_before: 1,
+                    entry: krakow_riichi_open(),
+                },
+                TournamentReminder {
+                    kind: ReminderKind::TournamentStart,
+                    days_before: 30,
+                    entry: krakow_riichi_open(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_target_rulesets() {
+        let target = ReminderTarget::new(ChannelId::new(1));
+        let mut entry = krakow_riichi_open();
+        assert!(target.wants(&entry));
+        entry.rules = "MCR".to_owned();
+        assert!(!target.wants(&entry));
+        entry.rules = "Riichi & MCR".to_owned();
+        assert!(target.wants(&entry));
+    }
+
+    #[test]
+    fn test_build_message() {
+        let reminders = vec![
+            TournamentReminder {
+                kind: ReminderKind::RegistrationStart,
+                days_before: 7,
+                entry: krakow_riichi_open(),
+            },
+            TournamentReminder {
+                kind: ReminderKind::TournamentStart,
+                days_before: 1,
+                entry: krakow_riichi_open(),
+            },
+        ];
+
+        assert_eq!(
+            build_message(
+                &reminders,
+                Some(RoleId::new(2137)),
+                Language::English.messages()
+            ),
+            "<@&2137> **TOURNAMENT REMINDER**\n\n\
+             * registration for _Krakow Riichi Open_ opens in 7 days (Sept. 1st 2024) \
+             https://chombo.club\n\
+             * _Krakow Riichi Open_ starts in 1 day (27-29 September 2024; Krakow) \
+             https://chombo.club\n"
+        );
+        assert_eq!(
+            build_message(&reminders, None, Language::Polish.messages()),
+            "**PRZYPOMNIENIE O TURNIEJU**\n\n\
+             * rejestracja na _Krakow Riichi Open_ rusza za 7 dni (Sept. 1st 2024) \
+             https://chombo.club\n\
+             * _Krakow Riichi Open_ zaczyna się jutro (27-29 September 2024; Krakow) \
+             https://chombo.club\n"
+        );
+    }
+}
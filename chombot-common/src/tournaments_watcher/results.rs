@@ -0,0 +1,151 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+
+use crate::ema_ranking::ranking::{parse_ranking, Ranking};
+use crate::ema_ranking::watcher::LinkedPlayer;
+use crate::i18n::{fill, Messages};
+use crate::scraping_utils::create_chombot_http_client;
+
+const PODIUM_MEDALS: [&str; 3] = ["🥇", "🥈", "🥉"];
+
+/// Final standings of a tournament, announced when its results are published.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TournamentResults {
+    pub name: String,
+    pub url: String,
+    pub standings: Ranking,
+}
+
+impl TournamentResults {
+    /// Returns the podium along with the placements of given players.
+    #[must_use]
+    pub fn as_message(&self, linked_players: &[LinkedPlayer], messages: &Messages) -> String {
+        let mut str = format!(
+            "**{}**: _{}_ ({})\n",
+            messages.tournament_results, self.name, self.url
+        );
+        for entry in &self.standings.0 {
+            let Some(medal) = entry
+                .rank
+                .checked_sub(1)
+                .and_then(|index| PODIUM_MEDALS.get(index as usize))
+            else {
+                continue;
+            };
+            let _ = write!(str, "{medal} {}", entry.full_name());
+            if !entry.country.is_empty() {
+                let _ = write!(str, " ({})", entry.country);
+            }
+            str.push('\n');
+        }
+
+        let player_count = self.standings.0.len().to_string();
+        let placements = linked_players
+            .iter()
+            .filter_map(|player| {
+                let entry = self.standings.by_ema_id(&player.ema_id)?;
+                Some((entry, player.user_id))
+            })
+            .sorted_by_key(|(entry, _)| entry.rank);
+        for (entry, user_id) in placements {
+            let placement = fill(
+                messages.results_placement,
+                &[
+                    ("ordinal", &ordinal(entry.rank)),
+                    ("rank", &entry.rank.to_string()),
+                    ("count", &player_count),
+                ],
+            );
+            let _ = writeln!(str, "* <@{user_id}> (_{}_): {placement}", entry.full_name());
+        }
+
+        str
+    }
+}
+
+#[must_use]
+fn ordinal(number: u32) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{number}{suffix}")
+}
+
+pub async fn get_tournament_results(name: &str, url: &str) -> anyhow::Result<TournamentResults> {
+    let body = create_chombot_http_client()?
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    Ok(TournamentResults {
+        name: name.to_owned(),
+        url: url.to_owned(),
+        standings: parse_ranking(&body, url)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use poise::serenity_prelude::UserId;
+
+    use crate::ema_ranking::ranking::parse_ranking;
+    use crate::ema_ranking::watcher::LinkedPlayer;
+    use crate::i18n::Language;
+    use crate::tournaments_watcher::results::{ordinal, TournamentResults};
+
+    #[test]
+    fn test_ordinal() {
+        let ordinals: Vec<_> = [1, 2, 3, 4, 11, 12, 13, 21, 102, 113]
+            .into_iter()
+            .map(ordinal)
+            .collect();
+
+        assert_eq!(
+            ordinals,
+            ["1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "102nd", "113th"]
+        );
+    }
+
+    #[test]
+    fn test_results_message() {
+        let url = "http://mahjong-europe.org/ranking/Tournament/TR_RCR_350.html";
+        let results = TournamentResults {
+            name: "Krakow Riichi Open".to_owned(),
+            url: url.to_owned(),
+            standings: parse_ranking(include_str!("test_data/tournament_results.html"), url)
+                .unwrap(),
+        };
+        assert_eq!(
+            results.standings.0[0].url,
+            "http://mahjong-europe.org/ranking/Players/10990042.html"
+        );
+        let player = |user_id: u64, ema_id: &str| LinkedPlayer {
+            user_id: UserId::new(user_id),
+            ema_id: ema_id.to_owned(),
+        };
+
+        let players = [player(69, "10990077"), player(2137, "10990042")];
+
+        assert_eq!(
+            results.as_message(&players, Language::English.messages()),
+            "**RESULTS**: _Krakow Riichi Open_ \
+             (http://mahjong-europe.org/ranking/Tournament/TR_RCR_350.html)\n\
+             🥇 Jan Kowalski (PL)\n\
+             🥈 Marie Dupont (FR)\n\
+             🥉 Tom Smith\n\
+             * <@2137> (_Jan Kowalski_): 1st of 5\n\
+             * <@69> (_Anna Kowalska_): 5th of 5\n"
+        );
+        assert!(results
+            .as_message(&players, Language::Polish.messages())
+            .ends_with("* <@69> (_Anna Kowalska_): 5. miejsce na 5\n"));
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::{Display, Formatter};
+
+use clap::ValueEnum;
+use poise::ChoiceParameter;
+use serde::{Deserialize, Serialize};
+
+/// Rulesets of the tournaments in the EMA calendar.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ChoiceParameter,
+    ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Ruleset {
+    Riichi,
+    #[name = "MCR"]
+    Mcr,
+}
+
+impl Ruleset {
+    /// Returns the name used in the "Rules" column of the EMA calendar.
+    #[must_use]
+    pub const fn rules_name(self) -> &'static str {
+        match self {
+            Self::Riichi => "Riichi",
+            Self::Mcr => "MCR",
+        }
+    }
+
+    /// Returns the URL of the EMA player ranking for the ruleset.
+    #[must_use]
+    pub const fn ranking_url(self) -> &'static str {
+        match self {
+            Self::Riichi => "http://mahjong-europe.org/ranking/rcr.html",
+            Self::Mcr => "http://mahjong-europe.org/ranking/mcr.html",
+        }
+    }
+
+    #[must_use]
+    pub fn from_rules_name(rules_name: &str) -> Option<Self> {
+        [Self::Riichi, Self::Mcr]
+            .into_iter()
+            .find(|ruleset| ruleset.rules_name().eq_ignore_ascii_case(rules_name.trim()))
+    }
+}
+
+impl Display for Ruleset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rules_name())
+    }
+}
+
+/// Rulesets a guild wants to be notified about. Riichi only by default.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RulesetSubscription(Vec<Ruleset>);
+
+impl RulesetSubscription {
+    #[must_use]
+    pub fn new(mut rulesets: Vec<Ruleset>) -> Self {
+        rulesets.sort_unstable();
+        rulesets.dedup();
+        Self(rulesets)
+    }
+
+    #[must_use]
+    pub fn contains(&self, ruleset: Ruleset) -> bool {
+        self.0.contains(&ruleset)
+    }
+
+    /// Returns whether the tournaments of given ruleset are wanted. The
+    /// tournaments with unknown rulesets (e.g. "Riichi & MCR") are always
+    /// wanted, so that nobody misses them.
+    #[must_use]
+    pub fn includes(&self, ruleset: Option<Ruleset>) -> bool {
+        ruleset.is_none_or(|ruleset| self.contains(ruleset))
+    }
+
+    #[must_use]
+    pub fn rulesets(&self) -> &[Ruleset] {
+        &self.0
+    }
+}
+
+impl Default for RulesetSubscription {
+    fn default() -> Self {
+        Self(vec![Ruleset::Riichi])
+    }
+}
+
+impl Display for RulesetSubscription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<_> = self.0.iter().map(|ruleset| ruleset.rules_name()).collect();
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+
+    #[test]
+    fn test_ruleset_subscription() {
+        let subscription =
+            RulesetSubscription::new(vec![Ruleset::Mcr, Ruleset::Riichi, Ruleset::Mcr]);
+
+        assert_eq!(subscription.rulesets(), &[Ruleset::Riichi, Ruleset::Mcr]);
+        assert!(subscription.includes(Ruleset::from_rules_name("MCR")));
+        assert!(!RulesetSubscription::default().includes(Ruleset::from_rules_name("MCR")));
+        assert!(RulesetSubscription::default().includes(Ruleset::from_rules_name("Riichi & MCR")));
+        assert_eq!(subscription.to_string(), "Riichi, MCR");
+    }
+}
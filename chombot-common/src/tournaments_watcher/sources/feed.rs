@@ -0,0 +1,247 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::scraping_utils::create_chombot_http_client;
+use crate::tournaments_watcher::ema::{
+    TournamentEntry, Tournaments, TournamentsFetchError, EMA_SOURCE_ID,
+};
+use crate::tournaments_watcher::rulesets::Ruleset;
+use crate::tournaments_watcher::sources::TournamentSource;
+
+/// Tournament calendar published by e.g. a national association or a club as
+/// a JSON or CSV file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeedSource {
+    id: String,
+    url: String,
+}
+
+impl FeedSource {
+    #[must_use]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[async_trait]
+impl TournamentSource for FeedSource {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    async fn tournaments(&self) -> Result<Tournaments, TournamentsFetchError> {
+        let body = create_chombot_http_client()
+            .map_err(TournamentsFetchError::FetchError)?
+            .get(&self.url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| TournamentsFetchError::FetchError(err.into()))?
+            .text()
+            .await
+            .map_err(|err| TournamentsFetchError::FetchError(err.into()))?;
+        parse_feed(&self.id, &body).map_err(TournamentsFetchError::ParseError)
+    }
+}
+
+/// Parses the "ID=URL" format used in the arguments.
+impl FromStr for FeedSource {
+    type Err = InvalidFeedSource;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, url) = s
+            .split_once('=')
+            .ok_or_else(|| InvalidFeedSource(format!("expected ID=URL; got `{s}`")))?;
+        let (id, url) = (id.trim(), url.trim());
+
+        let valid_id_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+        if id.is_empty() || !id.chars().all(valid_id_char) {
+            return Err(InvalidFeedSource(format!(
+                "the ID can only contain letters, digits, `-` and `_`; got `{id}`"
+            )));
+        }
+        if id == EMA_SOURCE_ID {
+            return Err(InvalidFeedSource(format!(
+                "the `{EMA_SOURCE_ID}` ID is reserved for the EMA calendar"
+            )));
+        }
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(InvalidFeedSource(format!(
+                "expected an HTTP(S) URL; got `{url}`"
+            )));
+        }
+
+        Ok(Self {
+            id: id.to_owned(),
+            url: url.to_owned(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidFeedSource(String);
+
+impl Display for InvalidFeedSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid tournament feed: {}", self.0)
+    }
+}
+
+impl Error for InvalidFeedSource {}
+
+/// Single tournament in the feed. Only the name and the date are required;
+/// the tournaments without rules are assumed to be Riichi.
+#[derive(Debug, Deserialize)]
+struct FeedEntry {
+    name: String,
+    date: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    rules: String,
+    #[serde(default)]
+    place: String,
+    #[serde(default)]
+    approval_status: String,
+    #[serde(default)]
+    registration_start: String,
+    #[serde(default)]
+    results_status: String,
+    #[serde(default)]
+    results_url: String,
+}
+
+impl FeedEntry {
+    #[must_use]
+    fn into_entry(self, source_id: &str) -> TournamentEntry {
+        let rules = if self.rules.trim().is_empty() {
+            Ruleset::Riichi.rules_name().to_owned()
+        } else {
+            self.rules
+        };
+
+        TournamentEntry {
+            name: self.name,
+            source: source_id.to_owned(),
+            url: self.url,
+            rules,
+            date: self.date,
+            place: self.place,
+            approval_status: self.approval_status,
+            registration_start: self.registration_start,
+            results_status: self.results_status,
+            results_url: self.results_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedDocument {
+    tournaments: Vec<FeedEntry>,
+}
+
+/// Parses a JSON feed (an array of tournaments, or an object with the
+/// `tournaments` array) or a CSV feed with a header row. Both use the
+/// `TournamentEntry` field names.
+pub fn parse_feed(source_id: &str, body: &str) -> anyhow::Result<Tournaments> {
+    let body = body.trim_start_matches('\u{feff}').trim();
+    let entries: Vec<FeedEntry> = if body.starts_with('[') {
+        serde_json::from_str(body).context("Invalid JSON tournament feed")?
+    } else if body.starts_with('{') {
+        serde_json::from_str::<FeedDocument>(body)
+            .context("Invalid JSON tournament feed")?
+            .tournaments
+    } else {
+        csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(body.as_bytes())
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .context("Invalid CSV tournament feed")?
+    };
+
+    Ok(Tournaments(
+        entries
+            .into_iter()
+            .map(|entry| entry.into_entry(source_id))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tournaments_watcher::ema::TournamentEntry;
+    use crate::tournaments_watcher::sources::feed::{parse_feed, FeedSource};
+
+    fn krakow_riichi_open() -> TournamentEntry {
+        TournamentEntry {
+            name: "Krakow Riichi Open".to_owned(),
+            source: "pzm".to_owned(),
+            url: "https://chombo.club".to_owned(),
+            rules: "Riichi".to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: String::new(),
+            registration_start: "Sept. 1st 2024".to_owned(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    fn poznan_mcr_cup() -> TournamentEntry {
+        TournamentEntry {
+            name: "Poznan MCR Cup".to_owned(),
+            source: "pzm".to_owned(),
+            url: String::new(),
+            rules: "MCR".to_owned(),
+            date: "12 October 2024".to_owned(),
+            place: "Poznan".to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_json_feed() {
+        let feed = parse_feed("pzm", include_str!("../test_data/feed.json")).unwrap();
+
+        assert_eq!(feed.0, vec![krakow_riichi_open(), poznan_mcr_cup()]);
+    }
+
+    #[test]
+    fn test_parse_csv_feed() {
+        let feed = parse_feed("pzm", include_str!("../test_data/feed.csv")).unwrap();
+
+        assert_eq!(feed.0, vec![krakow_riichi_open(), poznan_mcr_cup()]);
+    }
+
+    #[test]
+    fn test_parse_invalid_feed() {
+        assert!(parse_feed("pzm", r#"[{"place": "Krakow"}]"#).is_err());
+        assert!(parse_feed("pzm", "name,place\nKrakow Riichi Open,Krakow").is_err());
+    }
+
+    #[test]
+    fn test_parse_feed_source() {
+        let source: FeedSource = "pzm = https://riichi.pl/calendar.json".parse().unwrap();
+        assert_eq!(source.url(), "https://riichi.pl/calendar.json");
+
+        assert!("https://riichi.pl/calendar.json"
+            .parse::<FeedSource>()
+            .is_err());
+        assert!("ema=https://riichi.pl/calendar.json"
+            .parse::<FeedSource>()
+            .is_err());
+        assert!("p z m=https://riichi.pl/calendar.json"
+            .parse::<FeedSource>()
+            .is_err());
+        assert!("pzm=calendar.json".parse::<FeedSource>().is_err());
+    }
+}
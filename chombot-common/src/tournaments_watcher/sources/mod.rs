@@ -0,0 +1,170 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::tournaments_watcher::ema::{
+    EmaCalendarSource, Tournaments, TournamentsFetchError, EMA_SOURCE_ID,
+};
+use crate::tournaments_watcher::sources::feed::FeedSource;
+
+pub mod feed;
+
+/// Calendar the tournaments are fetched from.
+#[async_trait]
+pub trait TournamentSource: Send + Sync {
+    /// Short, unique ID stored as the source of the fetched tournaments.
+    fn id(&self) -> &str;
+
+    /// Returns the tournaments tagged with the source ID.
+    async fn tournaments(&self) -> Result<Tournaments, TournamentsFetchError>;
+}
+
+/// All the tournament sources known to the bot. The EMA calendar is always
+/// included.
+#[derive(Clone)]
+pub struct TournamentSources(Vec<Arc<dyn TournamentSource>>);
+
+impl TournamentSources {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(vec![Arc::new(EmaCalendarSource)])
+    }
+
+    /// Adds given source, replacing the one with the same ID.
+    #[must_use]
+    pub fn with_source(mut self, source: Arc<dyn TournamentSource>) -> Self {
+        self.0.retain(|existing| existing.id() != source.id());
+        self.0.push(source);
+        self
+    }
+
+    #[must_use]
+    pub fn with_feeds(self, feeds: &[FeedSource]) -> Self {
+        feeds.iter().fold(self, |sources, feed| {
+            sources.with_source(Arc::new(feed.clone()))
+        })
+    }
+
+    #[must_use]
+    pub fn ids(&self) -> Vec<&str> {
+        self.0.iter().map(|source| source.id()).collect()
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.0.iter().any(|source| source.id() == id)
+    }
+
+    /// Returns the subscription to all the sources.
+    #[must_use]
+    pub fn subscribe_all(&self) -> SourceSubscription {
+        SourceSubscription::new(self.ids().into_iter().map(ToOwned::to_owned).collect())
+    }
+
+    /// Fetches the tournaments from all the sources. Fails if any of the
+    /// sources fails, so that its tournaments are not reported as removed and
+    /// then as new again.
+    pub async fn tournaments(&self) -> Result<Tournaments, TournamentsFetchError> {
+        let mut entries = Vec::new();
+        for source in &self.0 {
+            let tournaments = source
+                .tournaments()
+                .await
+                .map_err(|err| err.for_source(source.id()))?;
+            info!(
+                "Got {} tournaments from {}",
+                tournaments.0.len(),
+                source.id()
+            );
+            entries.extend(tournaments.0);
+        }
+
+        Ok(Tournaments(entries))
+    }
+}
+
+impl Default for TournamentSources {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for TournamentSources {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TournamentSources")
+            .field(&self.ids())
+            .finish()
+    }
+}
+
+/// Tournament sources a guild wants to be notified about. The EMA calendar
+/// only by default.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SourceSubscription(Vec<String>);
+
+impl SourceSubscription {
+    #[must_use]
+    pub fn new(mut ids: Vec<String>) -> Self {
+        ids.sort_unstable();
+        ids.dedup();
+        Self(ids)
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: &str) -> bool {
+        self.0.iter().any(|subscribed| subscribed == id)
+    }
+
+    #[must_use]
+    pub fn ids(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl Default for SourceSubscription {
+    fn default() -> Self {
+        Self(vec![EMA_SOURCE_ID.to_owned()])
+    }
+}
+
+impl Display for SourceSubscription {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tournaments_watcher::sources::feed::FeedSource;
+    use crate::tournaments_watcher::sources::{SourceSubscription, TournamentSources};
+
+    #[test]
+    fn test_sources_ids() {
+        let sources = TournamentSources::new().with_feeds(&[
+            "pzm=https://riichi.pl/calendar.json"
+                .parse::<FeedSource>()
+                .unwrap(),
+            "club=https://chombo.club/calendar.csv".parse().unwrap(),
+            "pzm=https://riichi.pl/calendar.csv".parse().unwrap(),
+        ]);
+
+        assert_eq!(sources.ids(), vec!["ema", "club", "pzm"]);
+        assert!(sources.contains("club"));
+        assert!(!sources.contains("kcc"));
+    }
+
+    #[test]
+    fn test_source_subscription() {
+        let subscription =
+            SourceSubscription::new(vec!["pzm".to_owned(), "ema".to_owned(), "pzm".to_owned()]);
+
+        assert_eq!(subscription.ids(), &["ema", "pzm"]);
+        assert!(subscription.contains("pzm"));
+        assert!(!SourceSubscription::default().contains("pzm"));
+        assert_eq!(subscription.to_string(), "ema, pzm");
+    }
+}
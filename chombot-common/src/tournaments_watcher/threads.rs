@@ -0,0 +1,207 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use poise::serenity_prelude::{
+    ChannelId, ChannelType, Context, CreateEmbed, CreateForumPost, CreateMessage, CreateThread,
+    EditThread, GuildChannel, Http, UserId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::discord_utils::crosspost_if_announcement;
+use crate::tournaments_watcher::ema::TournamentEntry;
+use crate::tournaments_watcher::embeds::truncate;
+
+const MAX_THREAD_NAME_LEN: usize = 100;
+/// Number of the most recently archived threads searched for the threads
+/// started before the threads were remembered by the tournament.
+const ARCHIVED_THREADS_LIMIT: u64 = 100;
+
+/// Thread (or forum post) with the updates of a single tournament, created
+/// by the bot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TournamentThread {
+    pub id: ChannelId,
+    pub name: String,
+}
+
+/// Identity of a tournament thread. The date tells apart the tournaments
+/// listed under the same name (e.g. the yearly editions).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TournamentThreadKey {
+    pub channel_id: ChannelId,
+    pub source: String,
+    pub name: String,
+    pub date: String,
+}
+
+impl TournamentThreadKey {
+    #[must_use]
+    pub fn new(channel_id: ChannelId, entry: &TournamentEntry) -> Self {
+        Self {
+            channel_id,
+            source: entry.source.clone(),
+            name: entry.name.clone(),
+            date: entry.date.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct TournamentThreadRecord {
+    #[serde(flatten)]
+    key: TournamentThreadKey,
+    thread_id: ChannelId,
+}
+
+/// Threads of the tournaments, remembered by the tournament rather than
+/// by the thread name, so that they are found no matter how long ago they
+/// were archived.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TournamentThreads(Vec<TournamentThreadRecord>);
+
+impl TournamentThreads {
+    #[must_use]
+    pub fn get(&self, key: &TournamentThreadKey) -> Option<ChannelId> {
+        self.0
+            .iter()
+            .find(|record| record.key == *key)
+            .map(|record| record.thread_id)
+    }
+
+    /// Remembers the thread of the tournament, forgetting any other
+    /// tournament the thread was remembered for.
+    pub fn insert(&mut self, key: TournamentThreadKey, thread_id: ChannelId) {
+        self.0
+            .retain(|record| record.key != key && record.thread_id != thread_id);
+        self.0.push(TournamentThreadRecord { key, thread_id });
+    }
+
+    pub fn remove(&mut self, key: &TournamentThreadKey) {
+        self.0.retain(|record| record.key != *key);
+    }
+}
+
+/// Returns the name of the thread with the updates of given tournament.
+#[must_use]
+pub fn thread_name(tournament_name: &str) -> String {
+    truncate(tournament_name, MAX_THREAD_NAME_LEN)
+}
+
+/// Discord threads API, used to post the updates of every tournament in its
+/// own thread. The messages are sent to the threads with a `MessageSink`.
+#[async_trait]
+pub trait TournamentThreadSink: Debug + Send + Sync {
+    /// Returns whether the channel is a forum, where every message has to
+    /// start a post.
+    async fn is_forum(&self, channel_id: ChannelId) -> anyhow::Result<bool>;
+
+    /// Returns the threads created by the bot in the channel.
+    async fn threads(&self, channel_id: ChannelId) -> anyhow::Result<Vec<TournamentThread>>;
+
+    /// Starts a thread (a post in forum channels) with a message with given
+    /// text and embeds; returns the ID of the new thread.
+    async fn create_thread(
+        &self,
+        channel_id: ChannelId,
+        name: &str,
+        text: &str,
+        embeds: Vec<CreateEmbed>,
+    ) -> anyhow::Result<ChannelId>;
+
+    async fn rename_thread(&self, thread_id: ChannelId, name: &str) -> anyhow::Result<()>;
+}
+
+/// Creates the tournament threads in Discord channels; requires the "Create
+/// Public Threads" permission (or "Send Messages" in forum channels).
+#[derive(Clone, Debug)]
+pub struct DiscordThreadSink {
+    http: Arc<Http>,
+    /// ID of the bot user, used to tell the threads created by the bot apart
+    bot_user_id: UserId,
+}
+
+impl DiscordThreadSink {
+    #[must_use]
+    pub const fn new(http: Arc<Http>, bot_user_id: UserId) -> Self {
+        Self { http, bot_user_id }
+    }
+
+    async fn guild_channel(&self, channel_id: ChannelId) -> anyhow::Result<GuildChannel> {
+        channel_id
+            .to_channel(&self.http)
+            .await?
+            .guild()
+            .ok_or_else(|| anyhow!("{channel_id} is not a guild channel"))
+    }
+}
+
+impl From<&Context> for DiscordThreadSink {
+    fn from(ctx: &Context) -> Self {
+        Self::new(ctx.http.clone(), ctx.cache.current_user().id)
+    }
+}
+
+#[async_trait]
+impl TournamentThreadSink for DiscordThreadSink {
+    async fn is_forum(&self, channel_id: ChannelId) -> anyhow::Result<bool> {
+        Ok(self.guild_channel(channel_id).await?.kind == ChannelType::Forum)
+    }
+
+    async fn threads(&self, channel_id: ChannelId) -> anyhow::Result<Vec<TournamentThread>> {
+        let guild_id = self.guild_channel(channel_id).await?.guild_id;
+        let active = guild_id.get_active_threads(&self.http).await?.threads;
+        let archived = channel_id
+            .get_archived_public_threads(&self.http, None, Some(ARCHIVED_THREADS_LIMIT))
+            .await?
+            .threads;
+
+        Ok(active
+            .into_iter()
+            .chain(archived)
+            .filter(|thread| {
+                thread.parent_id == Some(channel_id) && thread.owner_id == Some(self.bot_user_id)
+            })
+            .map(|thread| TournamentThread {
+                id: thread.id,
+                name: thread.name,
+            })
+            .collect())
+    }
+
+    async fn create_thread(
+        &self,
+        channel_id: ChannelId,
+        name: &str,
+        text: &str,
+        embeds: Vec<CreateEmbed>,
+    ) -> anyhow::Result<ChannelId> {
+        let mut message = CreateMessage::new().embeds(embeds);
+        if !text.is_empty() {
+            message = message.content(text);
+        }
+
+        if self.is_forum(channel_id).await? {
+            let post = channel_id
+                .create_forum_post(&self.http, CreateForumPost::new(name, message))
+                .await?;
+            return Ok(post.id);
+        }
+
+        let message = channel_id.send_message(&self.http, message).await?;
+        crosspost_if_announcement(channel_id, &self.http, std::slice::from_ref(&message)).await?;
+        let thread = channel_id
+            .create_thread_from_message(&self.http, message.id, CreateThread::new(name))
+            .await?;
+        Ok(thread.id)
+    }
+
+    async fn rename_thread(&self, thread_id: ChannelId, name: &str) -> anyhow::Result<()> {
+        thread_id
+            .edit_thread(&self.http, EditThread::new().name(name))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,364 @@
+use std::collections::HashSet;
+use std::iter;
+
+use chrono::{NaiveDate, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::data_watcher::WatchableData;
+use crate::tournaments_watcher::ema::{
+    tournaments_diff, TournamentEntry, TournamentStatus, TournamentStatuses, Tournaments,
+    TournamentsFetchError,
+};
+use crate::tournaments_watcher::identity::match_entries;
+use crate::tournaments_watcher::rulesets::Ruleset;
+use crate::tournaments_watcher::sources::{SourceSubscription, TournamentSources};
+
+pub const DEFAULT_REMOVAL_GRACE_FETCHES: u32 = 3;
+
+/// Tournament missing from the recent fetches that has not been announced as
+/// removed yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissingTournament {
+    pub entry: TournamentEntry,
+    /// Number of consecutive fetches the tournament was missing from
+    pub missing_fetches: u32,
+}
+
+/// Tournament list watched for changes. A tournament is announced as removed
+/// only once it is missing from a few fetches in a row, so that the scraping
+/// glitches do not make it disappear and reappear.
+///
+/// The finished tournaments drop off the calendars, so they are never announced
+/// as removed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "WatchedTournamentsSnapshot")]
+pub struct WatchedTournaments {
+    pub tournaments: Tournaments,
+    pub missing: Vec<MissingTournament>,
+    /// Whether the tournaments of all the rulesets are listed; the snapshots
+    /// made before the other rulesets were supported list the Riichi ones
+    /// only
+    pub all_rulesets: bool,
+    /// Number of consecutive fetches a tournament has to be missing from to
+    /// be announced as removed
+    #[serde(skip)]
+    removal_grace_fetches: u32,
+    /// Sources the tournaments were fetched from (`None` if all of them);
+    /// the tournaments from the other sources are dropped silently
+    #[serde(skip)]
+    fetched_sources: Option<SourceSubscription>,
+    /// Date of the fetch; the tournaments that ended before it are not
+    /// announced as removed
+    #[serde(skip)]
+    today: NaiveDate,
+}
+
+impl WatchedTournaments {
+    #[must_use]
+    pub fn new(tournaments: Tournaments) -> Self {
+        Self {
+            tournaments,
+            missing: Vec::new(),
+            all_rulesets: true,
+            removal_grace_fetches: DEFAULT_REMOVAL_GRACE_FETCHES,
+            fetched_sources: None,
+            today: today(),
+        }
+    }
+
+    #[must_use]
+    pub const fn with_removal_grace_fetches(mut self, removal_grace_fetches: u32) -> Self {
+        self.removal_grace_fetches = removal_grace_fetches;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_today(mut self, today: NaiveDate) -> Self {
+        self.today = today;
+        self
+    }
+
+    #[must_use]
+    pub fn with_fetched_sources(mut self, fetched_sources: SourceSubscription) -> Self {
+        self.fetched_sources = Some(fetched_sources);
+        self
+    }
+
+    /// Returns the listed tournaments along with the missing ones.
+    #[must_use]
+    fn known_tournaments(&self) -> Tournaments {
+        let missing = self.missing.iter().map(|missing| missing.entry.clone());
+        Tournaments(self.tournaments.0.iter().cloned().chain(missing).collect())
+    }
+
+    /// Returns the tournaments missing from the new fetch, with their missing
+    /// fetch counts updated.
+    #[must_use]
+    fn missing_after(&self, new: &Self) -> Vec<MissingTournament> {
+        let known = self.known_tournaments();
+        let matched: HashSet<_> = match_entries(&known.0, &new.tournaments.0)
+            .into_iter()
+            .flatten()
+            .collect();
+        let was_fetched = |entry: &TournamentEntry| {
+            new.fetched_sources
+                .as_ref()
+                .is_none_or(|sources| sources.contains(&entry.source))
+        };
+
+        let missing_fetches = iter::repeat_n(0, self.tournaments.0.len())
+            .chain(self.missing.iter().map(|missing| missing.missing_fetches));
+        known
+            .0
+            .into_iter()
+            .zip(missing_fetches)
+            .enumerate()
+            .filter(|(index, (entry, _))| {
+                !matched.contains(index) && was_fetched(entry) && !has_ended(entry, new.today)
+            })
+            .map(|(_, (entry, missing_fetches))| MissingTournament {
+                entry,
+                missing_fetches: missing_fetches + 1,
+            })
+            .collect()
+    }
+}
+
+/// Returns whether the tournament ended before given day; the tournaments
+/// with unknown dates have not.
+#[must_use]
+fn has_ended(entry: &TournamentEntry, today: NaiveDate) -> bool {
+    entry
+        .date_range()
+        .is_some_and(|date_range| date_range.end < today)
+}
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+impl WatchableData for WatchedTournaments {
+    type Diff = TournamentStatuses;
+
+    fn should_notify(&self, new: &Self) -> Option<Self::Diff> {
+        let removal_grace_fetches = new.removal_grace_fetches.max(1);
+        let mut statuses = tournaments_diff(&self.known_tournaments(), &new.tournaments).0;
+        if !self.all_rulesets {
+            // the other tournaments were not listed before, rather than added
+            statuses.retain(|status| {
+                !matches!(status, TournamentStatus::New(_))
+                    || status.ruleset() == Some(Ruleset::Riichi)
+            });
+        }
+        statuses.extend(
+            self.missing_after(new)
+                .into_iter()
+                .filter(|missing| missing.missing_fetches >= removal_grace_fetches)
+                .map(|missing| TournamentStatus::Removed(missing.entry)),
+        );
+
+        if statuses.is_empty() {
+            None
+        } else {
+            Some(TournamentStatuses(statuses))
+        }
+    }
+
+    fn update(&mut self, new: Self) {
+        let removal_grace_fetches = new.removal_grace_fetches.max(1);
+        self.missing = self
+            .missing_after(&new)
+            .into_iter()
+            .filter(|missing| missing.missing_fetches < removal_grace_fetches)
+            .collect();
+        self.tournaments = new.tournaments;
+        self.all_rulesets = new.all_rulesets;
+        self.removal_grace_fetches = new.removal_grace_fetches;
+        self.fetched_sources = new.fetched_sources;
+    }
+}
+
+/// The snapshots made before the removals were detected contain the
+/// tournament list only. Both them and the ones made before the other
+/// rulesets were supported contain the Riichi tournaments only.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WatchedTournamentsSnapshot {
+    Current {
+        tournaments: Tournaments,
+        #[serde(default)]
+        missing: Vec<MissingTournament>,
+        #[serde(default)]
+        all_rulesets: bool,
+    },
+    Legacy(Tournaments),
+}
+
+impl From<WatchedTournamentsSnapshot> for WatchedTournaments {
+    fn from(value: WatchedTournamentsSnapshot) -> Self {
+        match value {
+            WatchedTournamentsSnapshot::Current {
+                tournaments,
+                missing,
+                all_rulesets,
+            } => Self {
+                missing,
+                all_rulesets,
+                ..Self::new(tournaments)
+            },
+            WatchedTournamentsSnapshot::Legacy(tournaments) => Self {
+                all_rulesets: false,
+                ..Self::new(tournaments)
+            },
+        }
+    }
+}
+
+pub async fn get_watched_tournaments(
+    sources: TournamentSources,
+    removal_grace_fetches: u32,
+) -> Result<Option<WatchedTournaments>, TournamentsFetchError> {
+    let tournaments = sources.tournaments().await?;
+    info!(
+        "Got {} tournaments: {:?}",
+        tournaments.0.len(),
+        tournaments.0
+    );
+    Ok(Some(
+        WatchedTournaments::new(tournaments)
+            .with_removal_grace_fetches(removal_grace_fetches)
+            .with_fetched_sources(sources.subscribe_all()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::data_watcher::WatchableData;
+    use crate::tournaments_watcher::ema::{TournamentEntry, TournamentStatus, Tournaments};
+    use crate::tournaments_watcher::sources::SourceSubscription;
+    use crate::tournaments_watcher::watched::WatchedTournaments;
+
+    fn entry(name: &str, source: &str) -> TournamentEntry {
+        dated_entry(name, source, "27-29 September 2024")
+    }
+
+    fn dated_entry(name: &str, source: &str, date: &str) -> TournamentEntry {
+        TournamentEntry {
+            name: name.to_owned(),
+            source: source.to_owned(),
+            url: String::new(),
+            rules: "Riichi".to_owned(),
+            date: date.to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: "OK".to_owned(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        }
+    }
+
+    fn fetched(entries: &[TournamentEntry]) -> WatchedTournaments {
+        WatchedTournaments::new(Tournaments(entries.to_vec()))
+            .with_removal_grace_fetches(2)
+            .with_today(NaiveDate::from_ymd_opt(2024, 9, 1).unwrap())
+    }
+
+    #[test]
+    fn test_removal_grace_period() {
+        let krakow = entry("Krakow Riichi Open", "ema");
+        let poteto = entry("Poteto Riichi Taikai", "ema");
+        let mut watched = fetched(&[krakow.clone(), poteto.clone()]);
+
+        let without_poteto = fetched(std::slice::from_ref(&krakow));
+        assert_eq!(watched.should_notify(&without_poteto), None);
+        watched.update(without_poteto.clone());
+        assert_eq!(watched.missing.len(), 1);
+
+        // the tournament is back, so it is neither new nor removed
+        let with_poteto = fetched(&[krakow, poteto.clone()]);
+        assert_eq!(watched.should_notify(&with_poteto), None);
+        watched.update(with_poteto);
+        assert!(watched.missing.is_empty());
+
+        watched.update(without_poteto.clone());
+        assert_eq!(
+            watched.should_notify(&without_poteto).unwrap().0,
+            vec![TournamentStatus::Removed(poteto)]
+        );
+        watched.update(without_poteto.clone());
+        assert!(watched.missing.is_empty());
+        assert_eq!(watched.should_notify(&without_poteto), None);
+    }
+
+    #[test]
+    fn test_removal_from_unfetched_source() {
+        let mut watched = fetched(&[entry("Krakow Riichi League", "pzm")]);
+        let without_pzm = fetched(&[])
+            .with_removal_grace_fetches(1)
+            .with_fetched_sources(SourceSubscription::default());
+
+        assert_eq!(watched.should_notify(&without_pzm), None);
+        watched.update(without_pzm);
+        assert!(watched.missing.is_empty());
+    }
+
+    #[test]
+    fn test_removal_of_finished_tournament() {
+        let finished = dated_entry("Warsaw Riichi Open", "ema", "3-4 August 2024");
+        let ongoing = dated_entry("Poznan Riichi Open", "ema", "31-1 August 2024");
+        let mut watched = fetched(&[finished, ongoing.clone()]);
+
+        let without_both = fetched(&[]).with_removal_grace_fetches(1);
+        assert_eq!(
+            watched.should_notify(&without_both).unwrap().0,
+            vec![TournamentStatus::Removed(ongoing)]
+        );
+        watched.update(without_both);
+        assert!(watched.missing.is_empty());
+
+        // the finished tournament is dropped before the grace period ends
+        let mut watched = fetched(&[dated_entry("Warsaw Riichi Open", "ema", "3-4 August 2024")]);
+        watched.update(fetched(&[]));
+        assert!(watched.missing.is_empty());
+    }
+
+    #[test]
+    fn test_load_legacy_snapshot() {
+        let krakow = entry("Krakow Riichi Open", "ema");
+        let legacy = serde_json::to_string(&Tournaments(vec![krakow.clone()])).unwrap();
+        let current = serde_json::to_string(&fetched(std::slice::from_ref(&krakow))).unwrap();
+
+        for json in [legacy, current] {
+            let watched: WatchedTournaments = serde_json::from_str(&json).unwrap();
+            assert_eq!(watched.tournaments.0, vec![krakow.clone()]);
+            assert!(watched.missing.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_first_fetch_of_all_rulesets() {
+        let krakow = entry("Krakow Riichi Open", "ema");
+        let poteto = entry("Poteto Riichi Taikai", "ema");
+        let mut mcr = entry("Krakow MCR Open", "ema");
+        mcr.rules = "MCR".to_owned();
+        let riichi_only = "{\"tournaments\":[],\"missing\":[]}";
+        let mut watched: WatchedTournaments = serde_json::from_str(riichi_only).unwrap();
+        assert!(!watched.all_rulesets);
+
+        let all_rulesets = fetched(&[krakow.clone(), mcr]);
+        assert_eq!(
+            watched.should_notify(&all_rulesets).unwrap().0,
+            vec![TournamentStatus::New(krakow.clone())]
+        );
+        watched.update(all_rulesets);
+        assert!(watched.all_rulesets);
+
+        let mut mcr_2 = entry("Warsaw MCR Open", "ema");
+        mcr_2.rules = "MCR".to_owned();
+        let with_new = fetched(&[krakow, poteto, mcr_2]);
+        assert_eq!(watched.should_notify(&with_new).unwrap().0.len(), 2);
+    }
+}
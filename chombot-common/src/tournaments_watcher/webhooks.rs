@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+
+use anyhow::bail;
+use async_trait::async_trait;
+use log::error;
+use poise::serenity_prelude::CreateEmbed;
+use serde::Serialize;
+
+use crate::data_watcher::DataUpdateNotifier;
+use crate::discord_utils::split_message;
+use crate::i18n::{Language, Messages};
+use crate::scraping_utils::create_chombot_http_client;
+use crate::tournaments_watcher::ema::TournamentStatuses;
+use crate::tournaments_watcher::embeds::status_embeds;
+use crate::tournaments_watcher::notifier::{build_message, message_prefix};
+use crate::tournaments_watcher::rulesets::RulesetSubscription;
+use crate::tournaments_watcher::sources::SourceSubscription;
+use crate::tournaments_watcher::watched::WatchedTournaments;
+
+/// Discord webhook the tournament updates are posted to; unlike the
+/// channels, it does not require the bot to be a member of the guild.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DiscordWebhook {
+    pub url: String,
+    /// Name the updates are posted under (the name of the webhook if not set)
+    pub username: Option<String>,
+    /// Avatar the updates are posted with (the avatar of the webhook if not
+    /// set)
+    pub avatar_url: Option<String>,
+}
+
+/// Omits the URL, as it contains the webhook token.
+impl Debug for DiscordWebhook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiscordWebhook")
+            .field("username", &self.username)
+            .field("avatar_url", &self.avatar_url)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Message executed with a Discord webhook.
+#[derive(Clone, Debug, Serialize)]
+struct WebhookMessage<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    username: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avatar_url: Option<&'a str>,
+    content: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    embeds: Vec<CreateEmbed>,
+}
+
+/// Posts the tournament updates to Discord webhooks, formatted like the
+/// channel messages.
+#[derive(Clone, Debug)]
+pub struct TournamentsWebhookNotifier {
+    webhooks: Vec<DiscordWebhook>,
+    /// Rulesets of the tournaments to notify about
+    rulesets: RulesetSubscription,
+    /// IDs of the sources of the tournaments to notify about
+    sources: SourceSubscription,
+    /// Language the updates are posted in
+    language: Language,
+    client: reqwest::Client,
+}
+
+impl TournamentsWebhookNotifier {
+    pub fn new(webhooks: Vec<DiscordWebhook>) -> anyhow::Result<Self> {
+        Ok(Self {
+            webhooks,
+            rulesets: RulesetSubscription::default(),
+            sources: SourceSubscription::default(),
+            language: Language::default(),
+            client: create_chombot_http_client()?,
+        })
+    }
+
+    #[must_use]
+    pub fn with_rulesets(mut self, rulesets: RulesetSubscription) -> Self {
+        self.rulesets = rulesets;
+        self
+    }
+
+    #[must_use]
+    pub fn with_sources(mut self, sources: SourceSubscription) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    async fn execute(
+        &self,
+        webhook: &DiscordWebhook,
+        diff: &TournamentStatuses,
+    ) -> anyhow::Result<()> {
+        // the errors must not contain the URL, as it contains the webhook token
+        for message in webhook_messages(webhook, diff, self.language.messages()) {
+            self.client
+                .post(&webhook.url)
+                .json(&message)
+                .send()
+                .await
+                .map_err(reqwest::Error::without_url)?
+                .error_for_status()
+                .map_err(reqwest::Error::without_url)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the messages to execute the webhook with: a single message with
+/// the embeds if they fit, or the plain text split into several messages.
+#[must_use]
+fn webhook_messages<'a>(
+    webhook: &'a DiscordWebhook,
+    tournament_statuses: &TournamentStatuses,
+    messages: &Messages,
+) -> Vec<WebhookMessage<'a>> {
+    let message = |content, embeds| WebhookMessage {
+        username: webhook.username.as_deref(),
+        avatar_url: webhook.avatar_url.as_deref(),
+        content,
+        embeds,
+    };
+
+    if let Some(embeds) = status_embeds(tournament_statuses, &HashMap::new(), messages) {
+        return vec![message(
+            message_prefix(tournament_statuses, messages)
+                .trim_end()
+                .to_owned(),
+            embeds.into_iter().map(CreateEmbed::from).collect(),
+        )];
+    }
+
+    // too much to fit in the embeds; fall back to plain text
+    let text = format!(
+        "{}{}",
+        message_prefix(tournament_statuses, messages),
+        build_message(tournament_statuses, &HashMap::new(), messages)
+    );
+    split_message(&text)
+        .into_iter()
+        .map(|content| message(content, Vec::new()))
+        .collect()
+}
+
+#[async_trait]
+impl DataUpdateNotifier<Option<WatchedTournaments>> for TournamentsWebhookNotifier {
+    async fn notify(&self, diff: &TournamentStatuses) -> anyhow::Result<()> {
+        let diff = diff
+            .with_rulesets(&self.rulesets)
+            .with_sources(&self.sources);
+        if diff.0.is_empty() {
+            return Ok(());
+        }
+
+        let mut failed_webhooks = 0;
+        for webhook in &self.webhooks {
+            if let Err(why) = self.execute(webhook, &diff).await {
+                error!("Could not post the tournaments update to a webhook: {why:?}");
+                failed_webhooks += 1;
+            }
+        }
+
+        if failed_webhooks > 0 {
+            bail!("Could not post the tournaments update to {failed_webhooks} webhook(s)");
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::i18n::Language;
+    use crate::tournaments_watcher::ema::{TournamentEntry, TournamentStatus, TournamentStatuses};
+    use crate::tournaments_watcher::webhooks::{webhook_messages, DiscordWebhook};
+
+    fn new_tournament(name: String) -> TournamentStatus {
+        TournamentStatus::New(TournamentEntry {
+            name,
+            source: "ema".to_owned(),
+            url: "https://chombo.club".to_owned(),
+            rules: "Riichi".to_owned(),
+            date: "27-29 September 2024".to_owned(),
+            place: "Krakow".to_owned(),
+            approval_status: String::new(),
+            registration_start: String::new(),
+            results_status: String::new(),
+            results_url: String::new(),
+        })
+    }
+
+    #[test]
+    fn test_webhook_debug() {
+        let webhook = DiscordWebhook {
+            url: "https://discord.com/api/webhooks/1/token".to_owned(),
+            username: None,
+            avatar_url: None,
+        };
+
+        assert!(!format!("{webhook:?}").contains("token"));
+    }
+
+    #[test]
+    fn test_webhook_messages() {
+        let webhook = DiscordWebhook {
+            url: "https://discord.com/api/webhooks/1/token".to_owned(),
+            username: Some("Polish Mahjong Association".to_owned()),
+            avatar_url: None,
+        };
+
+        let messages = webhook_messages(
+            &webhook,
+            &TournamentStatuses(vec![new_tournament("Krakow Riichi Open".to_owned())]),
+            Language::English.messages(),
+        );
+        assert_eq!(messages.len(), 1);
+        let json = serde_json::to_value(&messages[0]).unwrap();
+        assert_eq!(json["username"], "Polish Mahjong Association");
+        assert!(json.get("avatar_url").is_none());
+        assert_eq!(json["embeds"][0]["title"], "NEW: Krakow Riichi Open");
+
+        let messages = webhook_messages(
+            &webhook,
+            &TournamentStatuses(
+                (0..100)
+                    .map(|index| new_tournament(format!("Riichi League #{index}")))
+                    .collect(),
+            ),
+            Language::Polish.messages(),
+        );
+        assert!(messages.len() > 1);
+        assert!(messages.iter().all(|message| message.embeds.is_empty()));
+        assert!(messages[0]
+            .content
+            .starts_with("**AKTUALIZACJA TURNIEJÓW**"));
+    }
+}
@@ -1,5 +1,13 @@
 #![allow(clippy::struct_excessive_bools)]
 
+use std::path::PathBuf;
+
+use chombot_common::i18n::Language;
+use chombot_common::tournaments_watcher::args::{
+    TournamentRemindersArgs, TournamentSourcesArgs, TournamentsIcsArgs,
+    TournamentsWatcherScheduleArgs, TournamentsWebhooksArgs,
+};
+use chombot_common::tournaments_watcher::rulesets::Ruleset;
 use clap::Parser;
 
 #[derive(Parser)]
@@ -10,6 +18,11 @@ pub struct Arguments {
     /// Guild (Discord server) ID
     #[arg(long, env)]
     pub guild_id: u64,
+    /// Language of the bot messages in the guild; if not set, the tournament
+    /// updates are posted in English and the commands reply in the user's
+    /// language
+    #[arg(long, env, value_enum)]
+    pub guild_language: Option<Language>,
 
     /// Enable tournaments watcher
     #[arg(long, env, default_value_t = false)]
@@ -17,6 +30,51 @@ pub struct Arguments {
     /// Tournaments watcher channel ID
     #[arg(long, env)]
     pub tournaments_watcher_channel_id: Option<u64>,
+    /// Post the updates of every tournament in its own thread in the
+    /// tournaments watcher channel (always the case in forum channels)
+    #[arg(long, env, default_value_t = false)]
+    pub tournaments_watcher_threads: bool,
+    /// Path to the file storing the last seen tournament list
+    #[arg(long, env)]
+    pub tournaments_watcher_snapshot_path: Option<PathBuf>,
+    /// Path to the file that all tournament list changes are appended to
+    #[arg(long, env)]
+    pub tournaments_watcher_archive_path: Option<PathBuf>,
+    /// Path to the file storing the threads of the tournaments
+    #[arg(long, env)]
+    pub tournaments_watcher_threads_path: Option<PathBuf>,
+    #[command(flatten)]
+    pub tournaments_watcher_schedule: TournamentsWatcherScheduleArgs,
+    /// Comma-separated list of rulesets of the tournaments to notify about
+    /// (also applies to the tournament reminders)
+    #[arg(long, env, value_enum, value_delimiter = ',', default_values_t = [Ruleset::Riichi])]
+    pub tournaments_watcher_rulesets: Vec<Ruleset>,
+    #[command(flatten)]
+    pub tournament_sources: TournamentSourcesArgs,
+    #[command(flatten)]
+    pub tournaments_ics: TournamentsIcsArgs,
+    #[command(flatten)]
+    pub tournaments_webhooks: TournamentsWebhooksArgs,
+
+    /// Enable tournament reminders (sent to the tournaments watcher channel)
+    #[arg(long, env, default_value_t = false)]
+    pub feature_tournament_reminders: bool,
+    /// Role pinged in the tournament reminders
+    #[arg(long, env)]
+    pub tournament_reminders_role_id: Option<u64>,
+    #[command(flatten)]
+    pub tournament_reminders: TournamentRemindersArgs,
+    /// Enable scraping the tournament websites for details (entry fee,
+    /// player cap etc.)
+    #[arg(long, env, default_value_t = false)]
+    pub feature_tournament_details: bool,
+    /// Enable announcing the podium when the tournament results are published
+    #[arg(long, env, default_value_t = false)]
+    pub feature_tournament_results: bool,
+    /// Enable listing the upcoming tournaments as the guild scheduled events
+    /// (requires the tournaments watcher)
+    #[arg(long, env, default_value_t = false)]
+    pub feature_tournament_events: bool,
 
     /// Enable KCC3 features
     #[arg(long, env, default_value_t = false)]
@@ -7,15 +7,39 @@
 
 extern crate core;
 
+use std::sync::Arc;
+
 use anyhow::Error;
+use chombot_common::bot_status::BotStatus;
 use chombot_common::chombot::ChombotBase;
+use chombot_common::data_watcher::registry::WatcherRegistry;
+use chombot_common::i18n::Language;
+use chombot_common::message_sink::DiscordMessageSink;
+use chombot_common::slash_commands::ema::ema;
 use chombot_common::slash_commands::hand::hand;
-use chombot_common::slash_commands::score::score;
-use chombot_common::{start_tournaments_watcher, ChombotPoiseUserData};
+use chombot_common::slash_commands::score::{score, score_reverse};
+use chombot_common::slash_commands::status::status;
+use chombot_common::slash_commands::tournaments::tournaments;
+use chombot_common::slash_commands::watchers::watchers;
+use chombot_common::tournaments_watcher::details::TournamentDetailsScraper;
+use chombot_common::tournaments_watcher::events::{
+    DiscordScheduledEventSink, TournamentEventGuild, TournamentEventsNotifier,
+};
+use chombot_common::tournaments_watcher::history::TournamentHistory;
+use chombot_common::tournaments_watcher::notifier::TournamentWatcherChannel;
+use chombot_common::tournaments_watcher::reminders::ReminderTarget;
+use chombot_common::tournaments_watcher::rulesets::RulesetSubscription;
+use chombot_common::tournaments_watcher::sources::{SourceSubscription, TournamentSources};
+use chombot_common::tournaments_watcher::threads::DiscordThreadSink;
+use chombot_common::{
+    spawn_shutdown_handler, start_tournament_reminders_watcher, start_tournaments_ics_server,
+    start_tournaments_watcher, ChombotPoiseUserData, TournamentsWatcherOptions,
+};
 use clap::Parser;
 use log::{error, info, LevelFilter};
 use poise::serenity_prelude::{
-    ChannelId, ClientBuilder, Context as SerenityContext, FullEvent, GatewayIntents,
+    ChannelId, ClientBuilder, Context as SerenityContext, FullEvent, GatewayIntents, GuildId,
+    RoleId,
 };
 use poise::{BoxFuture, Command, Context, Framework, FrameworkContext, FrameworkOptions};
 
@@ -35,12 +59,42 @@ const AT_EVERYONE_REACTIONS: [&str; 2] = ["Ichiangry", "Mikiknife"];
 pub struct PoiseUserData {
     pub chombot: ChombotBase,
     pub kcc_chombot: Chombot,
+    pub bot_status: BotStatus,
+    pub watcher_registry: Arc<WatcherRegistry>,
+    pub tournament_details_scraper: Option<Arc<TournamentDetailsScraper>>,
+    pub tournament_sources: TournamentSources,
+    pub tournament_history: Option<TournamentHistory>,
+    pub guild_language: Option<Language>,
 }
 
 impl ChombotPoiseUserData for PoiseUserData {
     fn chombot(&self) -> &ChombotBase {
         &self.chombot
     }
+
+    fn bot_status(&self) -> &BotStatus {
+        &self.bot_status
+    }
+
+    fn watcher_registry(&self) -> &WatcherRegistry {
+        &self.watcher_registry
+    }
+
+    fn tournament_details_scraper(&self) -> Option<&TournamentDetailsScraper> {
+        self.tournament_details_scraper.as_deref()
+    }
+
+    fn tournament_sources(&self) -> &TournamentSources {
+        &self.tournament_sources
+    }
+
+    fn tournament_history(&self) -> Option<&TournamentHistory> {
+        self.tournament_history.as_ref()
+    }
+
+    async fn guild_language(&self, guild_id: Option<GuildId>) -> Option<Language> {
+        guild_id.and(self.guild_language)
+    }
 }
 
 pub type PoiseContext<'a> = Context<'a, PoiseUserData, anyhow::Error>;
@@ -94,7 +148,15 @@ fn event_handler<'a>(
 }
 
 fn get_command_list(args: &Arguments) -> Vec<Command<PoiseUserData, Error>> {
-    let mut ret: Vec<Command<PoiseUserData, Error>> = vec![hand(), score()];
+    let mut ret: Vec<Command<PoiseUserData, Error>> = vec![
+        ema(),
+        hand(),
+        score(),
+        score_reverse(),
+        status(),
+        tournaments(),
+        watchers(),
+    ];
     if args.feature_kcc3 {
         ret.push(chombo());
     }
@@ -108,6 +170,103 @@ fn get_command_list(args: &Arguments) -> Vec<Command<PoiseUserData, Error>> {
     ret
 }
 
+fn get_enabled_features(args: &Arguments) -> Vec<&'static str> {
+    [
+        (args.feature_tournaments_watcher, "tournaments watcher"),
+        (args.feature_tournament_reminders, "tournament reminders"),
+        (args.feature_tournament_details, "tournament details"),
+        (args.feature_tournament_results, "tournament results"),
+        (args.feature_tournament_events, "tournament events"),
+        (
+            !args.tournament_sources.tournament_feeds.is_empty(),
+            "tournament feeds",
+        ),
+        (
+            args.tournaments_ics.tournaments_ics_addr.is_some(),
+            "tournament calendar",
+        ),
+        (args.feature_kcc3, "KCC3"),
+        (args.feature_pasta, "pasta"),
+        (args.feature_fancy_text, "fancy text"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect()
+}
+
+fn get_tournaments_watcher_options(
+    args: &Arguments,
+    details_scraper: Option<Arc<TournamentDetailsScraper>>,
+    sources: TournamentSources,
+) -> TournamentsWatcherOptions {
+    TournamentsWatcherOptions {
+        snapshot_path: args.tournaments_watcher_snapshot_path.clone(),
+        archive_path: args.tournaments_watcher_archive_path.clone(),
+        threads_path: args.tournaments_watcher_threads_path.clone(),
+        schedule: (&args.tournaments_watcher_schedule).into(),
+        details_scraper,
+        results_announcements: args.feature_tournament_results,
+        sources,
+        removal_grace_fetches: args
+            .tournaments_watcher_schedule
+            .tournaments_watcher_removal_grace_fetches,
+        events_notifier: None,
+        thread_sink: None,
+        webhook_notifier: args
+            .tournaments_webhooks
+            .webhook_notifier()
+            .expect("Could not create the tournaments webhook notifier")
+            .map(|notifier| {
+                let (rulesets, sources) = get_tournament_subscriptions(args);
+                notifier.with_rulesets(rulesets).with_sources(sources)
+            }),
+        http_notifier: args
+            .tournaments_webhooks
+            .http_notifier()
+            .expect("Could not create the tournaments HTTP notifier"),
+    }
+}
+
+/// Returns the rulesets and sources of the tournaments the guild is notified
+/// about.
+fn get_tournament_subscriptions(args: &Arguments) -> (RulesetSubscription, SourceSubscription) {
+    (
+        RulesetSubscription::new(args.tournaments_watcher_rulesets.clone()),
+        TournamentSources::from(&args.tournament_sources).subscribe_all(),
+    )
+}
+
+fn get_tournaments_watcher_channel(args: &Arguments) -> TournamentWatcherChannel {
+    let (rulesets, sources) = get_tournament_subscriptions(args);
+    TournamentWatcherChannel::new(ChannelId::from(
+        args.tournaments_watcher_channel_id
+            .expect("Tournaments watcher feature enabled but no channel ID provided"),
+    ))
+    .with_rulesets(rulesets)
+    .with_sources(sources)
+    .with_threads(args.tournaments_watcher_threads)
+    .with_language(args.guild_language.unwrap_or_default())
+}
+
+fn get_reminder_target(args: &Arguments) -> ReminderTarget {
+    let (rulesets, sources) = get_tournament_subscriptions(args);
+    ReminderTarget::new(ChannelId::from(
+        args.tournaments_watcher_channel_id
+            .expect("Tournament reminders feature enabled but no channel ID provided"),
+    ))
+    .with_role_id(args.tournament_reminders_role_id.map(RoleId::from))
+    .with_rulesets(rulesets)
+    .with_sources(sources)
+    .with_language(args.guild_language.unwrap_or_default())
+}
+
+fn get_tournament_event_guild(args: &Arguments) -> TournamentEventGuild {
+    let (rulesets, sources) = get_tournament_subscriptions(args);
+    TournamentEventGuild::new(GuildId::new(args.guild_id))
+        .with_rulesets(rulesets)
+        .with_sources(sources)
+}
+
 #[tokio::main]
 async fn main() {
     env_logger::builder()
@@ -118,6 +277,36 @@ async fn main() {
     let kcc3_client = get_kcc3_client(&args).unwrap();
     let chombot = ChombotBase::new();
     let kcc_chombot = Chombot::new(kcc3_client);
+    let bot_status = BotStatus::new(get_enabled_features(&args));
+    let watcher_registry = Arc::new(WatcherRegistry::new());
+    let watcher_registry_ref = watcher_registry.clone();
+    let tournament_sources = TournamentSources::from(&args.tournament_sources);
+    if let Some(addr) = args.tournaments_ics.tournaments_ics_addr {
+        start_tournaments_ics_server(addr, tournament_sources.clone())
+            .await
+            .expect("Could not start the tournament calendar server");
+    }
+    let tournament_details_scraper = args
+        .feature_tournament_details
+        .then(|| Arc::new(TournamentDetailsScraper::new()));
+    let tournaments_watcher_channel = args
+        .feature_tournaments_watcher
+        .then(|| get_tournaments_watcher_channel(&args));
+    let reminder_target = args
+        .feature_tournament_reminders
+        .then(|| get_reminder_target(&args));
+    let tournament_history = args
+        .tournaments_watcher_archive_path
+        .clone()
+        .map(TournamentHistory::new);
+    let tournament_event_guild = args
+        .feature_tournament_events
+        .then(|| get_tournament_event_guild(&args));
+    let mut tournaments_watcher_options = get_tournaments_watcher_options(
+        &args,
+        tournament_details_scraper.clone(),
+        tournament_sources.clone(),
+    );
 
     let framework = Framework::builder()
         .options(FrameworkOptions {
@@ -127,18 +316,45 @@ async fn main() {
         })
         .setup(move |ctx, ready, framework| {
             Box::pin(async move {
-                if args.feature_tournaments_watcher {
-                    let tournaments_watcher_channel_id =
-                        ChannelId::from(args.tournaments_watcher_channel_id.expect(
-                            "Tournaments watcher feature enabled but no channel ID provided",
-                        ));
-                    start_tournaments_watcher(tournaments_watcher_channel_id, ctx.clone());
+                if let Some(tournaments_watcher_channel) = tournaments_watcher_channel {
+                    tournaments_watcher_options.thread_sink =
+                        Some(Arc::new(DiscordThreadSink::from(ctx)));
+                    tournaments_watcher_options.events_notifier =
+                        tournament_event_guild.map(|guild| {
+                            TournamentEventsNotifier::new(
+                                Arc::new(guild),
+                                Arc::new(DiscordScheduledEventSink::from(ctx)),
+                            )
+                        });
+                    start_tournaments_watcher(
+                        &watcher_registry_ref,
+                        tournaments_watcher_channel,
+                        tournaments_watcher_options,
+                        Arc::new(DiscordMessageSink::from(ctx)),
+                    )
+                    .await;
+                }
+                if let Some(reminder_target) = reminder_target {
+                    start_tournament_reminders_watcher(
+                        &watcher_registry_ref,
+                        reminder_target,
+                        (&args.tournament_reminders).into(),
+                        tournament_sources.clone(),
+                        Arc::new(DiscordMessageSink::from(ctx)),
+                    )
+                    .await;
                 }
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
                 info!("{} is connected!", ready.user.name);
                 Ok(PoiseUserData {
                     chombot,
                     kcc_chombot,
+                    bot_status,
+                    watcher_registry: watcher_registry_ref,
+                    tournament_details_scraper,
+                    tournament_sources,
+                    tournament_history,
+                    guild_language: args.guild_language,
                 })
             })
         })
@@ -149,7 +365,10 @@ async fn main() {
         .await
         .expect("Could not create client");
 
+    spawn_shutdown_handler(client.shard_manager.clone());
+
     if let Err(why) = client.start().await {
         error!("Client error: {why:?}");
     }
+    watcher_registry.shutdown().await;
 }
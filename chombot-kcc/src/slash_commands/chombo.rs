@@ -1,5 +1,6 @@
 use anyhow::Result;
 use chombot_common::data::{DISCORD_EMBED_FIELD_LIMIT, DISCORD_MESSAGE_SIZE_LIMIT};
+use chombot_common::i18n::{context_language, fill, Messages};
 use poise::serenity_prelude::{Color, CreateAllowedMentions, CreateEmbed, User};
 use poise::CreateReply;
 use slug::slugify;
@@ -15,20 +16,26 @@ pub async fn chombo(_: PoiseContext<'_>) -> Result<()> {
 }
 
 /// Display the chombo ranking.
-#[poise::command(slash_command)]
+#[poise::command(slash_command, description_localized("pl", "Pokaż ranking chombo."))]
 async fn ranking(ctx: PoiseContext<'_>) -> Result<()> {
+    let messages = context_language(ctx).await.messages();
     let entries = get_chombos_embed_entries(&ctx.data().kcc_chombot).await?;
 
-    ctx.send(CreateReply::default().embed(create_chombos_embed(entries)))
+    ctx.send(CreateReply::default().embed(create_chombos_embed(entries, messages)))
         .await?;
 
     Ok(())
 }
 
 /// List all chombos.
-#[poise::command(slash_command)]
+#[poise::command(
+    slash_command,
+    name_localized("pl", "lista"),
+    description_localized("pl", "Pokaż wszystkie chombo.")
+)]
 async fn list(ctx: PoiseContext<'_>) -> Result<()> {
-    let chombos = create_chombos_list(&ctx.data().kcc_chombot).await?;
+    let messages = context_language(ctx).await.messages();
+    let chombos = create_chombos_list(&ctx.data().kcc_chombot, messages).await?;
 
     ctx.send(
         CreateReply::default()
@@ -41,13 +48,27 @@ async fn list(ctx: PoiseContext<'_>) -> Result<()> {
 }
 
 /// Add a chombo for a user.
-#[poise::command(slash_command)]
+#[poise::command(
+    slash_command,
+    name_localized("pl", "dodaj"),
+    description_localized("pl", "Dodaj chombo użytkownikowi.")
+)]
 async fn add(
     ctx: PoiseContext<'_>,
-    #[description = "User that made a chombo"] user: User,
-    #[description = "Chombo description"] description: String,
-    #[description = "MERS tournament weight (default: 1)"] weight: Option<ChomboWeight>,
+    #[description = "User that made a chombo"]
+    #[name_localized("pl", "użytkownik")]
+    #[description_localized("pl", "Użytkownik, który zrobił chombo")]
+    user: User,
+    #[description = "Chombo description"]
+    #[name_localized("pl", "opis")]
+    #[description_localized("pl", "Opis chombo")]
+    description: String,
+    #[description = "MERS tournament weight (default: 1)"]
+    #[name_localized("pl", "waga")]
+    #[description_localized("pl", "Waga turnieju MERS (domyślnie: 1)")]
+    weight: Option<ChomboWeight>,
 ) -> Result<()> {
+    let messages = context_language(ctx).await.messages();
     let chombot = &ctx.data().kcc_chombot;
     chombot
         .add_chombo_for_player(
@@ -64,13 +85,13 @@ async fn add(
         )
         .await?;
 
-    let message_content = format_add_message(&user, &description);
+    let message_content = format_add_message(&user, &description, messages);
     let entries = get_chombos_embed_entries(chombot).await?;
 
     ctx.send(
         CreateReply::default()
             .content(message_content)
-            .embed(create_chombos_embed(entries)),
+            .embed(create_chombos_embed(entries, messages)),
     )
     .await?;
 
@@ -87,23 +108,32 @@ async fn get_chombos_embed_entries(
         .map(|(player, score)| (player.short_name(), score.to_string(), true)))
 }
 
-fn create_chombos_embed(entries: impl Iterator<Item = (String, String, bool)>) -> CreateEmbed {
+fn create_chombos_embed(
+    entries: impl Iterator<Item = (String, String, bool)>,
+    messages: &Messages,
+) -> CreateEmbed {
     CreateEmbed::new()
-            .title("**CHOMBO COUNTER**")
+            .title(format!("**{}**", messages.chombo_counter))
             .color(Color::RED)
             .thumbnail("https://cdn.discordapp.com/attachments/591385176685281293/597292309792686090/1562356453777.png")
             .fields(entries)
 }
 
-fn format_add_message(user: &User, description: &str) -> String {
-    format!("Adding chombo for <@!{}>: *{}*", user.id, description)
+fn format_add_message(user: &User, description: &str, messages: &Messages) -> String {
+    fill(
+        messages.chombo_added,
+        &[
+            ("user", &format!("<@!{}>", user.id)),
+            ("description", description),
+        ],
+    )
 }
 
-async fn create_chombos_list(chombot: &Chombot) -> Result<String> {
+async fn create_chombos_list(chombot: &Chombot, messages: &Messages) -> Result<String> {
     let chombo_list = chombot.get_chombo_list().await?;
     let mut result = String::new();
     for (player, chombo) in &chombo_list {
-        let entry = format_chombo_entry(player, chombo);
+        let entry = format_chombo_entry(player, chombo, messages);
         if result.len() + entry.len() <= DISCORD_MESSAGE_SIZE_LIMIT {
             result += &entry;
         } else {
@@ -116,6 +146,7 @@ async fn create_chombos_list(chombot: &Chombot) -> Result<String> {
 
 #[cfg(test)]
 mod tests {
+    use chombot_common::i18n::Language;
     use chrono::{TimeZone, Utc};
 
     use super::*;
@@ -138,6 +169,7 @@ mod tests {
         let result = format_chombo_entry(
             &test_player(),
             &test_chombo("broke the wall", ChomboWeight::W1),
+            Language::English.messages(),
         );
         assert_eq!(
             result,
@@ -150,6 +182,7 @@ mod tests {
         let result = format_chombo_entry(
             &test_player(),
             &test_chombo("broke the wall", ChomboWeight::W2_5),
+            Language::English.messages(),
         );
         assert_eq!(
             result,
@@ -159,18 +192,39 @@ mod tests {
 
     #[test]
     fn format_chombo_entry_default_weight_no_comment() {
-        let result = format_chombo_entry(&test_player(), &test_chombo("", ChomboWeight::W1));
+        let result = format_chombo_entry(
+            &test_player(),
+            &test_chombo("", ChomboWeight::W1),
+            Language::English.messages(),
+        );
         assert_eq!(result, "<@!123456> at Saturday, 2025-03-15 14:30\n");
     }
 
     #[test]
     fn format_chombo_entry_custom_weight_no_comment() {
-        let result = format_chombo_entry(&test_player(), &test_chombo("", ChomboWeight::W2));
+        let result = format_chombo_entry(
+            &test_player(),
+            &test_chombo("", ChomboWeight::W2),
+            Language::English.messages(),
+        );
         assert_eq!(result, "<@!123456> at Saturday, 2025-03-15 14:30 (x2)\n");
     }
+
+    #[test]
+    fn format_chombo_entry_polish() {
+        let result = format_chombo_entry(
+            &test_player(),
+            &test_chombo("broke the wall", ChomboWeight::W2),
+            Language::Polish.messages(),
+        );
+        assert_eq!(
+            result,
+            "<@!123456>, sobota, 2025-03-15 14:30 (x2): *broke the wall*\n"
+        );
+    }
 }
 
-fn format_chombo_entry(player: &Player, chombo: &Chombo) -> String {
+fn format_chombo_entry(player: &Player, chombo: &Chombo, messages: &Messages) -> String {
     let comment = if chombo.comment.is_empty() {
         String::new()
     } else {
@@ -181,10 +235,13 @@ fn format_chombo_entry(player: &Player, chombo: &Chombo) -> String {
     } else {
         format!(" (x{})", chombo.weight)
     };
-    let timestamp = chombo.timestamp.format("%A, %Y-%m-%d %H:%M");
-
-    format!(
-        "<@!{}> at {}{}{}\n",
-        player.discord_id, timestamp, weight, comment
-    )
+    let entry = fill(
+        messages.chombo_entry,
+        &[
+            ("user", &format!("<@!{}>", player.discord_id)),
+            ("timestamp", &messages.format_timestamp(&chombo.timestamp)),
+        ],
+    );
+
+    format!("{entry}{weight}{comment}\n")
 }
@@ -1,5 +1,11 @@
 #![allow(clippy::struct_excessive_bools)]
 
+use std::path::PathBuf;
+
+use chombot_common::tournaments_watcher::args::{
+    TournamentRemindersArgs, TournamentSourcesArgs, TournamentsIcsArgs,
+    TournamentsWatcherScheduleArgs, TournamentsWebhooksArgs,
+};
 use clap::Parser;
 
 #[derive(Parser)]
@@ -7,4 +13,24 @@ pub struct Arguments {
     /// Discord API token
     #[arg(long, env)]
     pub discord_token: String,
+
+    /// Path to the file that all tournament list changes are appended to
+    /// (default: `tournaments_archive.jsonl`)
+    #[arg(long, env)]
+    pub tournaments_watcher_archive_path: Option<PathBuf>,
+    #[command(flatten)]
+    pub tournaments_watcher_schedule: TournamentsWatcherScheduleArgs,
+    #[command(flatten)]
+    pub tournament_reminders: TournamentRemindersArgs,
+    #[command(flatten)]
+    pub tournament_sources: TournamentSourcesArgs,
+    #[command(flatten)]
+    pub tournaments_ics: TournamentsIcsArgs,
+    #[command(flatten)]
+    pub tournaments_webhooks: TournamentsWebhooksArgs,
+
+    /// Enable scraping the tournament websites for details (entry fee,
+    /// player cap etc.)
+    #[arg(long, env, default_value_t = false)]
+    pub feature_tournament_details: bool,
 }
@@ -1,14 +1,29 @@
 #![allow(clippy::struct_excessive_bools)]
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
 
 use async_trait::async_trait;
-use chombot_common::tournaments_watcher::notifier::TournamentWatcherChannelListProvider;
+use chombot_common::ema_ranking::watcher::{
+    EmaRankingTarget, EmaRankingTargetProvider, LinkedPlayer,
+};
+use chombot_common::i18n::Language;
+use chombot_common::tournaments_watcher::events::{
+    TournamentEventGuild, TournamentEventGuildProvider,
+};
+use chombot_common::tournaments_watcher::filters::NotificationFilter;
+use chombot_common::tournaments_watcher::notifier::{
+    TournamentWatcherChannel, TournamentWatcherChannelListProvider,
+};
+use chombot_common::tournaments_watcher::reminders::{
+    ReminderTarget, TournamentReminderTargetProvider,
+};
+use chombot_common::tournaments_watcher::rulesets::RulesetSubscription;
+use chombot_common::tournaments_watcher::sources::SourceSubscription;
 use log::info;
-use poise::serenity_prelude::{ChannelId, GuildId};
+use poise::serenity_prelude::{ChannelId, GuildId, RoleId, UserId};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Default, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -22,17 +37,146 @@ pub struct Config {
 pub struct GuildConfig {
     /// Tournaments watcher channel ID
     pub tournaments_watcher_channel_id: Option<ChannelId>,
+    /// Notification filters of the tournaments watcher channel and the
+    /// additional channels the tournament updates are posted to
+    #[serde(default)]
+    pub tournaments_watcher_channels: HashMap<ChannelId, NotificationFilter>,
+    /// Channels the updates of every tournament are posted to in its own
+    /// thread
+    #[serde(default)]
+    pub tournaments_watcher_thread_channels: HashSet<ChannelId>,
+    /// Whether the tournament reminders are sent to the tournaments watcher
+    /// channel
+    #[serde(default)]
+    pub tournament_reminders: bool,
+    /// Role pinged in the tournament reminders
+    pub tournament_reminders_role_id: Option<RoleId>,
+    /// Rulesets of the tournaments the guild is notified about
+    #[serde(default)]
+    pub tournament_rulesets: RulesetSubscription,
+    /// Sources of the tournaments the guild is notified about
+    #[serde(default)]
+    pub tournament_sources: SourceSubscription,
+    /// Whether the tournaments are listed as the guild scheduled events
+    #[serde(default)]
+    pub tournament_events: bool,
+    /// EMA ranking watcher channel ID
+    pub ema_ranking_channel_id: Option<ChannelId>,
+    /// EMA IDs of the guild members
+    #[serde(default)]
+    pub ema_players: HashMap<UserId, String>,
+    /// Language of the bot messages; if not set, the tournament updates are
+    /// posted in English and the commands reply in the user's language
+    #[serde(default)]
+    pub language: Option<Language>,
+}
+
+impl GuildConfig {
+    /// Returns all the channels the tournament updates are posted to, along
+    /// with their notification filters.
+    #[must_use]
+    pub fn tournament_notification_channels(&self) -> HashMap<ChannelId, NotificationFilter> {
+        let mut channels = self.tournaments_watcher_channels.clone();
+        if let Some(channel_id) = self.tournaments_watcher_channel_id {
+            channels.entry(channel_id).or_default();
+        }
+        channels
+    }
+
+    #[must_use]
+    pub fn linked_players(&self) -> Vec<LinkedPlayer> {
+        self.ema_players
+            .iter()
+            .map(|(&user_id, ema_id)| LinkedPlayer {
+                user_id,
+                ema_id: ema_id.clone(),
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
 impl TournamentWatcherChannelListProvider for ChombotConfig {
-    type TournamentWatcherChannelList = Vec<ChannelId>;
+    type TournamentWatcherChannelList = Vec<TournamentWatcherChannel>;
 
     async fn tournament_watcher_channels(&self) -> Self::TournamentWatcherChannelList {
         self.config
             .guilds
             .values()
-            .filter_map(|config| config.tournaments_watcher_channel_id)
+            .flat_map(|config| {
+                config
+                    .tournament_notification_channels()
+                    .into_iter()
+                    .map(|(channel_id, filter)| {
+                        TournamentWatcherChannel::new(channel_id)
+                            .with_rulesets(config.tournament_rulesets.clone())
+                            .with_sources(config.tournament_sources.clone())
+                            .with_linked_players(config.linked_players())
+                            .with_filter(filter)
+                            .with_threads(
+                                config
+                                    .tournaments_watcher_thread_channels
+                                    .contains(&channel_id),
+                            )
+                            .with_language(config.language.unwrap_or_default())
+                    })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TournamentReminderTargetProvider for ChombotConfig {
+    async fn tournament_reminder_targets(&self) -> Vec<ReminderTarget> {
+        self.config
+            .guilds
+            .values()
+            .filter(|config| config.tournament_reminders)
+            .filter_map(|config| {
+                config.tournaments_watcher_channel_id.map(|channel_id| {
+                    ReminderTarget::new(channel_id)
+                        .with_role_id(config.tournament_reminders_role_id)
+                        .with_rulesets(config.tournament_rulesets.clone())
+                        .with_sources(config.tournament_sources.clone())
+                        .with_language(config.language.unwrap_or_default())
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TournamentEventGuildProvider for ChombotConfig {
+    async fn tournament_event_guilds(&self) -> Vec<TournamentEventGuild> {
+        self.config
+            .guilds
+            .iter()
+            .filter(|(_, config)| config.tournament_events)
+            .map(|(&guild_id, config)| {
+                TournamentEventGuild::new(guild_id)
+                    .with_rulesets(config.tournament_rulesets.clone())
+                    .with_sources(config.tournament_sources.clone())
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EmaRankingTargetProvider for ChombotConfig {
+    async fn ema_ranking_targets(&self) -> Vec<EmaRankingTarget> {
+        self.config
+            .guilds
+            .values()
+            .filter(|config| !config.ema_players.is_empty())
+            .filter_map(|config| {
+                config
+                    .ema_ranking_channel_id
+                    .map(|channel_id| EmaRankingTarget {
+                        channel_id,
+                        players: config.linked_players(),
+                        language: config.language.unwrap_or_default(),
+                    })
+            })
             .collect()
     }
 }
@@ -115,10 +259,26 @@ impl DerefMut for ConfigUpdateGuard<'_> {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
-
-    use chombot_common::tournaments_watcher::notifier::TournamentWatcherChannelListProvider;
-    use poise::serenity_prelude::ChannelId;
+    use std::collections::{HashMap, HashSet};
+    use std::path::PathBuf;
+
+    use chombot_common::ema_ranking::watcher::{
+        EmaRankingTarget, EmaRankingTargetProvider, LinkedPlayer,
+    };
+    use chombot_common::i18n::Language;
+    use chombot_common::tournaments_watcher::events::{
+        TournamentEventGuild, TournamentEventGuildProvider,
+    };
+    use chombot_common::tournaments_watcher::filters::{NotificationFilter, NotificationKind};
+    use chombot_common::tournaments_watcher::notifier::{
+        TournamentWatcherChannel, TournamentWatcherChannelListProvider,
+    };
+    use chombot_common::tournaments_watcher::reminders::{
+        ReminderTarget, TournamentReminderTargetProvider,
+    };
+    use chombot_common::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+    use chombot_common::tournaments_watcher::sources::SourceSubscription;
+    use poise::serenity_prelude::{ChannelId, RoleId, UserId};
     use tempfile::NamedTempFile;
 
     use crate::config::{ChombotConfig, Config, GuildConfig, GuildId};
@@ -134,12 +294,29 @@ mod tests {
                     GuildId::new(69),
                     GuildConfig {
                         tournaments_watcher_channel_id: Some(ChannelId::new(2137)),
+                        ema_ranking_channel_id: Some(ChannelId::new(2137)),
+                        ema_players: HashMap::from([(UserId::new(420), "10990042".to_owned())]),
+                        ..GuildConfig::default()
                     },
                 ),
                 (
                     GuildId::new(420),
                     GuildConfig {
                         tournaments_watcher_channel_id: Some(ChannelId::new(69)),
+                        tournaments_watcher_channels: HashMap::from([(
+                            ChannelId::new(70),
+                            NotificationFilter::default()
+                                .with_kinds(vec![
+                                    NotificationKind::New,
+                                    NotificationKind::Registration,
+                                ])
+                                .with_places(vec!["Poland".to_owned()])
+                                .with_role_id(
+                                    NotificationKind::Registration,
+                                    Some(RoleId::new(2137)),
+                                ),
+                        )]),
+                        ..GuildConfig::default()
                     },
                 ),
             ]),
@@ -162,6 +339,10 @@ mod tests {
     fn test_tournament_watcher_channel_list_provider_for_chombo_config() -> std::io::Result<()> {
         let file = NamedTempFile::new().unwrap();
         let path = file.into_temp_path();
+        let results_filter = NotificationFilter::default()
+            .with_kinds(vec![NotificationKind::Results])
+            .with_role_id(NotificationKind::Results, Some(RoleId::new(2137)));
+        let places_filter = NotificationFilter::default().with_places(vec!["Poland".to_owned()]);
 
         let config = Config {
             guilds: HashMap::from([
@@ -169,30 +350,201 @@ mod tests {
                     GuildId::new(69),
                     GuildConfig {
                         tournaments_watcher_channel_id: Some(ChannelId::new(2137)),
+                        ema_players: HashMap::from([(UserId::new(420), "10990042".to_owned())]),
+                        ..GuildConfig::default()
                     },
                 ),
                 (
                     GuildId::new(420),
                     GuildConfig {
                         tournaments_watcher_channel_id: Some(ChannelId::new(69)),
+                        tournaments_watcher_channels: HashMap::from([
+                            (ChannelId::new(69), results_filter.clone()),
+                            (ChannelId::new(70), places_filter.clone()),
+                        ]),
+                        tournaments_watcher_thread_channels: HashSet::from([ChannelId::new(70)]),
+                        tournament_rulesets: RulesetSubscription::new(vec![
+                            Ruleset::Riichi,
+                            Ruleset::Mcr,
+                        ]),
+                        language: Some(Language::Polish),
+                        ..GuildConfig::default()
                     },
                 ),
             ]),
         };
 
-        let channel_ids: Vec<ChannelId> = vec![ChannelId::new(69), ChannelId::new(2137)];
+        let channels = vec![
+            TournamentWatcherChannel::new(ChannelId::new(69))
+                .with_rulesets(RulesetSubscription::new(vec![
+                    Ruleset::Riichi,
+                    Ruleset::Mcr,
+                ]))
+                .with_filter(results_filter)
+                .with_language(Language::Polish),
+            TournamentWatcherChannel::new(ChannelId::new(70))
+                .with_rulesets(RulesetSubscription::new(vec![
+                    Ruleset::Riichi,
+                    Ruleset::Mcr,
+                ]))
+                .with_filter(places_filter)
+                .with_threads(true)
+                .with_language(Language::Polish),
+            TournamentWatcherChannel::new(ChannelId::new(2137)).with_linked_players(vec![
+                LinkedPlayer {
+                    user_id: UserId::new(420),
+                    ema_id: "10990042".to_owned(),
+                },
+            ]),
+        ];
 
         {
             let chombot_config = ChombotConfig::new(path.to_path_buf(), config);
-            let mut ids = tokio::runtime::Builder::new_current_thread()
+            let mut actual_channels = tokio::runtime::Builder::new_current_thread()
                 .build()?
                 .block_on(async { chombot_config.tournament_watcher_channels().await });
-            ids.sort();
-            assert_eq!(ids, channel_ids);
+            actual_channels.sort_by_key(|channel| channel.channel_id);
+            assert_eq!(actual_channels, channels);
         }
 
         path.close().unwrap();
 
         Ok(())
     }
+
+    #[test]
+    fn test_tournament_reminder_target_provider_for_chombot_config() -> std::io::Result<()> {
+        let config = Config {
+            guilds: HashMap::from([
+                (
+                    GuildId::new(69),
+                    GuildConfig {
+                        tournaments_watcher_channel_id: Some(ChannelId::new(2137)),
+                        tournament_reminders: true,
+                        tournament_reminders_role_id: Some(RoleId::new(420)),
+                        tournament_rulesets: RulesetSubscription::new(vec![Ruleset::Mcr]),
+                        tournament_sources: SourceSubscription::new(vec![
+                            "ema".to_owned(),
+                            "pzm".to_owned(),
+                        ]),
+                        ..GuildConfig::default()
+                    },
+                ),
+                (
+                    GuildId::new(420),
+                    GuildConfig {
+                        tournaments_watcher_channel_id: Some(ChannelId::new(69)),
+                        ..GuildConfig::default()
+                    },
+                ),
+                (
+                    GuildId::new(2137),
+                    GuildConfig {
+                        tournament_reminders: true,
+                        ..GuildConfig::default()
+                    },
+                ),
+            ]),
+        };
+
+        let chombot_config = ChombotConfig::new(PathBuf::new(), config);
+        let targets = tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async { chombot_config.tournament_reminder_targets().await });
+        assert_eq!(
+            targets,
+            vec![ReminderTarget::new(ChannelId::new(2137))
+                .with_role_id(Some(RoleId::new(420)))
+                .with_rulesets(RulesetSubscription::new(vec![Ruleset::Mcr]))
+                .with_sources(SourceSubscription::new(vec![
+                    "ema".to_owned(),
+                    "pzm".to_owned()
+                ]))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tournament_event_guild_provider_for_chombot_config() -> std::io::Result<()> {
+        let config = Config {
+            guilds: HashMap::from([
+                (
+                    GuildId::new(69),
+                    GuildConfig {
+                        tournament_events: true,
+                        tournament_rulesets: RulesetSubscription::new(vec![Ruleset::Mcr]),
+                        ..GuildConfig::default()
+                    },
+                ),
+                (
+                    GuildId::new(420),
+                    GuildConfig {
+                        tournaments_watcher_channel_id: Some(ChannelId::new(69)),
+                        ..GuildConfig::default()
+                    },
+                ),
+            ]),
+        };
+
+        let chombot_config = ChombotConfig::new(PathBuf::new(), config);
+        let guilds = tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async { chombot_config.tournament_event_guilds().await });
+        assert_eq!(
+            guilds,
+            vec![TournamentEventGuild::new(GuildId::new(69))
+                .with_rulesets(RulesetSubscription::new(vec![Ruleset::Mcr]))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ema_ranking_target_provider_for_chombot_config() -> std::io::Result<()> {
+        let config = Config {
+            guilds: HashMap::from([
+                (
+                    GuildId::new(69),
+                    GuildConfig {
+                        ema_ranking_channel_id: Some(ChannelId::new(2137)),
+                        ema_players: HashMap::from([(UserId::new(420), "10990042".to_owned())]),
+                        ..GuildConfig::default()
+                    },
+                ),
+                (
+                    GuildId::new(420),
+                    GuildConfig {
+                        ema_ranking_channel_id: Some(ChannelId::new(69)),
+                        ..GuildConfig::default()
+                    },
+                ),
+                (
+                    GuildId::new(2137),
+                    GuildConfig {
+                        ema_players: HashMap::from([(UserId::new(69), "04990013".to_owned())]),
+                        ..GuildConfig::default()
+                    },
+                ),
+            ]),
+        };
+
+        let chombot_config = ChombotConfig::new(PathBuf::new(), config);
+        let targets = tokio::runtime::Builder::new_current_thread()
+            .build()?
+            .block_on(async { chombot_config.ema_ranking_targets().await });
+        assert_eq!(
+            targets,
+            vec![EmaRankingTarget {
+                channel_id: ChannelId::new(2137),
+                players: vec![LinkedPlayer {
+                    user_id: UserId::new(420),
+                    ema_id: "10990042".to_owned(),
+                }],
+                language: Language::default(),
+            }]
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,58 @@
+use anyhow::anyhow;
+use chombot_common::ema_ranking::ranking::get_ranking;
+use chombot_common::tournaments_watcher::rulesets::Ruleset;
+
+use crate::PoiseContext;
+
+/// Link your EMA ID to get your rank changes announced (leave empty to unlink).
+#[poise::command(slash_command, guild_only)]
+pub async fn ema_link(
+    ctx: PoiseContext<'_>,
+    #[description = "EMA ID, e.g. 10990042"] ema_id: Option<String>,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+    let user = ctx.author().id;
+
+    let Some(ema_id) = ema_id.map(|ema_id| ema_id.trim().to_owned()) else {
+        let mut config = ctx.data().config.write().await;
+        config
+            .config_mut()
+            .guilds
+            .entry(guild)
+            .or_default()
+            .ema_players
+            .remove(&user);
+        drop(config);
+        ctx.say("Unlinked your EMA ID.").await?;
+        return Ok(());
+    };
+
+    ctx.defer().await?;
+    let ranking = get_ranking(Ruleset::Riichi).await?;
+    let Some(entry) = ranking.by_ema_id(&ema_id) else {
+        ctx.say(format!(
+            "There is no player with EMA ID `{ema_id}` in the Riichi ranking."
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let mut config = ctx.data().config.write().await;
+    config
+        .config_mut()
+        .guilds
+        .entry(guild)
+        .or_default()
+        .ema_players
+        .insert(user, ema_id);
+    drop(config);
+
+    ctx.say(format!(
+        "Linked your account to _{}_ (rank {}).",
+        entry.full_name(),
+        entry.rank
+    ))
+    .await?;
+
+    Ok(())
+}
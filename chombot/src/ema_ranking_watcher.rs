@@ -0,0 +1,39 @@
+use anyhow::anyhow;
+use poise::serenity_prelude::ChannelId;
+use poise::CreateReply;
+
+use crate::PoiseContext;
+
+/// Set the channel announcing the EMA rank changes of the linked members.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+pub async fn ema_ranking_watcher(
+    ctx: PoiseContext<'_>,
+    #[description = "Channel"] channel: Option<ChannelId>,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    {
+        let mut config = ctx.data().config.write().await;
+        config
+            .config_mut()
+            .guilds
+            .entry(guild)
+            .or_default()
+            .ema_ranking_channel_id = channel;
+    }
+
+    let reply_content = channel.as_ref().map_or_else(
+        || "Disabled the EMA ranking watcher.".to_owned(),
+        |channel| {
+            format!(
+                "Set the EMA ranking watcher channel to <#{}>. Members can link their EMA IDs \
+                 with `/ema_link`.",
+                channel.get()
+            )
+        },
+    );
+    ctx.send(CreateReply::default().content(reply_content))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anyhow::anyhow;
+use chombot_common::i18n::Language;
+
+use crate::PoiseContext;
+
+/// Choose the language of the bot messages in this server.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    name_localized("pl", "język"),
+    description_localized("pl", "Wybierz język wiadomości bota na tym serwerze.")
+)]
+pub async fn language(
+    ctx: PoiseContext<'_>,
+    #[description = "Language of the bot messages"]
+    #[name_localized("pl", "język")]
+    #[description_localized("pl", "Język wiadomości bota")]
+    language: Language,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let mut config = ctx.data().config.write().await;
+    {
+        let mut config_guard = config.config_mut();
+        config_guard.guilds.entry(guild).or_default().language = Some(language);
+    }
+    drop(config);
+
+    ctx.say(language.messages().language_set).await?;
+
+    Ok(())
+}
@@ -5,45 +5,189 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::missing_panics_doc)]
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use anyhow::Error;
+use chombot_common::bot_status::BotStatus;
 use chombot_common::chombot::ChombotBase;
+use chombot_common::data_watcher::registry::WatcherRegistry;
+use chombot_common::i18n::Language;
+use chombot_common::message_sink::DiscordMessageSink;
+use chombot_common::slash_commands::ema::ema;
 use chombot_common::slash_commands::hand::hand;
-use chombot_common::slash_commands::score::score;
-use chombot_common::{start_tournaments_watcher, ChombotPoiseUserData};
+use chombot_common::slash_commands::score::{score, score_reverse};
+use chombot_common::slash_commands::status::status;
+use chombot_common::slash_commands::tournaments::tournaments;
+use chombot_common::slash_commands::watchers::watchers;
+use chombot_common::tournaments_watcher::details::TournamentDetailsScraper;
+use chombot_common::tournaments_watcher::events::{
+    DiscordScheduledEventSink, TournamentEventsNotifier,
+};
+use chombot_common::tournaments_watcher::history::TournamentHistory;
+use chombot_common::tournaments_watcher::rulesets::Ruleset;
+use chombot_common::tournaments_watcher::sources::TournamentSources;
+use chombot_common::tournaments_watcher::threads::DiscordThreadSink;
+use chombot_common::{
+    spawn_shutdown_handler, start_ema_ranking_watcher, start_tournament_reminders_watcher,
+    start_tournaments_ics_server, start_tournaments_watcher, ChombotPoiseUserData,
+    TournamentsWatcherOptions,
+};
 use clap::Parser;
 use log::{error, info, LevelFilter};
-use poise::serenity_prelude::{ClientBuilder, GatewayIntents};
+use poise::serenity_prelude::{ClientBuilder, GatewayIntents, GuildId};
 use poise::{Command, Context, Framework, FrameworkOptions};
 use tokio::sync::RwLock;
 
 use crate::args::Arguments;
 use crate::config::ChombotConfig;
+use crate::ema_link::ema_link;
+use crate::ema_ranking_watcher::ema_ranking_watcher;
+use crate::language::language;
+use crate::tournament_events::tournament_events;
+use crate::tournament_notifications::tournament_notifications;
+use crate::tournament_reminders::tournament_reminders;
+use crate::tournament_rulesets::tournament_rulesets;
+use crate::tournament_sources::tournament_sources;
 use crate::tournament_watcher::tournament_watcher;
 
 mod args;
 mod config;
+mod ema_link;
+mod ema_ranking_watcher;
+mod language;
+mod tournament_events;
+mod tournament_notifications;
+mod tournament_reminders;
+mod tournament_rulesets;
+mod tournament_sources;
 mod tournament_watcher;
 
 pub struct PoiseUserData {
     pub chombot: ChombotBase,
     pub config: Arc<RwLock<ChombotConfig>>,
+    pub bot_status: BotStatus,
+    pub watcher_registry: Arc<WatcherRegistry>,
+    pub tournament_details_scraper: Option<Arc<TournamentDetailsScraper>>,
+    pub tournament_sources: TournamentSources,
+    pub tournament_history: Option<TournamentHistory>,
 }
 
 impl ChombotPoiseUserData for PoiseUserData {
     fn chombot(&self) -> &ChombotBase {
         &self.chombot
     }
+
+    fn bot_status(&self) -> &BotStatus {
+        &self.bot_status
+    }
+
+    fn watcher_registry(&self) -> &WatcherRegistry {
+        &self.watcher_registry
+    }
+
+    fn tournament_details_scraper(&self) -> Option<&TournamentDetailsScraper> {
+        self.tournament_details_scraper.as_deref()
+    }
+
+    fn tournament_sources(&self) -> &TournamentSources {
+        &self.tournament_sources
+    }
+
+    fn tournament_history(&self) -> Option<&TournamentHistory> {
+        self.tournament_history.as_ref()
+    }
+
+    async fn guild_language(&self, guild_id: Option<GuildId>) -> Option<Language> {
+        self.config
+            .read()
+            .await
+            .config()
+            .guilds
+            .get(&guild_id?)?
+            .language
+    }
 }
 
 pub type PoiseContext<'a> = Context<'a, PoiseUserData, anyhow::Error>;
 
 fn get_command_list() -> Vec<Command<PoiseUserData, Error>> {
-    vec![hand(), score(), tournament_watcher()]
+    vec![
+        ema(),
+        ema_link(),
+        ema_ranking_watcher(),
+        hand(),
+        language(),
+        score(),
+        score_reverse(),
+        status(),
+        tournament_events(),
+        tournament_notifications(),
+        tournament_reminders(),
+        tournament_rulesets(),
+        tournament_sources(),
+        tournament_watcher(),
+        tournaments(),
+        watchers(),
+    ]
+}
+
+fn get_enabled_features(args: &Arguments) -> Vec<&'static str> {
+    [
+        (true, "tournaments watcher"),
+        (true, "tournament reminders"),
+        (true, "tournament results"),
+        (true, "tournament events"),
+        (true, "EMA ranking watcher"),
+        (args.feature_tournament_details, "tournament details"),
+        (
+            !args.tournament_sources.tournament_feeds.is_empty(),
+            "tournament feeds",
+        ),
+        (
+            args.tournaments_ics.tournaments_ics_addr.is_some(),
+            "tournament calendar",
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect()
 }
 
 const CONFIG_DATA_PATH: &str = "data.toml";
+const TOURNAMENTS_SNAPSHOT_PATH: &str = "tournaments.json";
+const TOURNAMENTS_ARCHIVE_PATH: &str = "tournaments_archive.jsonl";
+const TOURNAMENT_THREADS_PATH: &str = "tournament_threads.json";
+
+fn get_tournaments_watcher_options(
+    args: &Arguments,
+    archive_path: PathBuf,
+    details_scraper: Option<Arc<TournamentDetailsScraper>>,
+    sources: TournamentSources,
+) -> TournamentsWatcherOptions {
+    TournamentsWatcherOptions {
+        snapshot_path: Some(TOURNAMENTS_SNAPSHOT_PATH.into()),
+        archive_path: Some(archive_path),
+        threads_path: Some(TOURNAMENT_THREADS_PATH.into()),
+        schedule: (&args.tournaments_watcher_schedule).into(),
+        details_scraper,
+        results_announcements: true,
+        sources,
+        removal_grace_fetches: args
+            .tournaments_watcher_schedule
+            .tournaments_watcher_removal_grace_fetches,
+        events_notifier: None,
+        thread_sink: None,
+        webhook_notifier: args
+            .tournaments_webhooks
+            .webhook_notifier()
+            .expect("Could not create the tournaments webhook notifier"),
+        http_notifier: args
+            .tournaments_webhooks
+            .http_notifier()
+            .expect("Could not create the tournaments HTTP notifier"),
+    }
+}
 
 #[tokio::main]
 async fn main() {
@@ -55,6 +199,29 @@ async fn main() {
     let chombot = ChombotBase::new();
     let config = ChombotConfig::load(CONFIG_DATA_PATH.into()).expect("Could not load config");
     let config_ref = Arc::new(RwLock::new(config));
+    let bot_status = BotStatus::new(get_enabled_features(&args));
+    let watcher_registry = Arc::new(WatcherRegistry::new());
+    let watcher_registry_ref = watcher_registry.clone();
+    let tournament_details_scraper = args
+        .feature_tournament_details
+        .then(|| Arc::new(TournamentDetailsScraper::new()));
+    let tournament_sources = TournamentSources::from(&args.tournament_sources);
+    let tournaments_archive_path = args
+        .tournaments_watcher_archive_path
+        .clone()
+        .unwrap_or_else(|| TOURNAMENTS_ARCHIVE_PATH.into());
+    let tournament_history = TournamentHistory::new(tournaments_archive_path.clone());
+    let mut tournaments_watcher_options = get_tournaments_watcher_options(
+        &args,
+        tournaments_archive_path,
+        tournament_details_scraper.clone(),
+        tournament_sources.clone(),
+    );
+    if let Some(addr) = args.tournaments_ics.tournaments_ics_addr {
+        start_tournaments_ics_server(addr, tournament_sources.clone())
+            .await
+            .expect("Could not start the tournament calendar server");
+    }
 
     let framework = Framework::builder()
         .options(FrameworkOptions {
@@ -63,12 +230,45 @@ async fn main() {
         })
         .setup(move |ctx, ready, framework| {
             Box::pin(async move {
-                start_tournaments_watcher(config_ref.clone(), ctx.clone());
+                tournaments_watcher_options.events_notifier = Some(TournamentEventsNotifier::new(
+                    Arc::new(config_ref.clone()),
+                    Arc::new(DiscordScheduledEventSink::from(ctx)),
+                ));
+                tournaments_watcher_options.thread_sink =
+                    Some(Arc::new(DiscordThreadSink::from(ctx)));
+                let message_sink = Arc::new(DiscordMessageSink::from(ctx));
+                start_tournaments_watcher(
+                    &watcher_registry_ref,
+                    config_ref.clone(),
+                    tournaments_watcher_options,
+                    message_sink.clone(),
+                )
+                .await;
+                start_tournament_reminders_watcher(
+                    &watcher_registry_ref,
+                    config_ref.clone(),
+                    (&args.tournament_reminders).into(),
+                    tournament_sources.clone(),
+                    message_sink.clone(),
+                )
+                .await;
+                start_ema_ranking_watcher(
+                    &watcher_registry_ref,
+                    config_ref.clone(),
+                    Ruleset::Riichi,
+                    message_sink,
+                )
+                .await;
                 poise::builtins::register_globally(ctx, &framework.options().commands).await?;
                 info!("{} is connected!", ready.user.name);
                 Ok(PoiseUserData {
                     chombot,
                     config: config_ref,
+                    bot_status,
+                    watcher_registry: watcher_registry_ref,
+                    tournament_details_scraper,
+                    tournament_sources,
+                    tournament_history: Some(tournament_history),
                 })
             })
         })
@@ -79,7 +279,10 @@ async fn main() {
         .await
         .expect("Could not create client");
 
+    spawn_shutdown_handler(client.shard_manager.clone());
+
     if let Err(why) = client.start().await {
         error!("Client error: {why:?}");
     }
+    watcher_registry.shutdown().await;
 }
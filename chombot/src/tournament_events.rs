@@ -0,0 +1,32 @@
+use anyhow::anyhow;
+
+use crate::PoiseContext;
+
+/// Enable or disable listing the tournaments as the server events.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+pub async fn tournament_events(
+    ctx: PoiseContext<'_>,
+    #[description = "Whether the upcoming tournaments should be listed as the server events"]
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let mut config = ctx.data().config.write().await;
+    config
+        .config_mut()
+        .guilds
+        .entry(guild)
+        .or_default()
+        .tournament_events = enabled;
+    drop(config);
+
+    let reply_content = if enabled {
+        "Enabled the tournament events. New tournaments will be added to the server events; \
+         the bot needs the Manage Events permission."
+    } else {
+        "Disabled the tournament events."
+    };
+    ctx.say(reply_content).await?;
+
+    Ok(())
+}
@@ -0,0 +1,280 @@
+use std::fmt::Write;
+
+use anyhow::anyhow;
+use chombot_common::tournaments_watcher::filters::{NotificationFilter, NotificationKind};
+use itertools::Itertools;
+use poise::serenity_prelude::{ChannelId, CreateAllowedMentions, RoleId};
+use poise::CreateReply;
+
+use crate::config::GuildConfig;
+use crate::PoiseContext;
+
+/// Choose which tournament updates are posted to which channels.
+#[poise::command(
+    slash_command,
+    guild_only,
+    required_permissions = "ADMINISTRATOR",
+    subcommands("kind", "places", "role", "threads", "remove", "list")
+)]
+pub async fn tournament_notifications(_: PoiseContext<'_>) -> anyhow::Result<()> {
+    #![allow(clippy::unused_async)]
+    Ok(())
+}
+
+/// Choose whether given kind of tournament updates is posted to a channel.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+async fn kind(
+    ctx: PoiseContext<'_>,
+    #[description = "Channel"] channel: ChannelId,
+    #[description = "Kind of the tournament updates"] kind: NotificationKind,
+    #[description = "Whether to post this kind of updates"] enabled: bool,
+) -> anyhow::Result<()> {
+    let mut kinds = current_filter(ctx, channel).await?.enabled_kinds();
+    kinds.retain(|&enabled_kind| enabled_kind != kind);
+    if enabled {
+        kinds.push(kind);
+    }
+    if kinds.is_empty() {
+        ctx.say(format!(
+            "Please keep at least one kind of updates enabled; use `/tournament_notifications \
+             remove` to stop posting them to <#{channel}>."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    update_filter(ctx, channel, |filter| filter.with_kinds(kinds)).await?;
+    reply(ctx, channel).await
+}
+
+/// Post only the updates of the tournaments in given places to a channel.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+async fn places(
+    ctx: PoiseContext<'_>,
+    #[description = "Channel"] channel: ChannelId,
+    #[description = "Comma-separated countries or cities, e.g. \"Poland, Berlin\" (default: all)"]
+    places: Option<String>,
+) -> anyhow::Result<()> {
+    let places = places
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|place| !place.is_empty())
+        .map(ToOwned::to_owned)
+        .collect();
+
+    update_filter(ctx, channel, |filter| filter.with_places(places)).await?;
+    reply(ctx, channel).await
+}
+
+/// Choose the role pinged about given kind of tournament updates in a channel.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+async fn role(
+    ctx: PoiseContext<'_>,
+    #[description = "Channel"] channel: ChannelId,
+    #[description = "Kind of the tournament updates"] kind: NotificationKind,
+    #[description = "Role to ping (default: none)"] role: Option<RoleId>,
+) -> anyhow::Result<()> {
+    update_filter(ctx, channel, |filter| filter.with_role_id(kind, role)).await?;
+    reply(ctx, channel).await
+}
+
+/// Choose whether the updates of every tournament are posted in its own thread.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+async fn threads(
+    ctx: PoiseContext<'_>,
+    #[description = "Channel"] channel: ChannelId,
+    #[description = "Whether to post in the tournament threads (always the case in forums)"]
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let mut config = ctx.data().config.write().await;
+    {
+        let mut config_guard = config.config_mut();
+        let guild_config = config_guard.guilds.entry(guild).or_default();
+        if enabled {
+            guild_config
+                .tournaments_watcher_thread_channels
+                .insert(channel);
+        } else {
+            guild_config
+                .tournaments_watcher_thread_channels
+                .remove(&channel);
+        }
+        guild_config
+            .tournaments_watcher_channels
+            .entry(channel)
+            .or_default();
+    }
+    drop(config);
+
+    reply(ctx, channel).await
+}
+
+/// Stop posting the tournament updates to a channel, or reset its filters.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+async fn remove(
+    ctx: PoiseContext<'_>,
+    #[description = "Channel"] channel: ChannelId,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let mut config = ctx.data().config.write().await;
+    let is_watcher_channel = {
+        let mut config_guard = config.config_mut();
+        let guild_config = config_guard.guilds.entry(guild).or_default();
+        guild_config.tournaments_watcher_channels.remove(&channel);
+        guild_config
+            .tournaments_watcher_thread_channels
+            .remove(&channel);
+        guild_config.tournaments_watcher_channel_id == Some(channel)
+    };
+    drop(config);
+
+    let reply_content = if is_watcher_channel {
+        format!(
+            "All the tournament updates will be posted to <#{channel}>. Use \
+             `/tournament_watcher` to change the tournament watcher channel."
+        )
+    } else {
+        format!("Tournament updates will no longer be posted to <#{channel}>.")
+    };
+    ctx.say(reply_content).await?;
+
+    Ok(())
+}
+
+/// Show the channels the tournament updates are posted to.
+#[poise::command(slash_command, guild_only)]
+async fn list(ctx: PoiseContext<'_>) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let guild_config = ctx
+        .data()
+        .config
+        .read()
+        .await
+        .config()
+        .guilds
+        .get(&guild)
+        .cloned()
+        .unwrap_or_default();
+    let channels = guild_config.tournament_notification_channels();
+    let reply_content = if channels.is_empty() {
+        "Tournament updates are not posted anywhere; use `/tournament_watcher` to choose the \
+         channel."
+            .to_owned()
+    } else {
+        channels
+            .keys()
+            .sorted()
+            .map(|&channel_id| describe_channel(&guild_config, channel_id))
+            .join("\n")
+    };
+    ctx.send(
+        CreateReply::default()
+            .content(reply_content)
+            .allowed_mentions(CreateAllowedMentions::new().empty_roles()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn current_filter(
+    ctx: PoiseContext<'_>,
+    channel: ChannelId,
+) -> anyhow::Result<NotificationFilter> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    Ok(ctx
+        .data()
+        .config
+        .read()
+        .await
+        .config()
+        .guilds
+        .get(&guild)
+        .and_then(|guild_config| guild_config.tournaments_watcher_channels.get(&channel))
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Updates the filter of the channel, adding the channel to the ones the
+/// tournament updates are posted to if needed.
+async fn update_filter(
+    ctx: PoiseContext<'_>,
+    channel: ChannelId,
+    update: impl FnOnce(NotificationFilter) -> NotificationFilter + Send,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let mut config = ctx.data().config.write().await;
+    {
+        let mut config_guard = config.config_mut();
+        let filter = config_guard
+            .guilds
+            .entry(guild)
+            .or_default()
+            .tournaments_watcher_channels
+            .entry(channel)
+            .or_default();
+        *filter = update(filter.clone());
+    }
+    drop(config);
+
+    Ok(())
+}
+
+/// Replies with the current settings of the channel.
+async fn reply(ctx: PoiseContext<'_>, channel: ChannelId) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let guild_config = ctx
+        .data()
+        .config
+        .read()
+        .await
+        .config()
+        .guilds
+        .get(&guild)
+        .cloned()
+        .unwrap_or_default();
+    ctx.send(
+        CreateReply::default()
+            .content(describe_channel(&guild_config, channel))
+            .allowed_mentions(CreateAllowedMentions::new().empty_roles()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+fn describe_channel(guild_config: &GuildConfig, channel: ChannelId) -> String {
+    let filter = guild_config
+        .tournaments_watcher_channels
+        .get(&channel)
+        .cloned()
+        .unwrap_or_default();
+    let kinds = if filter.kinds.is_empty() {
+        "all updates".to_owned()
+    } else {
+        filter.kinds.iter().join(", ")
+    };
+    let mut str = format!("* <#{channel}>: {kinds}");
+    if !filter.places.is_empty() {
+        let _ = write!(str, " in {}", filter.places.join(", "));
+    }
+    if guild_config
+        .tournaments_watcher_thread_channels
+        .contains(&channel)
+    {
+        str += ", one thread per tournament";
+    }
+    for (kind, role_id) in &filter.role_ids {
+        let _ = write!(str, "; {kind}: <@&{role_id}>");
+    }
+
+    str
+}
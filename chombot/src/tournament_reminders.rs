@@ -0,0 +1,45 @@
+use anyhow::anyhow;
+use poise::serenity_prelude::{CreateAllowedMentions, RoleId};
+use poise::CreateReply;
+
+use crate::PoiseContext;
+
+/// Enable or disable the tournament reminders in the tournament watcher
+/// channel.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+pub async fn tournament_reminders(
+    ctx: PoiseContext<'_>,
+    #[description = "Whether the reminders should be sent"] enabled: bool,
+    #[description = "Role to ping in the reminders"] role: Option<RoleId>,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let mut config = ctx.data().config.write().await;
+    let has_channel = {
+        let mut config_guard = config.config_mut();
+        let guild_config = config_guard.guilds.entry(guild).or_default();
+        guild_config.tournament_reminders = enabled;
+        guild_config.tournament_reminders_role_id = role;
+        guild_config.tournaments_watcher_channel_id.is_some()
+    };
+    drop(config);
+
+    let reply_content = match (enabled, role) {
+        (false, _) => "Disabled the tournament reminders.".to_owned(),
+        (true, None) => "Enabled the tournament reminders.".to_owned(),
+        (true, Some(role)) => format!("Enabled the tournament reminders, pinging <@&{role}>."),
+    };
+    let reply_content = if enabled && !has_channel {
+        format!("{reply_content} Please set the tournament watcher channel to receive them.")
+    } else {
+        reply_content
+    };
+    ctx.send(
+        CreateReply::default()
+            .content(reply_content)
+            .allowed_mentions(CreateAllowedMentions::new().empty_roles()),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anyhow::anyhow;
+use chombot_common::tournaments_watcher::rulesets::{Ruleset, RulesetSubscription};
+
+use crate::PoiseContext;
+
+/// Choose the rulesets of the tournaments the guild is notified about.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+pub async fn tournament_rulesets(
+    ctx: PoiseContext<'_>,
+    #[description = "Whether to notify about Riichi tournaments"] riichi: bool,
+    #[description = "Whether to notify about MCR tournaments"] mcr: bool,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let rulesets: Vec<_> = [(Ruleset::Riichi, riichi), (Ruleset::Mcr, mcr)]
+        .into_iter()
+        .filter_map(|(ruleset, enabled)| enabled.then_some(ruleset))
+        .collect();
+    if rulesets.is_empty() {
+        ctx.say("Please choose at least one ruleset.").await?;
+        return Ok(());
+    }
+    let subscription = RulesetSubscription::new(rulesets);
+
+    let mut config = ctx.data().config.write().await;
+    config
+        .config_mut()
+        .guilds
+        .entry(guild)
+        .or_default()
+        .tournament_rulesets = subscription.clone();
+    drop(config);
+
+    ctx.say(format!(
+        "Tournament notifications will include the following rulesets: {subscription}."
+    ))
+    .await?;
+
+    Ok(())
+}
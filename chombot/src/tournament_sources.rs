@@ -0,0 +1,66 @@
+use anyhow::anyhow;
+use chombot_common::slash_commands::tournaments::autocomplete_tournament_source;
+use chombot_common::tournaments_watcher::sources::SourceSubscription;
+use chombot_common::ChombotPoiseUserData;
+
+use crate::PoiseContext;
+
+/// Choose whether the guild is notified about the tournaments from given
+/// source.
+#[poise::command(slash_command, guild_only, required_permissions = "ADMINISTRATOR")]
+pub async fn tournament_sources(
+    ctx: PoiseContext<'_>,
+    #[description = "Tournament source ID, e.g. \"ema\""]
+    #[autocomplete = "autocomplete_tournament_source"]
+    source: String,
+    #[description = "Whether to notify about the tournaments from this source"] enabled: bool,
+) -> anyhow::Result<()> {
+    let guild = ctx.guild_id().ok_or_else(|| anyhow!("Guild ID is None"))?;
+
+    let source = source.trim().to_owned();
+    if !ctx.data().tournament_sources().contains(&source) {
+        let known = ctx.data().tournament_sources().ids().join(", ");
+        ctx.say(format!(
+            "Unknown tournament source `{source}`; available sources: {known}."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let mut config = ctx.data().config.write().await;
+    let mut ids: Vec<_> = config
+        .config()
+        .guilds
+        .get(&guild)
+        .map(|guild_config| guild_config.tournament_sources.clone())
+        .unwrap_or_default()
+        .ids()
+        .iter()
+        .filter(|&id| *id != source)
+        .cloned()
+        .collect();
+    if enabled {
+        ids.push(source);
+    }
+    if ids.is_empty() {
+        drop(config);
+        ctx.say("Please keep at least one tournament source enabled.")
+            .await?;
+        return Ok(());
+    }
+    let subscription = SourceSubscription::new(ids);
+    config
+        .config_mut()
+        .guilds
+        .entry(guild)
+        .or_default()
+        .tournament_sources = subscription.clone();
+    drop(config);
+
+    ctx.say(format!(
+        "Tournament notifications will include the following sources: {subscription}."
+    ))
+    .await?;
+
+    Ok(())
+}